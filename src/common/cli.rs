@@ -1,5 +1,6 @@
-use crate::common::types::{DatabaseType, FileExtension, LogLevel};
-use clap::Parser;
+use crate::common::types::{DatabaseType, FileExtension, LogLevel, OutputFormat};
+use crate::core::offline::snapshot::DEFAULT_SNAPSHOT_PATH;
+use clap::{Parser, Subcommand};
 use std::fmt;
 
 impl fmt::Display for FileExtension {
@@ -12,74 +13,153 @@ impl fmt::Display for FileExtension {
       FileExtension::Cts => ".cts".to_string(),
       FileExtension::Mjs => ".mjs".to_string(),
       FileExtension::Cjs => ".cjs".to_string(),
+      FileExtension::Tsx => ".tsx".to_string(),
+      FileExtension::Jsx => ".jsx".to_string(),
+      FileExtension::Vue => ".vue".to_string(),
+      FileExtension::Svelte => ".svelte".to_string(),
+      FileExtension::Astro => ".astro".to_string(),
     };
     write!(f, "{extension}")
   }
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+  /// Writes the schema of every table the project's queries touch into a snapshot file, used by --offline
+  Snapshot {
+    /// Path to the Typescript or Javascript project
+    path: std::path::PathBuf,
+  },
+  /// Runs a language server over stdio that publishes the errors of SQLs as a file is saved and shows their types on hover
+  Lsp,
+}
+
 #[derive(Parser, Debug, Clone)]
-#[clap(author, version, about)]
+#[clap(author, version, about, subcommand_negates_reqs = true)]
 pub struct Cli {
+  #[clap(subcommand)]
+  pub command: Option<Command>,
+
   /// Path to the Typescript or Javascript project
-  pub path: std::path::PathBuf,
+  #[clap(required = true)]
+  path: Option<std::path::PathBuf>,
 
   /// file extensions
-  #[clap(value_enum, long)]
+  #[clap(value_enum, long, global = true)]
   pub ext: Vec<FileExtension>,
 
   /// Type of primary database to connect
-  #[clap(value_enum, long)]
+  #[clap(value_enum, long, global = true)]
   pub db_type: Option<DatabaseType>,
 
   /// Primary DB host
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_host: Option<String>,
 
   /// Primary DB Port
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_port: Option<u16>,
 
   /// Primary DB user
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_user: Option<String>,
 
   /// Primary DB pass
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_pass: Option<String>,
 
   /// Primary DB database name
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_name: Option<String>,
 
   /// Custom database connection URL (overrides individual connection parameters if provided)
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub db_url: Option<String>,
 
   /// PostgreSQL schema search path (default is "$user,public") https://www.postgresql.org/docs/current/ddl-schemas.html#DDL-SCHEMAS-PATH
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub pg_search_path: Option<String>,
 
   /// Folder paths to ignore
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub ignore: Vec<String>,
 
   /// Path to the file based configuration
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub config: Option<std::path::PathBuf>,
 
   /// generate types of raw SQLs using default configuration
-  #[clap(long, short)]
+  #[clap(long, short, global = true)]
   pub generate_types: bool,
 
   /// generates types in a target file path (example: src/app/queries.ts)
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub generate_path: Option<std::path::PathBuf>,
 
   /// log level to be used for the CLI debug > info > warning > error
-  #[clap(value_enum, long)]
+  #[clap(value_enum, long, global = true)]
   pub log_level: Option<LogLevel>,
 
   /// Dotfile name (example: .env or .env.dev) [default: .env or environment]
-  #[clap(long)]
+  #[clap(long, global = true)]
   pub env: Option<String>,
+
+  /// Validate SQLs and generate types from the schema snapshot instead of connecting to the databases
+  #[clap(long, global = true)]
+  pub offline: bool,
+
+  /// Builds the schema from migration DDL files (a .sql file or a folder of them) instead of connecting to the databases
+  #[clap(long, global = true, conflicts_with = "offline")]
+  pub schema_from: Option<std::path::PathBuf>,
+
+  /// Path to the schema snapshot written by `sqlx-ts snapshot` [default: .sqlxsnapshot.json]
+  #[clap(long, global = true)]
+  pub snapshot_path: Option<std::path::PathBuf>,
+
+  /// Stops at the first file with failing SQLs instead of checking every file
+  #[clap(long, global = true)]
+  pub fail_fast: bool,
+
+  /// Keeps running and re-checks the files that change, rewriting only their types
+  #[clap(long)]
+  pub watch: bool,
+
+  /// Compares the types that would be generated with the files on disk without writing them, fails if any file is out of date
+  #[clap(long, conflicts_with = "watch")]
+  pub check: bool,
+
+  /// Reports the generated files that would be written or removed without touching them
+  #[clap(long, conflicts_with_all = ["watch", "check"])]
+  pub dry_run: bool,
+
+  /// Format of the reported errors, json, sarif and github print every error to stdout once all files are checked
+  #[clap(value_enum, long, global = true, default_value_t = OutputFormat::Human)]
+  pub format: OutputFormat,
+}
+
+impl Cli {
+  /// Path to the Typescript or Javascript project, either of the main command or the subcommand
+  /// The language server is the only command clap lets run without one
+  pub fn path(&self) -> Option<&std::path::PathBuf> {
+    match &self.command {
+      Some(Command::Snapshot { path }) => Some(path),
+      Some(Command::Lsp) => None,
+      None => self.path.as_ref(),
+    }
+  }
+
+  pub fn is_snapshot(&self) -> bool {
+    matches!(self.command, Some(Command::Snapshot { .. }))
+  }
+
+  pub fn is_lsp(&self) -> bool {
+    matches!(self.command, Some(Command::Lsp))
+  }
+
+  pub fn snapshot_path(&self) -> std::path::PathBuf {
+    self
+      .snapshot_path
+      .clone()
+      .unwrap_or_else(|| std::path::PathBuf::from(DEFAULT_SNAPSHOT_PATH))
+  }
 }
@@ -1,7 +1,9 @@
 use super::types::NamingConvention;
 use crate::common::dotenv::Dotenv;
 use crate::common::lazy::CLI_ARGS;
-use crate::common::types::{DatabaseType, LogLevel};
+use crate::common::types::{DatabaseType, LogLevel, TypeInference};
+use crate::common::SQL;
+use color_eyre::eyre::{eyre, Result};
 use colored::Colorize;
 use regex::Regex;
 use serde;
@@ -18,6 +20,8 @@ pub struct SqlxConfig {
   #[serde(rename = "generateTypes")]
   pub generate_types: Option<GenerateTypesConfig>,
   pub connections: HashMap<String, DbConnectionConfig>,
+  #[serde(default)]
+  pub tags: Vec<TagConfig>,
 }
 
 pub const fn default_bool<const V: bool>() -> bool {
@@ -58,6 +62,22 @@ pub struct DbConnectionConfig {
   pub pool_size: u32,
   #[serde(rename = "CONNECTION_TIMEOUT", default = "default_connection_timeout")]
   pub connection_timeout: u64,
+  #[serde(rename = "TYPE_INFERENCE", default)]
+  pub type_inference: TypeInference,
+  #[serde(rename = "INIT_SQL", default)]
+  pub init_sql: Vec<String>,
+}
+
+/// A tagged template function of a SQL library whose queries should be checked as well as sqlx-ts' own `sql` tag
+/// e.g. `{ "module": "@prisma/client", "name": "Prisma.sql" }`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TagConfig {
+  /// The module the tag is imported from, as it's written in the import statement
+  pub module: String,
+  /// Name of the export, or the export and its property for member tags such as `Prisma.sql`
+  pub name: String,
+  /// Name of the connection the queries of the tag run against, unless they have their own `@db` annotation
+  pub connection: Option<String>,
 }
 
 fn default_pool_size() -> u32 {
@@ -78,6 +98,7 @@ pub struct Config {
   pub connections: HashMap<String, DbConnectionConfig>,
   pub ignore_patterns: Vec<String>,
   pub log_level: LogLevel,
+  pub tags: Vec<TagConfig>,
 }
 
 impl Default for Config {
@@ -93,21 +114,44 @@ impl Config {
     let default_config_path = PathBuf::from_str(".sqlxrc.json").unwrap();
     let default_ignore_config_path = PathBuf::from_str(".sqlxignore").unwrap();
     let file_config_path = &CLI_ARGS.config.clone().unwrap_or(default_config_path);
-    let connections = Self::build_configs(&dotenv, file_config_path);
-    let generate_types_config = Self::generate_types_config(file_config_path);
+    let file_config = Self::get_file_config(file_config_path).unwrap_or_else(|err| {
+      Self::error(err.to_string().as_str());
+      std::process::exit(1)
+    });
+    let file_config = file_config.as_ref();
+
+    let connections = Self::build_configs(&dotenv, file_config_path, file_config);
+    let generate_types_config = Self::generate_types_config(file_config);
 
     let generate_types_config =
       generate_types_config.and_then(|config| if config.enabled { Some(config) } else { None });
     let ignore_patterns = Self::get_ignore_patterns(&default_ignore_config_path);
-    let log_level = Self::get_log_level(file_config_path);
+    let log_level = Self::get_log_level(file_config);
+    let tags = Self::get_tags(file_config);
     Config {
       connections,
       generate_types_config,
       ignore_patterns,
       log_level,
+      tags,
     }
   }
 
+  /// Reads the file based configuration, None when there is no file to read
+  fn get_file_config(file_config_path: &PathBuf) -> Result<Option<SqlxConfig>> {
+    let Ok(file_based_config) = fs::read_to_string(file_config_path) else {
+      return Ok(None);
+    };
+
+    serde_json::from_str::<SqlxConfig>(file_based_config.as_str())
+      .map(Some)
+      .map_err(|err| {
+        eyre!(
+          "Empty or invalid JSON provided for file based configuration - config file: {file_config_path:?}, error: {err}"
+        )
+      })
+  }
+
   fn get_ignore_patterns(ignore_config_path: &PathBuf) -> Vec<String> {
     let mut base_ignore_patterns = vec!["*.queries.ts".to_string(), "*.queries.js".to_string()];
     base_ignore_patterns.extend(CLI_ARGS.ignore.clone());
@@ -135,23 +179,20 @@ impl Config {
   /// Retrieves the configuration required for generating typescript interface
   /// If there is CLI_ARGS.generate_types set already, it would prioritise using CLI_ARGS
   #[allow(deprecated)]
-  fn generate_types_config(file_config_path: &PathBuf) -> Option<GenerateTypesConfig> {
-    let file_based_config = fs::read_to_string(file_config_path);
-    let file_based_config = &file_based_config.map(|f| serde_json::from_str::<SqlxConfig>(f.as_str()).unwrap());
-
+  fn generate_types_config(file_based_config: Option<&SqlxConfig>) -> Option<GenerateTypesConfig> {
     let cli_default = GenerateTypesConfig {
-      enabled: CLI_ARGS.generate_types,
+      enabled: CLI_ARGS.generate_types || CLI_ARGS.check || CLI_ARGS.dry_run,
       convert_to_camel_case_column_name: false,
       column_naming_convention: None,
       generate_path: CLI_ARGS.generate_path.to_owned(),
     };
 
-    if let Ok(file_based_config) = &file_based_config {
+    if let Some(file_based_config) = file_based_config {
       if let Some(generate_types) = &file_based_config.generate_types {
         let generate_types = generate_types.clone();
         // If the file config is provided, we will return the file config's default values but CLI config as priority
         return Some(GenerateTypesConfig {
-          enabled: CLI_ARGS.generate_types || generate_types.enabled,
+          enabled: CLI_ARGS.generate_types || CLI_ARGS.check || CLI_ARGS.dry_run || generate_types.enabled,
           generate_path: generate_types.generate_path.or(CLI_ARGS.generate_path.to_owned()),
           column_naming_convention: generate_types.column_naming_convention,
           convert_to_camel_case_column_name: generate_types.convert_to_camel_case_column_name,
@@ -165,34 +206,26 @@ impl Config {
   }
 
   /// Build the initial connection config to be used as a HashMap
-  fn build_configs(dotenv: &Dotenv, file_config_path: &PathBuf) -> HashMap<String, DbConnectionConfig> {
-    let file_based_config = fs::read_to_string(file_config_path);
-    let file_based_config = &file_based_config.map(|f| {
-      let result = serde_json::from_str::<SqlxConfig>(f.as_str());
-
-      if result.is_err() {
-        panic!(
-          "{}",
-          format!(
-            "Empty or invalid JSON provided for file based configuration - config file: {file_config_path:?}, error: {result:?}",
-          )
-        );
-      }
-
-      result.unwrap()
-    });
-
+  fn build_configs(
+    dotenv: &Dotenv,
+    file_config_path: &PathBuf,
+    file_based_config: Option<&SqlxConfig>,
+  ) -> HashMap<String, DbConnectionConfig> {
     let connections = &mut file_based_config
-      .as_ref()
       .map(|config| config.connections.clone())
-      .unwrap_or_else(|_| {
+      .unwrap_or_else(|| {
         Self::warning(
           format!("Failed to read config file from the path: {file_config_path:?}").as_str(),
-          Self::get_log_level(file_config_path),
+          Self::get_log_level(file_based_config),
         );
         Default::default()
       });
 
+    // The default connection is validated once CLI args and environment variables are applied to it
+    for (name, connection) in connections.iter().filter(|(name, _)| *name != "default") {
+      Self::validate_sqlite_db_url(name, &connection.db_type, &connection.db_url);
+    }
+
     connections.insert(
       "default".to_string(),
       Self::get_default_connection_config(dotenv, &connections.get("default")),
@@ -225,6 +258,7 @@ impl Config {
       .clone()
       .or_else(|| dotenv.db_url.clone())
       .or_else(|| default_config.map(|x| x.db_url.clone()).flatten());
+    Self::validate_sqlite_db_url("default", db_type, db_url);
 
     let db_host_chain = || {
       CLI_ARGS
@@ -316,6 +350,10 @@ impl Config {
       .or_else(|| Some(default_connection_timeout()))
       .unwrap();
 
+    let type_inference = default_config.map(|x| x.type_inference.clone()).unwrap_or_default();
+
+    let init_sql = default_config.map(|x| x.init_sql.clone()).unwrap_or_default();
+
     DbConnectionConfig {
       db_type: db_type.to_owned(),
       db_host,
@@ -327,6 +365,21 @@ impl Config {
       pg_search_path: pg_search_path.to_owned(),
       pool_size,
       connection_timeout,
+      type_inference,
+      init_sql,
+    }
+  }
+
+  /// A SQLite connection has no host, port or user as it's a database file, which DB_URL is required to point to
+  fn validate_sqlite_db_url(connection: &str, db_type: &DatabaseType, db_url: &Option<String>) {
+    if matches!(db_type, DatabaseType::Sqlite) && db_url.is_none() {
+      panic!(
+        r"
+       Failed to fetch DB URL of the SQLite connection '{connection}'.
+       Please provide the path to the database file, e.g. `./app.db` or `:memory:`, at least through a CLI arg
+       or an environment variable or through file based configuration
+       "
+      )
     }
   }
 
@@ -350,16 +403,31 @@ impl Config {
   ///
   /// It should return the connection name that is available based on your connection configurations
   pub fn get_correct_db_connection(&self, raw_sql: &str) -> String {
-    let re = Regex::new(r"(/*|//|--) @db: (?P<conn>[\w]+)( */){0,}").unwrap();
-    let found_matches = re.captures(raw_sql);
+    self
+      .get_db_annotation(raw_sql)
+      .unwrap_or_else(|| "default".to_string())
+  }
 
-    if let Some(found_match) = &found_matches {
-      let detected_conn_name = &found_match[2];
+  /// Returns the connection name of the `@db` annotation within the raw SQL if there is one
+  pub fn get_db_annotation(&self, raw_sql: &str) -> Option<String> {
+    let re = Regex::new(r"(/*|//|--) @db: (?P<conn>[\w]+)( */){0,}").unwrap();
+    re.captures(raw_sql).map(|found_match| found_match[2].to_string())
+  }
 
-      return detected_conn_name.to_string();
-    }
+  /// Figures out the connection name of a SQL, the `@db` annotation of a .sql file takes precedence over the query
+  pub fn get_sql_db_connection(&self, sql: &SQL) -> String {
+    sql
+      .db_connection
+      .clone()
+      .unwrap_or_else(|| self.get_correct_db_connection(&sql.query))
+  }
 
-    "default".to_string()
+  /// Whether types of the SQL should be taken from the database's description of the prepared statement
+  pub fn should_describe(&self, sql: &SQL) -> bool {
+    self
+      .connections
+      .get(&self.get_sql_db_connection(sql))
+      .is_some_and(|conn| conn.type_inference == TypeInference::Describe)
   }
 
   /// This is to follow the spec of connection string for MySQL
@@ -381,6 +449,21 @@ impl Config {
     .to_string()
   }
 
+  /// Path of the SQLite database from DB_URL, either a file path or `:memory:`
+  /// The URL may be prefixed with `sqlite://` or `sqlite:`, e.g. `sqlite://./app.db` or `sqlite::memory:`
+  pub fn get_sqlite_path(&self, conn: &DbConnectionConfig) -> String {
+    let db_url = conn
+      .db_url
+      .as_ref()
+      .expect("DB_URL of a SQLite connection is validated when the config is loaded");
+
+    db_url
+      .strip_prefix("sqlite://")
+      .or_else(|| db_url.strip_prefix("sqlite:"))
+      .unwrap_or(db_url)
+      .to_string()
+  }
+
   pub fn get_postgres_cred(&self, conn: &DbConnectionConfig) -> String {
     // If custom DB_URL is provided, use it directly
     if let Some(db_url) = &conn.db_url {
@@ -400,14 +483,36 @@ impl Config {
     )
   }
 
-  pub fn get_log_level(file_config_path: &PathBuf) -> LogLevel {
-    let file_based_config = fs::read_to_string(file_config_path);
-    let file_based_config = &file_based_config.map(|f| serde_json::from_str::<SqlxConfig>(f.as_str()).unwrap());
-    let log_level_from_file = file_based_config.as_ref().ok().and_then(|config| config.log_level);
+  /// Statements to run on every new connection of the pool
+  /// PG_SEARCH_PATH is applied first so that INIT_SQL can still override it
+  pub fn get_init_sql(&self, conn: &DbConnectionConfig) -> Vec<String> {
+    let search_path = match (&conn.db_type, &conn.pg_search_path) {
+      (DatabaseType::Postgres, Some(search_path)) => Some(format!("SET search_path TO {search_path}")),
+      _ => None,
+    };
+
+    search_path.into_iter().chain(conn.init_sql.iter().cloned()).collect()
+  }
+
+  pub fn get_log_level(file_based_config: Option<&SqlxConfig>) -> LogLevel {
+    let log_level_from_file = file_based_config.and_then(|config| config.log_level);
+
+    // Logs would corrupt the JSON or SARIF report, or the messages of the language server on stdout
+    // Only errors are logged as they go to stderr
+    if CLI_ARGS.format.is_machine_readable() || CLI_ARGS.is_lsp() {
+      return LogLevel::Error;
+    }
 
     CLI_ARGS.log_level.or(log_level_from_file).unwrap_or(LogLevel::Info)
   }
 
+  /// Tags of third party SQL libraries configured through the `tags` section of the file based configuration
+  pub fn get_tags(file_based_config: Option<&SqlxConfig>) -> Vec<TagConfig> {
+    file_based_config
+      .map(|config| config.tags.clone())
+      .unwrap_or_default()
+  }
+
   /// Custom logger for Config
   /// lazy::logger cannot be used as it requires Config itself to be initialised
   #[allow(clippy::print_stdout)]
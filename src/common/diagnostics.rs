@@ -0,0 +1,300 @@
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use swc_common::errors::{ColorConfig, DiagnosticBuilder, DiagnosticId, Emitter, Handler, Level};
+use swc_common::{sync::Lrc, SourceMap};
+
+use crate::common::lazy::{CLI_ARGS, DIAGNOSTICS};
+use crate::common::types::OutputFormat;
+use crate::common::SQL;
+use crate::ts_generator::generator::get_query_name;
+
+/// An error found in a source file, as it is reported by --format json, sarif and github
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+  pub file: String,
+  /// 1-based line and column (counted in characters) the error starts at
+  pub line: usize,
+  pub column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+  /// Name of the query the error is about, i.e. the name its types are generated with
+  pub query_name: Option<String>,
+  /// E0xx code of sqlx-ts, the SQLSTATE of PostgreSQL or the error number of MySQL
+  pub code: Option<String>,
+  /// error, warning or note
+  pub severity: String,
+  pub message: String,
+  /// Details, hints and suggestions that come with the error e.g. "help: did you mean `items`?"
+  pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+  /// The message followed by a line for each of its notes
+  pub fn get_full_message(&self) -> String {
+    std::iter::once(self.message.as_str())
+      .chain(self.notes.iter().map(String::as_str))
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+/// Collects the diagnostics of a file into `DIAGNOSTICS` instead of rendering them to the terminal
+struct DiagnosticsCollector {
+  cm: Lrc<SourceMap>,
+  file_path: String,
+}
+
+impl Emitter for DiagnosticsCollector {
+  fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+    let (code, message) = match &db.code {
+      Some(DiagnosticId::Error(code)) | Some(DiagnosticId::Lint(code)) => (Some(code.to_owned()), db.message()),
+      None => split_error_code(&db.message()),
+    };
+
+    let (file, line, column, end_line, end_column) = match db.span.primary_span().filter(|span| !span.is_dummy()) {
+      Some(span) => {
+        let lo = self.cm.lookup_char_pos(span.lo);
+        let hi = self.cm.lookup_char_pos(span.hi);
+        (lo.file.name.to_string(), lo.line, lo.col.0 + 1, hi.line, hi.col.0 + 1)
+      }
+      None => (self.file_path.to_owned(), 1, 1, 1, 1),
+    };
+
+    let notes = db
+      .children
+      .iter()
+      .map(|child| format!("{}: {}", child.level, child.message()))
+      .collect();
+
+    DIAGNOSTICS.lock().unwrap().push(Diagnostic {
+      file,
+      line,
+      column,
+      end_line,
+      end_column,
+      query_name: None,
+      code,
+      severity: get_severity(db.level).to_string(),
+      message,
+      notes,
+    });
+  }
+}
+
+/// Splits the `[E016] ` prefix of the errors of `TsGeneratorError` from the rest of the message
+fn split_error_code(message: &str) -> (Option<String>, String) {
+  let error_code = Regex::new(r"^\[(E\d+)\] ").unwrap();
+
+  match error_code.captures(message) {
+    Some(captures) => (Some(captures[1].to_string()), message[captures[0].len()..].to_string()),
+    None => (None, message.to_string()),
+  }
+}
+
+fn get_severity(level: Level) -> &'static str {
+  match level {
+    Level::Warning => "warning",
+    Level::Note | Level::Help => "note",
+    _ => "error",
+  }
+}
+
+/// Handler of the errors of a source file, it renders them to the terminal
+/// or collects them for the report of --format and the language server
+pub fn create_handler(cm: Lrc<SourceMap>, file_path: &str) -> Handler {
+  match CLI_ARGS.format {
+    OutputFormat::Human if !CLI_ARGS.is_lsp() => Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm)),
+    _ => Handler::with_emitter(
+      true,
+      false,
+      Box::new(DiagnosticsCollector {
+        cm,
+        file_path: file_path.to_string(),
+      }),
+    ),
+  }
+}
+
+/// Number of diagnostics collected so far, used along with `set_query_name` to find the diagnostics of a query
+pub fn get_num_diagnostics() -> usize {
+  DIAGNOSTICS.lock().unwrap().len()
+}
+
+/// Sets the name of the query on every diagnostic collected since `from`
+pub fn set_query_name(from: usize, sql: &SQL) {
+  let mut diagnostics = DIAGNOSTICS.lock().unwrap();
+  if diagnostics.len() <= from {
+    return;
+  }
+
+  let query_name = get_query_name(sql).ok();
+  for diagnostic in diagnostics.iter_mut().skip(from) {
+    diagnostic.query_name.clone_from(&query_name);
+  }
+}
+
+/// Escapes the message of a workflow command, and also its properties when `is_property` is set
+/// https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts
+fn escape_workflow_command(value: &str, is_property: bool) -> String {
+  let value = value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+
+  if is_property {
+    value.replace(':', "%3A").replace(',', "%2C")
+  } else {
+    value
+  }
+}
+
+fn get_github_annotation(diagnostic: &Diagnostic) -> String {
+  let command = match diagnostic.severity.as_str() {
+    "warning" => "warning",
+    "note" => "notice",
+    _ => "error",
+  };
+  let title = match (&diagnostic.code, &diagnostic.query_name) {
+    (Some(code), Some(query_name)) => format!("sqlx-ts {code} in {query_name}"),
+    (Some(code), None) => format!("sqlx-ts {code}"),
+    (None, Some(query_name)) => format!("sqlx-ts in {query_name}"),
+    (None, None) => "sqlx-ts".to_string(),
+  };
+  format!(
+    "::{command} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+    escape_workflow_command(&diagnostic.file, true),
+    diagnostic.line,
+    diagnostic.column,
+    diagnostic.end_line,
+    diagnostic.end_column,
+    escape_workflow_command(&title, true),
+    escape_workflow_command(&diagnostic.get_full_message(), false),
+  )
+}
+
+fn get_sarif_log(diagnostics: &[Diagnostic]) -> serde_json::Value {
+  let rules = diagnostics
+    .iter()
+    .filter_map(|diagnostic| diagnostic.code.to_owned())
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .map(|code| json!({ "id": code }))
+    .collect::<Vec<_>>();
+
+  let results = diagnostics
+    .iter()
+    .map(|diagnostic| {
+      json!({
+        "ruleId": diagnostic.code,
+        "level": diagnostic.severity,
+        "message": { "text": diagnostic.get_full_message() },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": { "uri": diagnostic.file.replace('\\', "/") },
+            "region": {
+              "startLine": diagnostic.line,
+              "startColumn": diagnostic.column,
+              "endLine": diagnostic.end_line,
+              "endColumn": diagnostic.end_column,
+            },
+          },
+        }],
+        "properties": { "queryName": diagnostic.query_name },
+      })
+    })
+    .collect::<Vec<_>>();
+
+  json!({
+    "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+    "version": "2.1.0",
+    "runs": [{
+      "tool": {
+        "driver": {
+          "name": "sqlx-ts",
+          "informationUri": "https://jasonshin.github.io/sqlx-ts/",
+          "version": env!("CARGO_PKG_VERSION"),
+          "rules": rules,
+        },
+      },
+      "results": results,
+    }],
+  })
+}
+
+/// Prints the report of every diagnostic collected in the run, in the format chosen with --format
+#[allow(clippy::print_stdout)]
+pub fn print_diagnostics() {
+  let diagnostics = DIAGNOSTICS.lock().unwrap();
+
+  match CLI_ARGS.format {
+    OutputFormat::Human => {}
+    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&*diagnostics).unwrap()),
+    OutputFormat::Sarif => println!(
+      "{}",
+      serde_json::to_string_pretty(&get_sarif_log(&diagnostics)).unwrap()
+    ),
+    OutputFormat::Github => {
+      for diagnostic in diagnostics.iter() {
+        println!("{}", get_github_annotation(diagnostic));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_diagnostic() -> Diagnostic {
+    Diagnostic {
+      file: "src/index.ts".to_string(),
+      line: 3,
+      column: 37,
+      end_line: 3,
+      end_column: 42,
+      query_name: Some("SomeQuery".to_string()),
+      code: Some("42P01".to_string()),
+      severity: "error".to_string(),
+      message: "relation \"itmes\" does not exist".to_string(),
+      notes: vec!["help: did you mean `items`?".to_string()],
+    }
+  }
+
+  #[test]
+  fn test_split_error_code() {
+    assert_eq!(
+      split_error_code("[E016] Column 'nme' not found in table 'items'"),
+      (
+        Some("E016".to_string()),
+        "Column 'nme' not found in table 'items'".to_string()
+      )
+    );
+    assert_eq!(
+      split_error_code("relation \"itmes\" does not exist"),
+      (None, "relation \"itmes\" does not exist".to_string())
+    );
+  }
+
+  #[test]
+  fn test_github_annotation_escapes_the_command() {
+    let mut diagnostic = get_diagnostic();
+    diagnostic.file = "src/a,b.ts".to_string();
+
+    assert_eq!(
+      get_github_annotation(&diagnostic),
+      "::error file=src/a%2Cb.ts,line=3,col=37,endLine=3,endColumn=42,title=sqlx-ts 42P01 in SomeQuery::relation \"itmes\" does not exist%0Ahelp: did you mean `items`?"
+    );
+  }
+
+  #[test]
+  fn test_sarif_log() {
+    let log = get_sarif_log(&[get_diagnostic()]);
+    let result = &log["runs"][0]["results"][0];
+
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["rules"][0]["id"], "42P01");
+    assert_eq!(result["ruleId"], "42P01");
+    assert_eq!(result["locations"][0]["physicalLocation"]["region"]["startColumn"], 37);
+    assert_eq!(result["properties"]["queryName"], "SomeQuery");
+  }
+}
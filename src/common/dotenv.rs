@@ -35,6 +35,8 @@ impl Dotenv {
         Some(val) => {
           if val == "mysql" {
             Some(DatabaseType::Mysql)
+          } else if val == "sqlite" {
+            Some(DatabaseType::Sqlite)
           } else {
             Some(DatabaseType::Postgres)
           }
@@ -1,9 +1,14 @@
 use crate::common::cli::Cli;
 use crate::common::config::Config;
+use crate::common::diagnostics::Diagnostic;
+use crate::common::logger::error;
 use crate::common::types::DatabaseType;
-use crate::core::connection::{DBConn, DBConnections};
+use crate::core::connection::{DBConn, DBConnections, LogErrorSink};
 use crate::core::mysql::pool::MySqlConnectionManager;
+use crate::core::offline::migrations::MigrationSchema;
+use crate::core::offline::snapshot::SchemaSnapshot;
 use crate::core::postgres::pool::PostgresConnectionManager;
+use crate::core::sqlite::pool::SqliteConnectionManager;
 use crate::ts_generator::information_schema::DBSchema;
 use clap::Parser;
 use std::sync::LazyLock;
@@ -18,6 +23,9 @@ pub static CONFIG: LazyLock<Config> = LazyLock::new(Config::new);
 // This is a holder for shared DBSChema used to fetch information for information_schema table
 // By having a singleton, we can think about caching the result if we are fetching a query too many times
 pub static DB_SCHEMA: LazyLock<Arc<Mutex<DBSchema>>> = LazyLock::new(|| Arc::new(Mutex::new(DBSchema::new())));
+// Errors of every file checked so far, printed as a single report at the end of a run with --format json, sarif or github
+// swc emitters are called synchronously, hence the std Mutex
+pub static DIAGNOSTICS: LazyLock<std::sync::Mutex<Vec<Diagnostic>>> = LazyLock::new(|| std::sync::Mutex::new(vec![]));
 // TODO: move this to errors.rs
 pub static ERR_DB_CONNECTION_ISSUE: LazyLock<String> = LazyLock::new(|| {
   "Unable to connect to the database, please check the connection configuration again https://jasonshin.github.io/sqlx-ts/api/1.connecting-to-db.html".to_string()
@@ -27,24 +35,61 @@ pub static ERR_DB_CONNECTION_ISSUE: LazyLock<String> = LazyLock::new(|| {
 // We are using lazy_static to initialize the connections once and use them throughout the application
 pub static DB_CONN_CACHE: LazyLock<HashMap<String, Arc<Mutex<DBConn>>>> = LazyLock::new(|| {
   let mut cache = HashMap::new();
+  // With --offline, every connection is backed by the schema snapshot and no database is ever reached
+  let snapshot = CLI_ARGS.offline.then(|| {
+    SchemaSnapshot::load(&CLI_ARGS.snapshot_path()).unwrap_or_else(|err| {
+      error!("{}", err);
+      std::process::exit(1)
+    })
+  });
+
   for connection in CONFIG.connections.keys() {
     let connection_config = CONFIG
       .connections
       .get(connection)
       .unwrap_or_else(|| panic!("Invalid connection name - {connection}"));
     let db_type = connection_config.db_type.to_owned();
+
+    // With --schema-from, the schema replayed from migrations backs the connection the same way as a snapshot
+    let offline_snapshot = match (&snapshot, &CLI_ARGS.schema_from) {
+      (Some(snapshot), _) => Some(snapshot.connections.get(connection).cloned().unwrap_or_else(|| {
+        error!(
+          "The connection '{}' is not in the schema snapshot {:?}, run `sqlx-ts snapshot` again to include it",
+          connection,
+          CLI_ARGS.snapshot_path()
+        );
+        std::process::exit(1)
+      })),
+      (None, Some(schema_from)) => Some(MigrationSchema::load(schema_from, &db_type).unwrap_or_else(|err| {
+        error!("{}", err);
+        std::process::exit(1)
+      })),
+      (None, None) => None,
+    };
+
+    if let Some(offline_snapshot) = offline_snapshot {
+      let conn = DBConn::Offline(offline_snapshot.with_search_path(&connection_config.pg_search_path));
+      cache.insert(connection.to_owned(), Arc::new(Mutex::new(conn)));
+      continue;
+    }
+
     let conn = match db_type {
       DatabaseType::Mysql => task::block_in_place(|| {
         Handle::current().block_on(async {
           let mysql_cred = CONFIG.get_mysql_cred_str(connection_config);
           let mysql_cred = mysql_cred.as_str();
-          let manager = MySqlConnectionManager::new(mysql_cred.to_string(), connection.to_string());
+          let init_sql = CONFIG.get_init_sql(connection_config);
+          let manager = MySqlConnectionManager::new(mysql_cred.to_string(), connection.to_string(), init_sql);
           let pool = bb8::Pool::builder()
             .max_size(connection_config.pool_size)
             .connection_timeout(std::time::Duration::from_secs(connection_config.connection_timeout))
+            .error_sink(Box::new(LogErrorSink::default()))
             .build(manager)
             .await
-            .expect(&ERR_DB_CONNECTION_ISSUE);
+            .unwrap_or_else(|err| {
+              error!("{} - {}", *ERR_DB_CONNECTION_ISSUE, err);
+              std::process::exit(1)
+            });
 
           DBConn::MySQLPooledConn(Mutex::new(pool))
         })
@@ -52,42 +97,39 @@ pub static DB_CONN_CACHE: LazyLock<HashMap<String, Arc<Mutex<DBConn>>>> = LazyLo
       DatabaseType::Postgres => task::block_in_place(|| {
         Handle::current().block_on(async {
           let postgres_cred = CONFIG.get_postgres_cred(connection_config);
-          let manager = PostgresConnectionManager::new(postgres_cred);
+          let init_sql = CONFIG.get_init_sql(connection_config);
+          let manager = PostgresConnectionManager::new(postgres_cred, init_sql);
           let pool = bb8::Pool::builder()
             .max_size(connection_config.pool_size)
             .connection_timeout(std::time::Duration::from_secs(connection_config.connection_timeout))
+            .error_sink(Box::new(LogErrorSink::default()))
             .build(manager)
             .await
-            .expect(&ERR_DB_CONNECTION_ISSUE);
-
-          let db_conn = DBConn::PostgresConn(Mutex::new(pool));
+            .unwrap_or_else(|err| {
+              error!("{} - {}", *ERR_DB_CONNECTION_ISSUE, err);
+              std::process::exit(1)
+            });
 
-          let conn = match &db_conn {
-            DBConn::PostgresConn(conn) => conn,
-            _ => panic!("Invalid connection type"),
-          };
-
-          if connection_config.pg_search_path.is_some() {
-            let search_path_query = format!(
-              "SET search_path TO {}",
-              &connection_config.pg_search_path.clone().unwrap().as_str()
-            );
-            {
-              let conn = conn.lock().await;
-              let conn = conn.get().await.expect(&ERR_DB_CONNECTION_ISSUE);
+          DBConn::PostgresConn(Mutex::new(pool))
+        })
+      }),
+      DatabaseType::Sqlite => task::block_in_place(|| {
+        Handle::current().block_on(async {
+          let sqlite_path = CONFIG.get_sqlite_path(connection_config);
+          let init_sql = CONFIG.get_init_sql(connection_config);
+          let manager = SqliteConnectionManager::new(sqlite_path, init_sql);
+          let pool = bb8::Pool::builder()
+            .max_size(connection_config.pool_size)
+            .connection_timeout(std::time::Duration::from_secs(connection_config.connection_timeout))
+            .error_sink(Box::new(LogErrorSink::default()))
+            .build(manager)
+            .await
+            .unwrap_or_else(|err| {
+              error!("{} - {}", *ERR_DB_CONNECTION_ISSUE, err);
+              std::process::exit(1)
+            });
 
-              let err_search_path_query = format!(
-                "Failed to execute the search_path query {:?}",
-                search_path_query.as_str()
-              );
-              let err_search_path_query = err_search_path_query.as_str();
-              conn
-                .execute(&search_path_query, &[])
-                .await
-                .expect(err_search_path_query);
-            }
-          }
-          db_conn
+          DBConn::SqliteConn(Mutex::new(pool))
         })
       }),
     };
@@ -3,10 +3,15 @@ extern crate core;
 
 use swc_common::MultiSpan;
 
+use crate::common::query_span::QuerySpan;
+
 pub mod cli;
 pub mod config;
+pub mod diagnostics;
 pub mod dotenv;
 pub mod lazy;
+pub mod query_span;
+pub mod suggestion;
 pub mod types;
 
 pub mod errors;
@@ -19,4 +24,9 @@ pub struct SQL {
   pub var_decl_name: Option<String>,
   pub query: String,
   pub span: MultiSpan,
+  /// Name of the connection to run the query against. When it's not set, the connection is detected from
+  /// the `@db` annotation within the query (or falls back to `default`)
+  pub db_connection: Option<String>,
+  /// Where the text of the query comes from in the source file, used to point errors at the offending token
+  pub query_spans: Vec<QuerySpan>,
 }
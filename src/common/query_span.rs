@@ -0,0 +1,165 @@
+use regex::Regex;
+use sqlparser::parser::ParserError;
+use std::ops::Range;
+use swc_common::{BytePos, MultiSpan, Span};
+
+use crate::common::SQL;
+
+/// A piece of `SQL::query` along with the code in the source file it comes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySpan {
+  /// Byte range of the piece within the query
+  pub range: Range<usize>,
+  /// Span of the code in the source file
+  pub span: Span,
+  /// Whether the piece is the code as is, otherwise it stands in for the code e.g. `$1` for the `${id}` of a template
+  pub is_verbatim: bool,
+}
+
+impl QuerySpan {
+  pub fn new(range: Range<usize>, span: Span, is_verbatim: bool) -> Self {
+    Self {
+      range,
+      span,
+      is_verbatim,
+    }
+  }
+}
+
+/// Byte length of the SQL token the text starts with, e.g. a keyword, an identifier or a quoted name
+fn get_token_len(text: &str) -> usize {
+  let mut chars = text.chars();
+  match chars.next() {
+    Some(quote @ ('"' | '\'' | '`')) => text[1..].find(quote).map(|end| end + 2).unwrap_or(text.len()),
+    Some(char) if char.is_alphanumeric() || char == '_' => text
+      .find(|char: char| !char.is_alphanumeric() && char != '_')
+      .unwrap_or(text.len()),
+    Some(char) => char.len_utf8(),
+    None => 0,
+  }
+}
+
+/// Byte offset of the 1-based line and column (counted in characters) within the text
+pub fn get_offset_at_line_column(text: &str, line: usize, column: usize) -> Option<usize> {
+  let line_start = match line {
+    0 => return None,
+    1 => 0,
+    _ => text.match_indices('\n').nth(line - 2)?.0 + 1,
+  };
+  let line_text = text[line_start..].split('\n').next()?;
+
+  line_text
+    .char_indices()
+    .nth(column.checked_sub(1)?)
+    .map(|(idx, _)| line_start + idx)
+}
+
+impl SQL {
+  /// Span of the token at the byte offset of the query within the source file
+  /// It falls back to the span of the whole statement when the query cannot be traced back to the source
+  pub fn span_at(&self, offset: usize) -> MultiSpan {
+    let query_span = self
+      .query_spans
+      .iter()
+      .find(|query_span| query_span.range.contains(&offset));
+
+    match query_span {
+      Some(query_span) if query_span.is_verbatim => {
+        let len = get_token_len(&self.query[offset..query_span.range.end]);
+        let lo = query_span.span.lo + BytePos((offset - query_span.range.start) as u32);
+        Span::new(lo, lo + BytePos(len as u32)).into()
+      }
+      Some(query_span) => query_span.span.into(),
+      None => self.span.clone(),
+    }
+  }
+
+  /// Span of the token at the 1-based position (counted in characters) of the query, as PostgreSQL reports it
+  pub fn span_at_position(&self, position: usize) -> MultiSpan {
+    let offset = position
+      .checked_sub(1)
+      .and_then(|idx| self.query.char_indices().nth(idx))
+      .map(|(offset, _)| offset);
+
+    match offset {
+      Some(offset) => self.span_at(offset),
+      None => self.span.clone(),
+    }
+  }
+
+  /// Span of the token at the 1-based line and column of the query, as sqlparser reports it
+  pub fn span_at_line_column(&self, line: usize, column: usize) -> MultiSpan {
+    match get_offset_at_line_column(&self.query, line, column) {
+      Some(offset) => self.span_at(offset),
+      None => self.span.clone(),
+    }
+  }
+
+  /// Span of the location a sqlparser error ends with e.g. `Expected: an expression, found: FROM at Line: 1, Column: 8`
+  pub fn span_of_parser_error(&self, err: &ParserError) -> MultiSpan {
+    let location = Regex::new(r"at Line: (\d+), Column: (\d+)$").unwrap();
+    let message = err.to_string();
+
+    match location.captures(&message) {
+      Some(captures) => self.span_at_line_column(captures[1].parse().unwrap(), captures[2].parse().unwrap()),
+      None => self.span.clone(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_sql(query: &str, query_spans: Vec<QuerySpan>) -> SQL {
+    SQL {
+      var_decl_name: None,
+      query: query.to_string(),
+      span: Span::new(BytePos(1), BytePos(100)).into(),
+      db_connection: None,
+      query_spans,
+    }
+  }
+
+  #[test]
+  fn test_span_at_points_at_the_token_in_the_source() {
+    // `sql`SELECT * FROM ${table} WHERE ...`` where the template starts at byte 10 of the file
+    let sql = get_sql(
+      "SELECT * FROM $1 WHERE unknown = 1",
+      vec![
+        QuerySpan::new(0..14, Span::new(BytePos(10), BytePos(24)), true),
+        QuerySpan::new(14..16, Span::new(BytePos(26), BytePos(31)), false),
+        QuerySpan::new(16..34, Span::new(BytePos(32), BytePos(50)), true),
+      ],
+    );
+
+    assert_eq!(sql.span_at(0).primary_span(), Some(Span::new(BytePos(10), BytePos(16))));
+    assert_eq!(
+      sql.span_at(14).primary_span(),
+      Some(Span::new(BytePos(26), BytePos(31)))
+    );
+    assert_eq!(
+      sql.span_at(23).primary_span(),
+      Some(Span::new(BytePos(39), BytePos(46)))
+    );
+    assert_eq!(sql.span_at(100), sql.span);
+  }
+
+  #[test]
+  fn test_span_at_line_column_and_position() {
+    let sql = get_sql(
+      "SELECT id\nFROM \"unknown table\"",
+      vec![QuerySpan::new(0..30, Span::new(BytePos(1), BytePos(31)), true)],
+    );
+
+    assert_eq!(
+      sql.span_at_line_column(2, 6).primary_span(),
+      Some(Span::new(BytePos(16), BytePos(31)))
+    );
+    assert_eq!(
+      sql.span_at_position(8).primary_span(),
+      Some(Span::new(BytePos(8), BytePos(10)))
+    );
+    assert_eq!(get_offset_at_line_column(&sql.query, 3, 1), None);
+  }
+}
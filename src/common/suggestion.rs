@@ -0,0 +1,110 @@
+use regex::Regex;
+
+/// Edit distance between two names, where swapping two adjacent characters counts as a single edit
+/// Names are compared case-insensitively, as unquoted identifiers are case-insensitive in SQL
+fn get_edit_distance(a: &str, b: &str) -> usize {
+  let a = a.to_lowercase().chars().collect::<Vec<_>>();
+  let b = b.to_lowercase().chars().collect::<Vec<_>>();
+  let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+  for (i, row) in distances.iter_mut().enumerate() {
+    row[0] = i;
+  }
+  for (j, distance) in distances[0].iter_mut().enumerate() {
+    *distance = j;
+  }
+
+  for i in 1..=a.len() {
+    for j in 1..=b.len() {
+      let cost = usize::from(a[i - 1] != b[j - 1]);
+      let mut distance = (distances[i - 1][j] + 1)
+        .min(distances[i][j - 1] + 1)
+        .min(distances[i - 1][j - 1] + cost);
+
+      if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+        distance = distance.min(distances[i - 2][j - 2] + 1);
+      }
+      distances[i][j] = distance;
+    }
+  }
+
+  distances[a.len()][b.len()]
+}
+
+/// Candidates that are likely what the unknown name was meant to be, closest first
+/// A candidate is only suggested if it takes at most a third of the name's length in edits to get to it
+pub fn get_suggestions<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+  let max_distance = (name.chars().count() / 3).max(1);
+
+  let mut suggestions = candidates
+    .into_iter()
+    .filter(|candidate| !candidate.eq_ignore_ascii_case(name))
+    .map(|candidate| (get_edit_distance(name, candidate), candidate))
+    .filter(|(distance, _)| *distance <= max_distance)
+    .collect::<Vec<_>>();
+
+  suggestions.sort();
+  suggestions.dedup();
+  suggestions
+    .into_iter()
+    .take(3)
+    .map(|(_, candidate)| candidate.to_string())
+    .collect()
+}
+
+/// e.g. "did you mean `items`?" or "did you mean one of `name`, `rarity`?"
+pub fn format_suggestions(suggestions: &[String]) -> Option<String> {
+  let names = suggestions
+    .iter()
+    .map(|suggestion| format!("`{suggestion}`"))
+    .collect::<Vec<_>>();
+
+  match names.len() {
+    0 => None,
+    1 => Some(format!("did you mean {}?", names[0])),
+    _ => Some(format!("did you mean one of {}?", names.join(", "))),
+  }
+}
+
+/// Whether the query mentions the table, used to only suggest columns of the tables a query could refer to
+pub fn is_table_in_query(query: &str, table: &str) -> bool {
+  let table = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(table))).unwrap();
+  table.is_match(query)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_edit_distance() {
+    assert_eq!(get_edit_distance("items", "items"), 0);
+    assert_eq!(get_edit_distance("itmes", "items"), 1);
+    assert_eq!(get_edit_distance("nme", "name"), 1);
+    assert_eq!(get_edit_distance("NAME", "name"), 0);
+    assert_eq!(get_edit_distance("rarity", "flavor_text"), 8);
+  }
+
+  #[test]
+  fn test_get_suggestions_ranked_by_edit_distance() {
+    let columns = ["id", "name", "names", "rarity", "flavor_text", "inventory_id"];
+
+    assert_eq!(get_suggestions("nme", columns), vec!["name"]);
+    assert_eq!(get_suggestions("namess", columns), vec!["names", "name"]);
+    assert_eq!(get_suggestions("inventroy_id", columns), vec!["inventory_id"]);
+    assert!(get_suggestions("unknown", columns).is_empty());
+  }
+
+  #[test]
+  fn test_format_suggestions() {
+    assert_eq!(format_suggestions(&[]), None);
+    assert_eq!(
+      format_suggestions(&["items".to_string()]),
+      Some("did you mean `items`?".to_string())
+    );
+    assert_eq!(
+      format_suggestions(&["name".to_string(), "names".to_string()]),
+      Some("did you mean one of `name`, `names`?".to_string())
+    );
+  }
+}
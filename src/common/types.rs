@@ -11,6 +11,11 @@ pub enum FileExtension {
   Cts,
   Mjs,
   Cjs,
+  Tsx,
+  Jsx,
+  Vue,
+  Svelte,
+  Astro,
 }
 
 #[derive(ValueEnum, Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +23,28 @@ pub enum FileExtension {
 pub enum DatabaseType {
   Postgres,
   Mysql,
+  Sqlite,
+}
+
+impl DatabaseType {
+  /// Bind parameter placeholder of the database for the param at the given 1-based position
+  pub fn get_placeholder(&self, position: usize) -> String {
+    match self {
+      DatabaseType::Postgres => format!("${position}"),
+      DatabaseType::Mysql | DatabaseType::Sqlite => "?".to_string(),
+    }
+  }
+}
+
+/// How types of params and result columns are inferred for a connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeInference {
+  /// Walks the SQL AST and looks up column types from information_schema
+  #[default]
+  Ast,
+  /// Prefers the param and column types the database reports for the prepared statement
+  Describe,
 }
 
 #[derive(ValueEnum, Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +73,27 @@ impl NamingConvention {
   }
 }
 
+/// Format the errors of a run are reported in
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputFormat {
+  /// Code frames of every error, as they are found
+  #[default]
+  Human,
+  /// A JSON array of every error, once all files are checked
+  Json,
+  /// A SARIF 2.1.0 log of every error, once all files are checked
+  Sarif,
+  /// GitHub Actions workflow commands that annotate the pull request, once all files are checked
+  Github,
+}
+
+impl OutputFormat {
+  /// Whether stdout must only hold the report, so that it can be parsed
+  pub fn is_machine_readable(&self) -> bool {
+    matches!(self, OutputFormat::Json | OutputFormat::Sarif)
+  }
+}
+
 #[derive(ValueEnum, Debug, Clone, Serialize, Deserialize, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
@@ -1,17 +1,22 @@
 use crate::common::lazy::CONFIG;
+use crate::common::logger::error;
 use crate::common::types::DatabaseType;
 use crate::common::SQL;
 use crate::core::mysql::prepare as mysql_explain;
+use crate::core::offline::prepare as offline_explain;
+use crate::core::offline::snapshot::ConnectionSnapshot;
 use crate::core::postgres::prepare as postgres_explain;
+use crate::core::sqlite::prepare as sqlite_explain;
 use crate::ts_generator::types::ts_query::TsQuery;
-use bb8::Pool;
-use std::collections::HashMap;
+use bb8::{ErrorSink, Pool};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use super::mysql::pool::MySqlConnectionManager;
 use super::postgres::pool::PostgresConnectionManager;
-use crate::common::errors::DB_CONN_FROM_LOCAL_CACHE_ERROR;
+use super::sqlite::pool::SqliteConnectionManager;
 use color_eyre::Result;
 use swc_common::errors::Handler;
 
@@ -19,6 +24,9 @@ use swc_common::errors::Handler;
 pub enum DBConn {
   MySQLPooledConn(Mutex<Pool<MySqlConnectionManager>>),
   PostgresConn(Mutex<Pool<PostgresConnectionManager>>),
+  SqliteConn(Mutex<Pool<SqliteConnectionManager>>),
+  // Used with --offline, the connection reads the schema from a snapshot rather than a database
+  Offline(ConnectionSnapshot),
 }
 
 impl DBConn {
@@ -31,16 +39,27 @@ impl DBConn {
     let (explain_failed, ts_query) = match &self {
       DBConn::MySQLPooledConn(_conn) => mysql_explain::prepare(self, sql, should_generate_types, handler).await?,
       DBConn::PostgresConn(_conn) => postgres_explain::prepare(self, sql, should_generate_types, handler).await?,
+      DBConn::SqliteConn(_conn) => sqlite_explain::prepare(self, sql, should_generate_types, handler).await?,
+      DBConn::Offline(snapshot) => {
+        offline_explain::prepare(self, snapshot, sql, should_generate_types, handler).await?
+      }
     };
 
     Ok((explain_failed, ts_query))
   }
 
+  /// Whether the connection reads the schema from a snapshot rather than a database
+  pub fn is_offline(&self) -> bool {
+    matches!(self, DBConn::Offline(_))
+  }
+
   /// Get the database type for this connection
   pub fn get_db_type(&self) -> DatabaseType {
     match self {
       DBConn::MySQLPooledConn(_) => DatabaseType::Mysql,
       DBConn::PostgresConn(_) => DatabaseType::Postgres,
+      DBConn::SqliteConn(_) => DatabaseType::Sqlite,
+      DBConn::Offline(snapshot) => snapshot.db_type.to_owned(),
     }
   }
 }
@@ -54,10 +73,35 @@ impl<'a> DBConnections<'a> {
     Self { cache }
   }
 
-  pub fn get_connection(&mut self, raw_sql: &str) -> Arc<Mutex<DBConn>> {
-    let db_conn_name = &CONFIG.get_correct_db_connection(raw_sql);
+  /// Name of the connection the SQL should be validated against, as defined in the configuration
+  pub fn get_connection_name(&self, sql: &SQL) -> String {
+    CONFIG.get_sql_db_connection(sql)
+  }
+
+  /// Connection of the SQL, None when its `@db` annotation or its tag names a connection that is not configured
+  pub fn get_connection(&mut self, sql: &SQL) -> Option<Arc<Mutex<DBConn>>> {
+    let db_conn_name = &self.get_connection_name(sql);
+
+    self.cache.get(db_conn_name).cloned()
+  }
+}
+
+/// Reports errors of connections that a pool opens in the background, e.g. an INIT_SQL statement that fails, as they
+/// would otherwise only surface as a timeout. The pool retries a failing connection, so each error is reported once
+#[derive(Clone, Debug, Default)]
+pub struct LogErrorSink {
+  reported: Arc<std::sync::Mutex<HashSet<String>>>,
+}
+
+impl<E: Display> ErrorSink<E> for LogErrorSink {
+  fn sink(&self, err: E) {
+    let message = err.to_string();
+    if self.reported.lock().unwrap().insert(message.to_owned()) {
+      error!(message);
+    }
+  }
 
-    let conn = self.cache.get(db_conn_name).expect(DB_CONN_FROM_LOCAL_CACHE_ERROR);
-    conn.to_owned()
+  fn boxed_clone(&self) -> Box<dyn ErrorSink<E>> {
+    Box::new(self.clone())
   }
 }
@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+use swc_common::errors::{DiagnosticBuilder, DiagnosticId, Handler, Level};
+use swc_common::MultiSpan;
+
+use crate::common::errors::DB_CONN_FAILURE_ERROR;
+use crate::common::suggestion::{format_suggestions, get_suggestions, is_table_in_query};
+
+/// An error the database reported while preparing a query
+#[derive(Debug, Default)]
+pub struct DbError {
+  /// SQLSTATE of PostgreSQL or the error number of MySQL
+  pub code: Option<String>,
+  pub message: String,
+  pub detail: Option<String>,
+  pub hint: Option<String>,
+  /// Names of tables or columns close to the unknown one the error is about, closest first
+  pub suggestions: Vec<String>,
+}
+
+impl DbError {
+  pub fn new(code: Option<String>, message: &str) -> Self {
+    Self {
+      code,
+      message: message.to_string(),
+      ..Default::default()
+    }
+  }
+
+  /// The query never reached the database, e.g. the connection dropped or the pool ran out of connections
+  pub fn connection_failure(err: impl Display) -> Self {
+    Self::new(None, &format!("{DB_CONN_FAILURE_ERROR}: {err}"))
+  }
+
+  /// Suggests the tables closest to the unknown table
+  pub fn suggest_table(&mut self, table: &str, tables: &[String]) {
+    self.suggestions = get_suggestions(table, tables.iter().map(String::as_str));
+  }
+
+  /// Suggests the columns closest to the unknown column, out of the columns of tables the query mentions
+  pub fn suggest_column(&mut self, column: &str, columns: &[(String, String)], query: &str) {
+    let candidates = columns
+      .iter()
+      .filter(|(table, _)| is_table_in_query(query, table))
+      .map(|(_, column)| column.as_str());
+
+    self.suggestions = get_suggestions(column, candidates);
+  }
+
+  /// Emits the error as `error[42P01]: relation "itmes" does not exist`, followed by its detail, hint and suggestions
+  pub fn emit(&self, handler: &Handler, span: MultiSpan) {
+    let mut diagnostic = DiagnosticBuilder::new(handler, Level::Error, &self.message);
+    diagnostic.set_span(span);
+
+    if let Some(code) = &self.code {
+      diagnostic.code(DiagnosticId::Error(code.to_owned()));
+    }
+    if let Some(detail) = &self.detail {
+      diagnostic.note(detail);
+    }
+    if let Some(hint) = &self.hint {
+      diagnostic.help(hint);
+    }
+    if let Some(did_you_mean) = format_suggestions(&self.suggestions) {
+      diagnostic.help(&did_you_mean);
+    }
+
+    diagnostic.emit();
+  }
+}
@@ -1,42 +1,151 @@
-use crate::common::lazy::{CLI_ARGS, CONFIG, DB_CONNECTIONS};
+use crate::common::diagnostics::{get_num_diagnostics, set_query_name};
+use crate::common::errors::DB_CONN_FROM_LOCAL_CACHE_ERROR;
+use crate::common::lazy::{CLI_ARGS, CONFIG, DB_CONNECTIONS, DB_SCHEMA};
+use crate::common::logger::*;
+use crate::common::types::OutputFormat;
 use crate::common::SQL;
-use crate::ts_generator::generator::{write_colocated_ts_file, write_single_ts_file};
 
 use color_eyre::eyre::Result;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use swc_common::errors::Handler;
 
-pub async fn execute(queries: &HashMap<PathBuf, Vec<SQL>>, handler: &Handler) -> Result<bool> {
+/// Totals of every file checked in a run, reported once all files are processed
+#[derive(Debug, Default)]
+pub struct Summary {
+  pub files_scanned: usize,
+  pub queries_checked: usize,
+  /// Number of SQLs that failed to compile in each file
+  pub failures: BTreeMap<PathBuf, usize>,
+  /// Errors that stopped the types of a SQL from being generated, along with the file of the SQL
+  pub generation_errors: Vec<(PathBuf, String)>,
+  /// Types generated for each file, written once every file is checked or compared with the files on disk by --check
+  pub generated_types: BTreeMap<PathBuf, String>,
+  /// Files without any SQLs, whose colocated files are no longer needed
+  pub files_without_sqls: BTreeSet<PathBuf>,
+}
+
+impl Summary {
+  pub fn has_failures(&self) -> bool {
+    !self.failures.is_empty() || !self.generation_errors.is_empty()
+  }
+
+  pub fn print(&self) {
+    info!("Scanned {} files", self.files_scanned);
+
+    if self.queries_checked == 0 {
+      info!("No SQL queries found");
+    } else {
+      let num_sqls_msg = format!("Found {} SQL queries", self.queries_checked);
+      info!(num_sqls_msg);
+    }
+
+    for (file_path, failures) in &self.failures {
+      error!("{} SQLs failed to compile in {:?}", failures, file_path);
+    }
+
+    for (file_path, generation_error) in &self.generation_errors {
+      error!("Failed to generate types in {:?} - {}", file_path, generation_error);
+    }
+  }
+}
+
+/// Checks the SQLs of the file and collects their types into the summary
+pub async fn execute(
+  file_path: &Path,
+  queries: &HashMap<PathBuf, Vec<SQL>>,
+  handler: &Handler,
+  summary: &mut Summary,
+) -> Result<bool> {
+  if queries.values().all(Vec::is_empty) {
+    summary.files_without_sqls.insert(file_path.to_owned());
+  }
+
+  let (failed, types) = check(queries, handler, summary).await?;
+  summary.generated_types.extend(types);
+  Ok(failed)
+}
+
+/// Checks the SQLs of each file, returns whether any of them failed along with the types generated for each file
+/// Files without any types to write are left out
+pub async fn check(
+  queries: &HashMap<PathBuf, Vec<SQL>>,
+  handler: &Handler,
+  summary: &mut Summary,
+) -> Result<(bool, Vec<(PathBuf, String)>)> {
   let mut failed = false;
-  let should_generate_types = &CONFIG
-    .generate_types_config
-    .to_owned()
-    .filter(|x| x.enabled)
-    .is_some();
+  let mut types = vec![];
+  // `sqlx-ts snapshot` walks the queries the same way as type generation to find the tables they touch, without writing types
+  let is_snapshot = CLI_ARGS.is_snapshot();
+  let should_generate_types = &(is_snapshot
+    || CONFIG
+      .generate_types_config
+      .to_owned()
+      .filter(|x| x.enabled)
+      .is_some());
 
   for (file_path, sqls) in queries {
+    // Types are generated in the order the SQLs appear in the file
+    let mut sqls = sqls.iter().collect::<Vec<_>>();
+    sqls.sort_by_key(|sql| sql.span.primary_span().map(|span| span.lo));
+
     let mut sqls_to_write: Vec<String> = vec![];
     for sql in sqls {
-      let mut connection = DB_CONNECTIONS.lock().await;
-      let connection = &connection.get_connection(&sql.query).clone();
+      let mut connections = DB_CONNECTIONS.lock().await;
+      let connection_name = connections.get_connection_name(sql);
+
+      summary.queries_checked += 1;
+      let num_diagnostics = get_num_diagnostics();
+
+      // A connection that is not configured fails the SQL that uses it, rather than stopping the whole run
+      let Some(connection) = connections.get_connection(sql) else {
+        let message = format!("{DB_CONN_FROM_LOCAL_CACHE_ERROR} - connection: {connection_name}");
+        handler.span_err(sql.span.to_owned(), message.as_str());
+        set_query_name(num_diagnostics, sql);
+        *summary.failures.entry(file_path.to_owned()).or_default() += 1;
+        failed = true;
+        continue;
+      };
       let connection = &connection.lock().await;
 
-      let (explain_failed, ts_query) = &connection.prepare(sql, should_generate_types, handler).await?;
+      // A SQL that types cannot be generated for is reported in the summary, rather than stopping the whole run
+      let (explain_failed, ts_query) = &match connection.prepare(sql, should_generate_types, handler).await {
+        Ok((explain_failed, ts_query)) => {
+          if explain_failed {
+            *summary.failures.entry(file_path.to_owned()).or_default() += 1;
+          }
+          (explain_failed, ts_query)
+        }
+        Err(err) => {
+          // The summary already prints the error for human output, the other formats only report diagnostics
+          if CLI_ARGS.format != OutputFormat::Human {
+            handler.span_err(sql.span.to_owned(), err.to_string().as_str());
+          }
+          summary
+            .generation_errors
+            .push((file_path.to_owned(), err.to_string()));
+          (true, None)
+        }
+      };
+      set_query_name(num_diagnostics, sql);
 
       // If any prepare statement fails, we should set the failed flag as true
       // Use OR to accumulate failures - once failed, it stays failed
       failed = failed || *explain_failed;
 
-      if *should_generate_types {
-        let ts_query = &ts_query.clone().expect("Failed to generate types from query");
-        let ts_query = &ts_query.to_string();
-        sqls_to_write.push(ts_query.to_owned());
+      if is_snapshot {
+        DB_SCHEMA.lock().await.record_snapshot(&connection_name, connection);
+        continue;
+      }
+
+      // Offline mode does not generate types of SQLs that failed the checks
+      if let Some(ts_query) = ts_query {
+        sqls_to_write.push(ts_query.to_string());
       }
     }
 
-    if *should_generate_types {
+    if *should_generate_types && !is_snapshot {
       let is_sqls_empty = sqls_to_write.is_empty();
       let sqls_to_write = sqls_to_write.join("\n");
 
@@ -44,15 +153,9 @@ pub async fn execute(queries: &HashMap<PathBuf, Vec<SQL>>, handler: &Handler) ->
         continue;
       }
 
-      if CLI_ARGS.generate_path.is_none() {
-        // generates types colocated to source code
-        write_colocated_ts_file(file_path, sqls_to_write)?;
-      } else {
-        // generates types in a single directory/file
-        write_single_ts_file(sqls_to_write)?;
-      }
+      types.push((file_path.to_owned(), sqls_to_write));
     }
   }
 
-  Ok(failed)
+  Ok((failed, types))
 }
@@ -1,4 +1,7 @@
 pub mod connection;
+pub mod db_error;
 pub mod execute;
 pub mod mysql;
+pub mod offline;
 pub mod postgres;
+pub mod sqlite;
@@ -0,0 +1,99 @@
+use crate::ts_generator::types::ts_query::{DescribedStatement, TsFieldType};
+use mysql_async::consts::{ColumnFlags, ColumnType};
+use mysql_async::prelude::*;
+use mysql_async::Conn;
+
+/// Prepares the SQL through the binary protocol and reads the param and result columns the server reports
+pub async fn describe(conn: &mut Conn, query: &str) -> Option<DescribedStatement> {
+  let statement = conn.prep(query).await.ok()?;
+
+  let params = statement
+    .params()
+    .iter()
+    .map(|param| get_param_ts_field_type(param.column_type(), param.flags()))
+    .collect();
+
+  let columns = statement
+    .columns()
+    .iter()
+    .map(|column| {
+      let is_nullable = !column.flags().contains(ColumnFlags::NOT_NULL_FLAG);
+      (
+        column.name_str().to_string(),
+        get_ts_field_type(column.column_type(), column.flags()),
+        Some(is_nullable),
+      )
+    })
+    .collect();
+
+  let _ = conn.close(statement).await;
+
+  Some(DescribedStatement { params, columns })
+}
+
+/// Params the server could not derive a type of, e.g. on servers older than MySQL 8.0.22, are reported as `VAR_STRING`
+/// so those are left to the SQL walk
+fn get_param_ts_field_type(column_type: ColumnType, flags: ColumnFlags) -> TsFieldType {
+  match column_type {
+    ColumnType::MYSQL_TYPE_VAR_STRING => TsFieldType::Any,
+    _ => get_ts_field_type(column_type, flags),
+  }
+}
+
+fn get_ts_field_type(column_type: ColumnType, flags: ColumnFlags) -> TsFieldType {
+  // Values of enums are not part of the column definition, the SQL walk reads them from information_schema
+  if flags.contains(ColumnFlags::ENUM_FLAG) || flags.contains(ColumnFlags::SET_FLAG) {
+    return TsFieldType::Any;
+  }
+
+  match column_type {
+    ColumnType::MYSQL_TYPE_TINY => TsFieldType::Boolean,
+    ColumnType::MYSQL_TYPE_SHORT
+    | ColumnType::MYSQL_TYPE_LONG
+    | ColumnType::MYSQL_TYPE_INT24
+    | ColumnType::MYSQL_TYPE_LONGLONG
+    | ColumnType::MYSQL_TYPE_FLOAT
+    | ColumnType::MYSQL_TYPE_DOUBLE
+    | ColumnType::MYSQL_TYPE_DECIMAL
+    | ColumnType::MYSQL_TYPE_NEWDECIMAL
+    | ColumnType::MYSQL_TYPE_YEAR => TsFieldType::Number,
+    ColumnType::MYSQL_TYPE_VARCHAR
+    | ColumnType::MYSQL_TYPE_VAR_STRING
+    | ColumnType::MYSQL_TYPE_STRING
+    | ColumnType::MYSQL_TYPE_TINY_BLOB
+    | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+    | ColumnType::MYSQL_TYPE_LONG_BLOB
+    | ColumnType::MYSQL_TYPE_BLOB
+    | ColumnType::MYSQL_TYPE_BIT => TsFieldType::String,
+    ColumnType::MYSQL_TYPE_DATE
+    | ColumnType::MYSQL_TYPE_DATETIME
+    | ColumnType::MYSQL_TYPE_DATETIME2
+    | ColumnType::MYSQL_TYPE_TIMESTAMP
+    | ColumnType::MYSQL_TYPE_TIMESTAMP2 => TsFieldType::Date,
+    ColumnType::MYSQL_TYPE_JSON => TsFieldType::Object,
+    _ => TsFieldType::Any,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_param_ts_field_type() {
+    let params = [
+      (ColumnType::MYSQL_TYPE_LONGLONG, TsFieldType::Number),
+      (ColumnType::MYSQL_TYPE_VARCHAR, TsFieldType::String),
+      (ColumnType::MYSQL_TYPE_DATETIME, TsFieldType::Date),
+      (ColumnType::MYSQL_TYPE_VAR_STRING, TsFieldType::Any),
+    ];
+
+    for (column_type, ts_type) in params {
+      assert_eq!(
+        get_param_ts_field_type(column_type, ColumnFlags::empty()),
+        ts_type,
+        "{column_type:?}"
+      );
+    }
+  }
+}
@@ -6,13 +6,16 @@ pub struct MySqlConnectionManager {
   conn_url: String,
   // The connection name that user has defined in .sqlxrc.json
   connection_name: String,
+  // Statements that run on every new connection of the pool before it is handed out, e.g. `SET SESSION sql_mode = ...`
+  init_sql: Vec<String>,
 }
 
 impl MySqlConnectionManager {
-  pub fn new(conn_url: String, connection_name: String) -> Self {
+  pub fn new(conn_url: String, connection_name: String, init_sql: Vec<String>) -> Self {
     Self {
       conn_url,
       connection_name,
+      init_sql,
     }
   }
 }
@@ -27,7 +30,7 @@ impl bb8::ManageConnection for MySqlConnectionManager {
 
     let conn_opts = Opts::from_url(conn_url.as_str())?;
 
-    let conn = Conn::new(conn_opts).await.map_err(|err| {
+    let mut conn = Conn::new(conn_opts).await.map_err(|err| {
       match err {
         Error::Driver(driver_error) => {
           panic!("Driver error occurred while connecting to MySQL database - connection: {connection_name}, error: {driver_error}");
@@ -58,6 +61,17 @@ impl bb8::ManageConnection for MySqlConnectionManager {
       }
     }).unwrap();
 
+    for statement in &self.init_sql {
+      conn.query_drop(statement).await.map_err(|err| {
+        Error::Other(
+          format!(
+            "Failed to run the init statement {statement:?} on MySQL connection: {connection_name} - error: {err}"
+          )
+          .into(),
+        )
+      })?;
+    }
+
     Ok(conn)
   }
 
@@ -1,11 +1,63 @@
+use crate::common::lazy::CONFIG;
+use crate::common::query_span::get_offset_at_line_column;
 use crate::common::SQL;
 use crate::core::connection::DBConn;
+use crate::core::db_error::DbError;
+use crate::core::mysql::describe::describe;
 use crate::ts_generator::generator::generate_ts_interface;
 use crate::ts_generator::types::ts_query::TsQuery;
 use color_eyre::eyre::Result;
-use mysql_async::{prelude::*, Row};
+use mysql_async::{prelude::*, Conn, Row};
+use regex::Regex;
 
 use swc_common::errors::Handler;
+use swc_common::MultiSpan;
+
+/// MySQL tells where a syntax error is only through the text that follows it, e.g. `... near 'FORM items' at line 1`
+fn get_error_span(sql: &SQL, message: &str) -> MultiSpan {
+  let near = Regex::new(r"near '((?s).+)' at line (\d+)").unwrap();
+  let offset = near.captures(message).and_then(|captures| {
+    let line_start = get_offset_at_line_column(&sql.query, captures[2].parse().ok()?, 1)?;
+    sql.query[line_start..].find(&captures[1]).map(|idx| line_start + idx)
+  });
+
+  match offset {
+    Some(offset) => sql.span_at(offset),
+    None => sql.span.to_owned(),
+  }
+}
+
+/// Turns the error of the PREPARE statement into a diagnostic, along with "did you mean" suggestions for an unknown table
+/// or column
+async fn get_db_error(conn: &mut Conn, err: &mysql_async::Error, query: &str) -> DbError {
+  let mysql_async::Error::Server(server_err) = err else {
+    return DbError::connection_failure(err);
+  };
+
+  let mut error = DbError::new(Some(server_err.code.to_string()), &server_err.message);
+
+  // e.g. `Table 'app.itmes' doesn't exist` or `Unknown column 'i.nme' in 'field list'`
+  let unknown_table = Regex::new(r"^Table '(?:[^'.]+\.)?([^']+)' doesn't exist").unwrap();
+  let unknown_column = Regex::new(r"^Unknown column '(?:[^']+\.)?([^'.]+)' in").unwrap();
+
+  if let Some(captures) = unknown_table.captures(&server_err.message) {
+    let tables = conn
+      .query::<String, _>("SELECT TABLE_NAME FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE()")
+      .await
+      .unwrap_or_default();
+    error.suggest_table(&captures[1], &tables);
+  } else if let Some(captures) = unknown_column.captures(&server_err.message) {
+    let columns = conn
+      .query::<(String, String), _>(
+        "SELECT TABLE_NAME, COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE()",
+      )
+      .await
+      .unwrap_or_default();
+    error.suggest_column(&captures[1], &columns, query);
+  }
+
+  error
+}
 
 /// Runs the prepare statement on the input SQL.
 /// Validates the query is right by directly connecting to the configured database.
@@ -25,13 +77,24 @@ pub async fn prepare(
 
   {
     let explain_query = format!("PREPARE stmt FROM \"{}\"", sql.query);
-    let span = sql.span.to_owned();
     let conn = conn.lock().await;
-    let mut conn = conn.get().await?;
+    let mut conn = match conn.get().await {
+      Ok(conn) => conn,
+      Err(err) => {
+        DbError::connection_failure(err).emit(handler, sql.span.to_owned());
+        return Ok((true, None));
+      }
+    };
     let result = conn.query::<Row, String>(explain_query).await;
 
     if let Err(err) = result {
-      handler.span_bug_no_panic(span, err.to_string().as_str());
+      let error = get_db_error(&mut conn, &err, &sql.query).await;
+      error.emit(handler, get_error_span(sql, &error.message));
+
+      // Types cannot be generated without the database either
+      if !matches!(err, mysql_async::Error::Server(_)) {
+        return Ok((true, None));
+      }
       failed = true;
     }
   }
@@ -39,7 +102,19 @@ pub async fn prepare(
   let mut ts_query = None;
 
   if should_generate_types == &true {
-    ts_query = Some(generate_ts_interface(sql, db_conn).await?);
+    let mut query = generate_ts_interface(sql, db_conn).await?;
+
+    if !failed && CONFIG.should_describe(sql) {
+      let conn = conn.lock().await;
+      let conn = conn.get().await;
+      if let Ok(mut conn) = conn {
+        if let Some(described) = describe(&mut conn, &sql.query).await {
+          query.apply_described_statement(described);
+        }
+      }
+    }
+
+    ts_query = Some(query);
   }
 
   Ok((failed, ts_query))
@@ -0,0 +1,489 @@
+use crate::common::logger::*;
+use crate::common::types::DatabaseType;
+use crate::core::offline::snapshot::{ColumnSnapshot, ConnectionSnapshot, TableSnapshot};
+use crate::ts_generator::generator::get_sql_dialect;
+use crate::ts_generator::sql_parser::quoted_strings::{split_qualified_name, DisplayIndent, DisplayObjectName};
+use color_eyre::eyre::{eyre, Result};
+use sqlparser::ast::{
+  AlterColumnOperation, AlterTableOperation, AlterType, AlterTypeAddValuePosition, AlterTypeOperation, ColumnDef,
+  ColumnOption, DataType, EnumMember, ExactNumberInfo, Expr, ObjectName, ObjectType, RenameTableNameKind, Statement,
+  TableConstraint, TimezoneInfo, UserDefinedTypeRepresentation,
+};
+use sqlparser::dialect::Dialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+struct MigrationColumn {
+  data_type: DataType,
+  is_nullable: bool,
+}
+
+/// Schema built by replaying DDL statements of migration files in order, so types can be generated without a database
+pub struct MigrationSchema {
+  db_type: DatabaseType,
+  // Columns are kept in sqlparser's DataType until the end, as enum types can still change after a column uses them
+  tables: BTreeMap<String, BTreeMap<String, MigrationColumn>>,
+  enums: HashMap<String, Vec<String>>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PathChunk {
+  // Digits without their leading zeros, ordered by their number of digits first so they are compared by value
+  Number(usize, String),
+  Text(String),
+}
+
+/// Splits a path into runs of digits and runs of other characters, so paths are ordered by the value of their numbers
+fn natural_sort_key(path: &Path) -> Vec<PathChunk> {
+  let path = path.to_string_lossy();
+  let mut chunks = vec![];
+  let mut rest = path.as_ref();
+
+  while let Some(first) = rest.chars().next() {
+    let is_digit = first.is_ascii_digit();
+    let end = rest
+      .find(|c: char| c.is_ascii_digit() != is_digit)
+      .unwrap_or(rest.len());
+    let (chunk, tail) = rest.split_at(end);
+
+    chunks.push(if is_digit {
+      let digits = chunk.trim_start_matches('0');
+      PathChunk::Number(digits.len(), digits.to_string())
+    } else {
+      PathChunk::Text(chunk.to_string())
+    });
+    rest = tail;
+  }
+
+  chunks
+}
+
+impl MigrationSchema {
+  pub fn new(db_type: DatabaseType) -> Self {
+    Self {
+      db_type,
+      tables: BTreeMap::new(),
+      enums: HashMap::new(),
+    }
+  }
+
+  /// Reads a single migration file or every `.sql` file of a folder, in the natural order of their paths
+  /// Down migrations (`down.sql` or `*.down.sql`) are skipped as they revert the schema
+  pub fn load(path: &Path, db_type: &DatabaseType) -> Result<ConnectionSnapshot> {
+    let mut schema = Self::new(db_type.to_owned());
+
+    for file in Self::get_migration_files(path)? {
+      let content = fs::read_to_string(&file).map_err(|err| eyre!("Failed to read the migration {file:?} - {err}"))?;
+      schema
+        .apply_sql(content.as_str())
+        .map_err(|err| eyre!("Failed to parse the migration {file:?} - {err}"))?;
+    }
+
+    Ok(schema.into_snapshot())
+  }
+
+  fn get_migration_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.exists() {
+      return Err(eyre!(
+        "Failed to find migrations to build the schema from - path: {path:?}"
+      ));
+    }
+
+    if path.is_file() {
+      return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = WalkDir::new(path)
+      .into_iter()
+      .flatten()
+      .map(|entry| entry.into_path())
+      .filter(|file| file.is_file() && file.extension().is_some_and(|ext| ext == "sql"))
+      .filter(|file| {
+        let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+        file_name != "down.sql" && !file_name.ends_with(".down.sql")
+      })
+      .collect();
+
+    // Version prefixes are not always zero-padded, e.g. `2_create_pets.sql` has to run before `10_alter_pets.sql`
+    files.sort_by_cached_key(|file| (natural_sort_key(file), file.to_owned()));
+    Ok(files)
+  }
+
+  /// Applies every statement of a migration, statements that cannot be parsed (e.g. function bodies) are skipped
+  pub fn apply_sql(&mut self, sql: &str) -> Result<()> {
+    let dialect = get_sql_dialect(&self.db_type);
+    let tokens = Tokenizer::new(&*dialect, sql).tokenize()?;
+
+    for statement_tokens in tokens.split(|token| token == &Token::SemiColon) {
+      let is_empty = statement_tokens
+        .iter()
+        .all(|token| matches!(token, Token::Whitespace(_)));
+      if is_empty {
+        continue;
+      }
+
+      match Self::parse_statement(&*dialect, statement_tokens) {
+        Ok(statement) => self.apply_statement(&statement),
+        Err(err) => {
+          let statement = statement_tokens.iter().map(|x| x.to_string()).collect::<String>();
+          warning!(
+            "Skipping a migration statement that cannot be parsed - statement: {}, error: {}",
+            statement.trim(),
+            err
+          );
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn parse_statement(dialect: &dyn Dialect, tokens: &[Token]) -> Result<Statement> {
+    Ok(Parser::new(dialect).with_tokens(tokens.to_vec()).parse_statement()?)
+  }
+
+  fn apply_statement(&mut self, statement: &Statement) {
+    match statement {
+      Statement::CreateTable(create_table) => {
+        let table_name = DisplayObjectName(&create_table.name).to_string();
+        if create_table.query.is_some() || create_table.like.is_some() || create_table.clone.is_some() {
+          warning!(
+            "Skipping the migration of table {} as columns of CREATE TABLE AS, LIKE or CLONE cannot be inferred",
+            table_name
+          );
+          return;
+        }
+        if create_table.if_not_exists && self.tables.contains_key(&table_name) {
+          return;
+        }
+
+        let columns = create_table
+          .columns
+          .iter()
+          .map(|column| (DisplayIndent(&column.name).to_string(), Self::get_column(column)))
+          .collect();
+        self.tables.insert(table_name.to_owned(), columns);
+
+        for constraint in &create_table.constraints {
+          self.apply_constraint(&table_name, constraint);
+        }
+      }
+      Statement::AlterTable { name, operations, .. } => {
+        let mut table_name = DisplayObjectName(name).to_string();
+        for operation in operations {
+          table_name = self.apply_alter_table(table_name, operation);
+        }
+      }
+      Statement::CreateType {
+        name,
+        representation: UserDefinedTypeRepresentation::Enum { labels },
+      } => {
+        let labels = labels.iter().map(|label| label.value.to_owned()).collect();
+        self.enums.insert(DisplayObjectName(name).to_string(), labels);
+      }
+      Statement::AlterType(AlterType { name, operation }) => self.apply_alter_type(name, operation),
+      Statement::Drop { object_type, names, .. } => {
+        for name in names {
+          let name = DisplayObjectName(name).to_string();
+          match object_type {
+            ObjectType::Table => self.tables.remove(&name).map(|_| ()),
+            ObjectType::Type => self.enums.remove(&name).map(|_| ()),
+            _ => None,
+          };
+        }
+      }
+      _ => {}
+    }
+  }
+
+  /// Applies an operation of ALTER TABLE and returns the name of the table after the operation
+  fn apply_alter_table(&mut self, table_name: String, operation: &AlterTableOperation) -> String {
+    let Some(columns) = self.tables.get_mut(&table_name) else {
+      warning!(
+        "Skipping ALTER TABLE of table {} that is not created by previous migrations",
+        table_name
+      );
+      return table_name;
+    };
+
+    match operation {
+      AlterTableOperation::AddColumn {
+        column_def,
+        if_not_exists,
+        ..
+      } => {
+        let column_name = DisplayIndent(&column_def.name).to_string();
+        if !(*if_not_exists && columns.contains_key(&column_name)) {
+          columns.insert(column_name, Self::get_column(column_def));
+        }
+      }
+      AlterTableOperation::DropColumn { column_names, .. } => {
+        for column_name in column_names {
+          columns.remove(&DisplayIndent(column_name).to_string());
+        }
+      }
+      AlterTableOperation::RenameColumn {
+        old_column_name,
+        new_column_name,
+      } => {
+        if let Some(column) = columns.remove(&DisplayIndent(old_column_name).to_string()) {
+          columns.insert(DisplayIndent(new_column_name).to_string(), column);
+        }
+      }
+      AlterTableOperation::AlterColumn { column_name, op } => {
+        if let Some(column) = columns.get_mut(&DisplayIndent(column_name).to_string()) {
+          match op {
+            AlterColumnOperation::SetNotNull => column.is_nullable = false,
+            AlterColumnOperation::DropNotNull => column.is_nullable = true,
+            AlterColumnOperation::SetDataType { data_type, .. } => column.data_type = data_type.to_owned(),
+            _ => {}
+          }
+        }
+      }
+      AlterTableOperation::ChangeColumn {
+        old_name,
+        new_name,
+        data_type,
+        options,
+        ..
+      } => {
+        columns.remove(&DisplayIndent(old_name).to_string());
+        columns.insert(
+          DisplayIndent(new_name).to_string(),
+          Self::get_column_from_options(data_type, options.iter()),
+        );
+      }
+      AlterTableOperation::ModifyColumn {
+        col_name,
+        data_type,
+        options,
+        ..
+      } => {
+        columns.insert(
+          DisplayIndent(col_name).to_string(),
+          Self::get_column_from_options(data_type, options.iter()),
+        );
+      }
+      AlterTableOperation::AddConstraint { constraint, .. } => self.apply_constraint(&table_name, constraint),
+      AlterTableOperation::RenameTable { table_name: new_name } => {
+        let new_name = match new_name {
+          RenameTableNameKind::As(name) | RenameTableNameKind::To(name) => DisplayObjectName(name).to_string(),
+        };
+        // A renamed table stays in its schema unless the new name says otherwise
+        let new_name = match (split_qualified_name(&table_name), split_qualified_name(&new_name)) {
+          ((Some(schema), _), (None, new_name)) => format!("{schema}.{new_name}"),
+          _ => new_name,
+        };
+        if let Some(columns) = self.tables.remove(&table_name) {
+          self.tables.insert(new_name.to_owned(), columns);
+        }
+        return new_name;
+      }
+      _ => {}
+    }
+
+    table_name
+  }
+
+  fn apply_alter_type(&mut self, name: &ObjectName, operation: &AlterTypeOperation) {
+    let type_name = DisplayObjectName(name).to_string();
+
+    match operation {
+      AlterTypeOperation::Rename(rename) => {
+        if let Some(labels) = self.enums.remove(&type_name) {
+          let new_name = match split_qualified_name(&type_name) {
+            (Some(schema), _) => format!("{schema}.{}", rename.new_name.value),
+            (None, _) => rename.new_name.value.to_owned(),
+          };
+          self.enums.insert(new_name, labels);
+        }
+      }
+      AlterTypeOperation::AddValue(add_value) => {
+        if let Some(labels) = self.enums.get_mut(&type_name) {
+          let value = add_value.value.value.to_owned();
+          if labels.contains(&value) {
+            return;
+          }
+          let position = match &add_value.position {
+            Some(AlterTypeAddValuePosition::Before(label)) => labels.iter().position(|x| x == &label.value),
+            Some(AlterTypeAddValuePosition::After(label)) => {
+              labels.iter().position(|x| x == &label.value).map(|index| index + 1)
+            }
+            None => None,
+          };
+          labels.insert(position.unwrap_or(labels.len()), value);
+        }
+      }
+      AlterTypeOperation::RenameValue(rename_value) => {
+        if let Some(labels) = self.enums.get_mut(&type_name) {
+          for label in labels.iter_mut().filter(|label| **label == rename_value.from.value) {
+            *label = rename_value.to.value.to_owned();
+          }
+        }
+      }
+    }
+  }
+
+  /// Columns of a primary key are never nullable
+  fn apply_constraint(&mut self, table_name: &str, constraint: &TableConstraint) {
+    let (
+      Some(columns),
+      TableConstraint::PrimaryKey {
+        columns: key_columns, ..
+      },
+    ) = (self.tables.get_mut(table_name), constraint)
+    else {
+      return;
+    };
+
+    for key_column in key_columns {
+      if let Expr::Identifier(ident) = &key_column.column.expr {
+        if let Some(column) = columns.get_mut(&DisplayIndent(ident).to_string()) {
+          column.is_nullable = false;
+        }
+      }
+    }
+  }
+
+  fn get_column(column_def: &ColumnDef) -> MigrationColumn {
+    Self::get_column_from_options(&column_def.data_type, column_def.options.iter().map(|x| &x.option))
+  }
+
+  fn get_column_from_options<'a>(
+    data_type: &DataType,
+    options: impl Iterator<Item = &'a ColumnOption>,
+  ) -> MigrationColumn {
+    // SERIAL types of both databases imply NOT NULL
+    let mut is_nullable = !matches!(
+      Self::get_custom_type_name(data_type).as_deref(),
+      Some("serial" | "serial2" | "serial4" | "serial8" | "smallserial" | "bigserial")
+    );
+
+    for option in options {
+      match option {
+        ColumnOption::NotNull | ColumnOption::Unique { is_primary: true, .. } => is_nullable = false,
+        ColumnOption::Null => is_nullable = true,
+        _ => {}
+      }
+    }
+
+    MigrationColumn {
+      data_type: data_type.to_owned(),
+      is_nullable,
+    }
+  }
+
+  fn get_custom_type_name(data_type: &DataType) -> Option<String> {
+    match data_type {
+      DataType::Custom(name, _) => Some(DisplayObjectName(name).to_string().to_lowercase()),
+      _ => None,
+    }
+  }
+
+  fn into_snapshot(self) -> ConnectionSnapshot {
+    let mut snapshot = ConnectionSnapshot::new(self.db_type.to_owned());
+
+    for (table_name, columns) in &self.tables {
+      let table: TableSnapshot = columns
+        .iter()
+        .map(|(column_name, column)| {
+          let (data_type, enum_values) = match self.db_type {
+            DatabaseType::Postgres => self.get_postgres_data_type(&column.data_type),
+            DatabaseType::Mysql => Self::get_mysql_data_type(&column.data_type),
+            // PRAGMA table_info of SQLite reports the type as it was declared
+            DatabaseType::Sqlite => (column.data_type.to_string().to_lowercase(), None),
+          };
+          let column = ColumnSnapshot {
+            data_type,
+            is_nullable: column.is_nullable,
+            enum_values,
+          };
+          (column_name.to_owned(), column)
+        })
+        .collect();
+
+      snapshot.tables.insert(table_name.to_owned(), table);
+    }
+
+    snapshot
+  }
+
+  /// Translates the data type into DATA_TYPE that information_schema.COLUMNS of PostgreSQL would return
+  fn get_postgres_data_type(&self, data_type: &DataType) -> (String, Option<Vec<String>>) {
+    let data_type = match data_type {
+      DataType::SmallInt(_) | DataType::Int2(_) => "smallint",
+      DataType::Int(_) | DataType::Int4(_) | DataType::Integer(_) => "integer",
+      DataType::BigInt(_) | DataType::Int8(_) => "bigint",
+      DataType::Real | DataType::Float4 => "real",
+      DataType::Float(ExactNumberInfo::Precision(precision)) if *precision <= 24 => "real",
+      DataType::Float(_) | DataType::Float8 | DataType::Double(_) | DataType::DoublePrecision => "double precision",
+      DataType::Numeric(_) | DataType::Decimal(_) | DataType::Dec(_) => "numeric",
+      DataType::Character(_) | DataType::Char(_) => "character",
+      DataType::CharacterVarying(_) | DataType::CharVarying(_) | DataType::Varchar(_) => "character varying",
+      DataType::Text => "text",
+      DataType::Bytea => "bytea",
+      DataType::Uuid => "uuid",
+      DataType::Bool | DataType::Boolean => "boolean",
+      DataType::JSON => "json",
+      DataType::JSONB => "jsonb",
+      DataType::Array(_) => "ARRAY",
+      DataType::Date => "date",
+      DataType::Timestamp(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) => "timestamp with time zone",
+      DataType::Timestamp(_, _) => "timestamp without time zone",
+      DataType::Time(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) => "time with time zone",
+      DataType::Time(_, _) => "time without time zone",
+      DataType::Custom(name, _) => {
+        let type_name = DisplayObjectName(name).to_string();
+        return match type_name.to_lowercase().as_str() {
+          "smallserial" | "serial2" => ("smallint".to_string(), None),
+          "serial" | "serial4" => ("integer".to_string(), None),
+          "bigserial" | "serial8" => ("bigint".to_string(), None),
+          _ => ("USER-DEFINED".to_string(), self.enums.get(&type_name).cloned()),
+        };
+      }
+      _ => return (data_type.to_string().to_lowercase(), None),
+    };
+
+    (data_type.to_string(), None)
+  }
+
+  /// Translates the data type into DATA_TYPE that information_schema.COLUMNS of MySQL would return
+  fn get_mysql_data_type(data_type: &DataType) -> (String, Option<Vec<String>>) {
+    let data_type = match data_type {
+      DataType::TinyInt(_) | DataType::TinyIntUnsigned(_) | DataType::Bool | DataType::Boolean => "tinyint",
+      DataType::SmallInt(_) | DataType::SmallIntUnsigned(_) => "smallint",
+      DataType::MediumInt(_) | DataType::MediumIntUnsigned(_) => "mediumint",
+      DataType::Int(_) | DataType::Integer(_) | DataType::IntUnsigned(_) | DataType::IntegerUnsigned(_) => "int",
+      DataType::BigInt(_) | DataType::BigIntUnsigned(_) => "bigint",
+      DataType::Decimal(_) | DataType::DecimalUnsigned(_) | DataType::Dec(_) | DataType::Numeric(_) => "decimal",
+      DataType::Float(_) | DataType::FloatUnsigned(_) => "float",
+      DataType::Double(_) | DataType::DoubleUnsigned(_) | DataType::DoublePrecision | DataType::Real => "double",
+      DataType::Char(_) | DataType::Character(_) => "char",
+      DataType::Varchar(_) | DataType::CharacterVarying(_) | DataType::Nvarchar(_) => "varchar",
+      DataType::Binary(_) => "binary",
+      DataType::Varbinary(_) => "varbinary",
+      DataType::Blob(_) => "blob",
+      DataType::Datetime(_) => "datetime",
+      DataType::Timestamp(_, _) => "timestamp",
+      DataType::Time(_, _) => "time",
+      DataType::JSON => "json",
+      DataType::Enum(members, _) => {
+        let values = members
+          .iter()
+          .map(|member| match member {
+            EnumMember::Name(name) | EnumMember::NamedValue(name, _) => name.to_owned(),
+          })
+          .collect();
+        return ("enum".to_string(), Some(values));
+      }
+      DataType::Custom(name, _) if DisplayObjectName(name).to_string().to_lowercase() == "serial" => "bigint",
+      _ => return (data_type.to_string().to_lowercase(), None),
+    };
+
+    (data_type.to_string(), None)
+  }
+}
@@ -0,0 +1,60 @@
+use crate::common::SQL;
+use crate::core::connection::DBConn;
+use crate::core::db_error::DbError;
+use crate::core::offline::snapshot::ConnectionSnapshot;
+use crate::ts_generator::generator::{generate_ts_interface, get_sql_dialect};
+use crate::ts_generator::sql_parser::quoted_strings::split_qualified_name;
+use crate::ts_generator::types::ts_query::TsQuery;
+use color_eyre::eyre::Result;
+use sqlparser::parser::Parser;
+
+use swc_common::errors::Handler;
+
+/// Validates the input SQL against the schema snapshot instead of a live database.
+/// The query must parse and every table it touches must exist in the snapshot; columns are checked while generating types,
+/// which always happens in offline mode as it is the only way to verify them
+pub async fn prepare(
+  db_conn: &DBConn,
+  snapshot: &ConnectionSnapshot,
+  sql: &SQL,
+  should_generate_types: &bool,
+  handler: &Handler,
+) -> Result<(bool, Option<TsQuery>)> {
+  let span = sql.span.to_owned();
+  let dialect = get_sql_dialect(&snapshot.db_type);
+
+  let statements = match Parser::parse_sql(&*dialect, &sql.query) {
+    Ok(statements) => statements,
+    Err(err) => {
+      handler.span_bug_no_panic(sql.span_of_parser_error(&err), err.to_string().as_str());
+      return Ok((true, None));
+    }
+  };
+
+  let missing_tables = snapshot.find_missing_tables(&statements);
+  if !missing_tables.is_empty() {
+    let tables = snapshot
+      .tables
+      .keys()
+      .map(|table| split_qualified_name(table).1.to_string())
+      .collect::<Vec<_>>();
+
+    for table in missing_tables {
+      let mut error = DbError::new(
+        None,
+        &format!("relation \"{table}\" does not exist in the schema snapshot"),
+      );
+      error.suggest_table(split_qualified_name(&table).1, &tables);
+      error.emit(handler, span.clone());
+    }
+    return Ok((true, None));
+  }
+
+  match generate_ts_interface(sql, db_conn).await {
+    Ok(ts_query) => Ok((false, should_generate_types.then_some(ts_query))),
+    Err(err) => {
+      handler.span_bug_no_panic(span, err.to_string().as_str());
+      Ok((true, None))
+    }
+  }
+}
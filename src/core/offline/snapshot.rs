@@ -0,0 +1,207 @@
+use crate::common::types::DatabaseType;
+use crate::ts_generator::information_schema::{Field, Fields};
+use crate::ts_generator::sql_parser::quoted_strings::{split_qualified_name, DisplayObjectName};
+use crate::ts_generator::types::ts_query::TsFieldType;
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{ObjectName, Query, Statement, TableFactor, Visit, Visitor};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Default location of the schema snapshot, relative to where sqlx-ts is executed
+pub const DEFAULT_SNAPSHOT_PATH: &str = ".sqlxsnapshot.json";
+
+/// A column as it was described by the information_schema of the database
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ColumnSnapshot {
+  pub data_type: String,
+  pub is_nullable: bool,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub enum_values: Option<Vec<String>>,
+}
+
+/// Columns of a single table, keyed by the column name
+pub type TableSnapshot = BTreeMap<String, ColumnSnapshot>;
+
+/// Tables of a single connection defined in the configuration
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ConnectionSnapshot {
+  pub db_type: DatabaseType,
+  pub tables: BTreeMap<String, TableSnapshot>,
+  // Schemas that unqualified tables are looked up from, in order, it comes from PG_SEARCH_PATH of the connection
+  #[serde(skip)]
+  pub search_path: Vec<String>,
+}
+
+impl ConnectionSnapshot {
+  pub fn new(db_type: DatabaseType) -> Self {
+    Self {
+      db_type,
+      tables: BTreeMap::new(),
+      search_path: vec![],
+    }
+  }
+
+  /// Sets the search_path of a Postgres connection, it defaults to `public` like a database without `$user` schemas
+  pub fn with_search_path(mut self, pg_search_path: &Option<String>) -> Self {
+    if !matches!(self.db_type, DatabaseType::Postgres) {
+      return self;
+    }
+
+    self.search_path = pg_search_path
+      .as_deref()
+      .unwrap_or("public")
+      .split(',')
+      .map(|schema| schema.trim().trim_matches('"').to_string())
+      .filter(|schema| !schema.is_empty() && schema != "$user")
+      .collect();
+    self
+  }
+
+  /// Finds the table the way the database would resolve the name used by a query
+  ///
+  /// A qualified name is resolved exactly, except that tables recorded without a schema (e.g. `CREATE TABLE users`
+  /// in a migration) live in the first schema of the search_path. An unqualified name walks the search_path in order.
+  fn resolve_table(&self, table_name: &str) -> Option<&TableSnapshot> {
+    if let Some(table) = self.tables.get(table_name) {
+      return Some(table);
+    }
+
+    match split_qualified_name(table_name) {
+      (Some(schema), table) if self.search_path.first().map(String::as_str) == Some(schema) => self.tables.get(table),
+      (Some(_), _) => None,
+      (None, table) => self
+        .search_path
+        .iter()
+        .find_map(|schema| self.tables.get(&format!("{schema}.{table}"))),
+    }
+  }
+
+  /// Equivalent of `DBSchema::fetch_table` for offline mode, it returns None if any of the tables is not in the snapshot
+  pub fn fetch_table(&self, table_names: &Vec<&str>) -> Option<Fields> {
+    let mut fields = Fields::new();
+
+    for table_name in table_names {
+      let table = self.resolve_table(table_name)?;
+
+      for (column_name, column) in table {
+        let field_type = match self.db_type {
+          DatabaseType::Postgres => TsFieldType::get_ts_field_type_from_postgres_field_type(
+            column.data_type.to_owned(),
+            column_name.to_owned(),
+            table_name.to_string(),
+            column.enum_values.to_owned(),
+          ),
+          DatabaseType::Mysql => TsFieldType::get_ts_field_type_from_mysql_field_type(
+            column.data_type.to_owned(),
+            table_name.to_string(),
+            column_name.to_owned(),
+            column.enum_values.to_owned(),
+          ),
+          DatabaseType::Sqlite => TsFieldType::get_ts_field_type_from_sqlite_field_type(column.data_type.to_owned()),
+        };
+
+        fields.insert(
+          column_name.to_owned(),
+          Field {
+            field_type,
+            is_nullable: column.is_nullable,
+          },
+        );
+      }
+    }
+
+    Some(fields)
+  }
+
+  /// Returns the tables referenced by the statements that do not exist in the snapshot
+  /// CTEs and table-valued functions are not tables, so they are never reported
+  pub fn find_missing_tables(&self, statements: &[Statement]) -> Vec<String> {
+    let mut visitor = RelationVisitor::default();
+    for statement in statements {
+      let _ = statement.visit(&mut visitor);
+    }
+
+    visitor
+      .relations
+      .into_iter()
+      .filter(|relation| !visitor.ignored.contains(relation) && self.resolve_table(relation).is_none())
+      .collect()
+  }
+}
+
+/// Snapshot of the database schemas that queries of a project touch, it allows running sqlx-ts without any database
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SchemaSnapshot {
+  pub connections: BTreeMap<String, ConnectionSnapshot>,
+}
+
+impl SchemaSnapshot {
+  pub fn load(path: &Path) -> Result<Self> {
+    let content = fs::read_to_string(path).map_err(|err| {
+      eyre!("Failed to read the schema snapshot {path:?}, run `sqlx-ts snapshot` to create one - error: {err}")
+    })?;
+
+    serde_json::from_str(content.as_str())
+      .map_err(|err| eyre!("Invalid schema snapshot {path:?}, run `sqlx-ts snapshot` to recreate it - error: {err}"))
+  }
+
+  pub fn write(&self, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let mut content = serde_json::to_string_pretty(self)?;
+    content.push('\n');
+    fs::write(path, content)?;
+    Ok(())
+  }
+
+  /// Adds the tables to the connection's snapshot, keeping the tables recorded previously
+  pub fn add_tables(&mut self, connection: &str, db_type: DatabaseType, tables: BTreeMap<String, TableSnapshot>) {
+    self
+      .connections
+      .entry(connection.to_string())
+      .or_insert_with(|| ConnectionSnapshot::new(db_type))
+      .tables
+      .extend(tables);
+  }
+}
+
+/// Collects names of all relations in a statement along with names that look like relations but are not tables
+#[derive(Default)]
+struct RelationVisitor {
+  relations: BTreeSet<String>,
+  ignored: BTreeSet<String>,
+}
+
+impl Visitor for RelationVisitor {
+  type Break = ();
+
+  fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+    if let Some(with) = &query.with {
+      for cte in &with.cte_tables {
+        self.ignored.insert(cte.alias.name.value.to_owned());
+      }
+    }
+    ControlFlow::Continue(())
+  }
+
+  fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+    // Table-valued functions such as `jsonb_to_recordset($1)` are parsed as tables with args
+    if let TableFactor::Table {
+      name, args: Some(_), ..
+    } = table_factor
+    {
+      self.ignored.insert(DisplayObjectName(name).to_string());
+    }
+    ControlFlow::Continue(())
+  }
+
+  fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+    self.relations.insert(DisplayObjectName(relation).to_string());
+    ControlFlow::Continue(())
+  }
+}
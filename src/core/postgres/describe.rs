@@ -0,0 +1,95 @@
+use crate::ts_generator::types::ts_query::{DescribedStatement, TsFieldType};
+use tokio_postgres::types::{Kind, Type};
+use tokio_postgres::Client;
+
+/// Prepares the SQL through the extended query protocol and reads the param and column types the server reports
+/// PostgreSQL does not report nullability of the columns of a prepared statement, so it is left to the SQL walk
+pub async fn describe(client: &Client, query: &str) -> Option<DescribedStatement> {
+  let statement = client.prepare(query).await.ok()?;
+
+  Some(DescribedStatement {
+    params: statement.params().iter().map(get_ts_field_type).collect(),
+    columns: statement
+      .columns()
+      .iter()
+      .map(|column| (column.name().to_string(), get_ts_field_type(column.type_()), None))
+      .collect(),
+  })
+}
+
+/// Maps the type of a param or column through the same mapping as the columns read from information_schema, so a column
+/// gets the same type whether or not the statement is described
+fn get_ts_field_type(pg_type: &Type) -> TsFieldType {
+  let (data_type, enum_values) = match pg_type.kind() {
+    Kind::Enum(variants) => ("USER-DEFINED", Some(variants.to_owned())),
+    Kind::Array(_) => ("ARRAY", None),
+    Kind::Domain(base) => return get_ts_field_type(base),
+    _ => (get_data_type(pg_type), None),
+  };
+
+  TsFieldType::get_ts_field_type_from_postgres_field_type(
+    data_type.to_string(),
+    String::new(),
+    String::new(),
+    enum_values,
+  )
+}
+
+/// Name of the type as information_schema reports it in `data_type` e.g. `integer` for `int4`
+fn get_data_type(pg_type: &Type) -> &str {
+  match *pg_type {
+    Type::BOOL => "boolean",
+    Type::INT2 => "smallint",
+    Type::INT4 => "integer",
+    Type::INT8 => "bigint",
+    Type::FLOAT4 => "real",
+    Type::FLOAT8 => "double precision",
+    Type::NUMERIC => "numeric",
+    Type::TEXT => "text",
+    Type::VARCHAR => "character varying",
+    Type::BPCHAR => "character",
+    Type::BYTEA => "bytea",
+    Type::UUID => "uuid",
+    Type::JSON => "json",
+    Type::JSONB => "jsonb",
+    Type::DATE => "date",
+    Type::TIME => "time without time zone",
+    Type::TIMESTAMP => "timestamp without time zone",
+    Type::TIMESTAMPTZ => "timestamp with time zone",
+    _ => pg_type.name(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_ts_field_type_matches_information_schema() {
+    let types = [
+      (Type::INT4, "integer"),
+      (Type::INT8, "bigint"),
+      (Type::VARCHAR, "character varying"),
+      (Type::BOOL, "boolean"),
+      (Type::JSONB, "jsonb"),
+      (Type::DATE, "date"),
+      (Type::TIME, "time without time zone"),
+      (Type::TIMESTAMP, "timestamp without time zone"),
+      (Type::TIMESTAMPTZ, "timestamp with time zone"),
+      (Type::INT4_ARRAY, "ARRAY"),
+    ];
+
+    for (pg_type, data_type) in types {
+      assert_eq!(
+        get_ts_field_type(&pg_type),
+        TsFieldType::get_ts_field_type_from_postgres_field_type(
+          data_type.to_string(),
+          String::new(),
+          String::new(),
+          None
+        ),
+        "{pg_type}"
+      );
+    }
+  }
+}
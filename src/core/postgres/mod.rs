@@ -1,2 +1,3 @@
+pub mod describe;
 pub mod pool;
 pub mod prepare;
@@ -3,46 +3,56 @@ pub use bb8;
 use tokio;
 use tokio_postgres::{Client, Error, NoTls};
 
+/// Errors of opening a new connection of the pool
+#[derive(Debug, thiserror::Error)]
+pub enum PostgresPoolError {
+  #[error(transparent)]
+  Postgres(#[from] Error),
+  #[error("Failed to run the init statement {statement:?} on a Postgres connection - error: {source}")]
+  InitSql { statement: String, source: Error },
+}
+
 pub struct PostgresConnectionManager {
   conn_url: String,
+  // Statements that run on every new connection of the pool before it is handed out, e.g. `SET search_path TO ...`
+  init_sql: Vec<String>,
 }
 
 impl PostgresConnectionManager {
-  pub fn new(conn_url: String) -> Self {
-    Self { conn_url }
+  pub fn new(conn_url: String, init_sql: Vec<String>) -> Self {
+    Self { conn_url, init_sql }
   }
 }
 
 impl bb8::ManageConnection for PostgresConnectionManager {
   type Connection = Client;
-  type Error = Error;
+  type Error = PostgresPoolError;
 
-  async fn connect(&self) -> Result<Client, Error> {
+  async fn connect(&self) -> Result<Client, PostgresPoolError> {
     let conn_url = self.conn_url.clone();
 
-    let (client, connection) =
-      tokio_postgres::connect(&conn_url, NoTls)
-        .await
-        .map_err(|err| match err.as_db_error() {
-          Some(db_err) => {
-            let message = format!(
-              "Postgres database connection error - code: {:?}, message: {:?}",
-              db_err.code(),
-              db_err.message()
-            );
-            panic!("{message}")
-          }
-          None => {
-            // Include the source error details for better error messages
-            use std::error::Error as StdError;
-            let error_msg = if let Some(source) = err.source() {
-              format!("Postgres database connection error: {err}: {source}")
-            } else {
-              format!("Postgres database connection error: {err}")
-            };
-            panic!("{error_msg}")
-          }
-        })?;
+    let (client, connection) = tokio_postgres::connect(&conn_url, NoTls)
+      .await
+      .map_err::<Error, _>(|err| match err.as_db_error() {
+        Some(db_err) => {
+          let message = format!(
+            "Postgres database connection error - code: {:?}, message: {:?}",
+            db_err.code(),
+            db_err.message()
+          );
+          panic!("{message}")
+        }
+        None => {
+          // Include the source error details for better error messages
+          use std::error::Error as StdError;
+          let error_msg = if let Some(source) = err.source() {
+            format!("Postgres database connection error: {err}: {source}")
+          } else {
+            format!("Postgres database connection error: {err}")
+          };
+          panic!("{error_msg}")
+        }
+      })?;
 
     // The connection object performs the actual communication with the database,
     // so spawn it off to run on its own.
@@ -51,11 +61,21 @@ impl bb8::ManageConnection for PostgresConnectionManager {
         panic!("Postgres database connection error: {err}")
       }
     });
+
+    for statement in &self.init_sql {
+      client
+        .batch_execute(statement)
+        .await
+        .map_err(|source| PostgresPoolError::InitSql {
+          statement: statement.to_owned(),
+          source,
+        })?;
+    }
     Ok(client)
   }
 
-  async fn is_valid(&self, client: &mut Client) -> Result<(), Error> {
-    client.simple_query("SELECT 1;").await.map(|_| ())
+  async fn is_valid(&self, client: &mut Client) -> Result<(), PostgresPoolError> {
+    Ok(client.simple_query("SELECT 1;").await.map(|_| ())?)
   }
 
   fn has_broken(&self, client: &mut Client) -> bool {
@@ -1,10 +1,79 @@
+use crate::common::lazy::CONFIG;
 use crate::common::SQL;
 use crate::core::connection::DBConn;
+use crate::core::db_error::DbError;
+use crate::core::postgres::describe::describe;
 use crate::ts_generator::generator::generate_ts_interface;
 use crate::ts_generator::types::ts_query::TsQuery;
 use color_eyre::eyre::Result;
+use regex::Regex;
 
 use swc_common::errors::Handler;
+use tokio_postgres::error::{ErrorPosition, SqlState};
+use tokio_postgres::Client;
+
+const PREPARE_PREFIX: &str = "PREPARE sqlx_stmt AS ";
+
+/// Names of the tables and columns within the search_path of the connection, to suggest in place of unknown ones
+async fn get_schema_names(client: &Client, query: &str) -> Vec<Vec<String>> {
+  client
+    .query(query, &[])
+    .await
+    .map(|rows| {
+      rows
+        .iter()
+        .map(|row| (0..row.len()).map(|idx| row.get::<_, String>(idx)).collect())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Turns the error of the PREPARE statement into a diagnostic, along with "did you mean" suggestions for an unknown table
+/// or column when PostgreSQL does not give a hint of its own
+async fn get_db_error(client: &Client, err: &tokio_postgres::Error, query: &str) -> DbError {
+  let Some(db_error) = err.as_db_error() else {
+    return DbError::connection_failure(err);
+  };
+
+  let mut error = DbError::new(Some(db_error.code().code().to_string()), db_error.message());
+  error.detail = db_error.detail().map(str::to_string);
+  error.hint = db_error.hint().map(str::to_string);
+
+  if error.hint.is_some() {
+    return error;
+  }
+
+  // e.g. `relation "itmes" does not exist`, `column "nme" does not exist` or `column i.nme does not exist`
+  let unknown_name = Regex::new(r#"^(?:relation|column) "?(?:[^"\s]+\.)?([^"\s.]+)"? does not exist"#).unwrap();
+  let Some(name) = unknown_name
+    .captures(db_error.message())
+    .map(|captures| captures[1].to_string())
+  else {
+    return error;
+  };
+
+  if *db_error.code() == SqlState::UNDEFINED_TABLE {
+    let tables = get_schema_names(
+      client,
+      "SELECT table_name::text FROM information_schema.tables WHERE table_schema = ANY(current_schemas(false))",
+    )
+    .await;
+    error.suggest_table(&name, &tables.into_iter().flatten().collect::<Vec<_>>());
+  } else if *db_error.code() == SqlState::UNDEFINED_COLUMN {
+    let columns = get_schema_names(
+      client,
+      "SELECT table_name::text, column_name::text FROM information_schema.columns WHERE table_schema = ANY(current_schemas(false))",
+    )
+    .await;
+    let columns = columns
+      .into_iter()
+      .map(|row| (row[0].to_owned(), row[1].to_owned()))
+      .collect::<Vec<_>>();
+    error.suggest_column(&name, &columns, query);
+  }
+
+  error
+}
 
 /// Runs the prepare statement on the input SQL. Validates the query is right by directly connecting to the configured database.
 /// It also processes ts interfaces if the configuration is set to `generate_types = true`
@@ -22,26 +91,54 @@ pub async fn prepare(
   };
 
   {
-    let span = sql.span.to_owned();
-
-    let prepare_query = format!("PREPARE sqlx_stmt AS {}", sql.query);
+    let prepare_query = format!("{PREPARE_PREFIX}{}", sql.query);
     let conn = conn.lock().await;
-    let conn = conn.get().await.unwrap();
+    let conn = match conn.get().await {
+      Ok(conn) => conn,
+      Err(err) => {
+        DbError::connection_failure(err).emit(handler, sql.span.to_owned());
+        return Ok((true, None));
+      }
+    };
     let result = conn.query(prepare_query.as_str(), &[]).await;
 
     if let Err(e) = result {
-      handler.span_bug_no_panic(span, e.as_db_error().unwrap().message());
+      // The position is counted from the start of the PREPARE statement, rather than the query
+      let span = match e.as_db_error().and_then(|db_error| db_error.position()) {
+        Some(ErrorPosition::Original(position)) => {
+          sql.span_at_position((*position as usize).saturating_sub(PREPARE_PREFIX.chars().count()))
+        }
+        _ => sql.span.to_owned(),
+      };
+      get_db_error(&conn, &e, &sql.query).await.emit(handler, span);
+
+      // Types cannot be generated without the database either
+      if e.as_db_error().is_none() {
+        return Ok((true, None));
+      }
       failed = true;
     } else {
       // We should only deallocate if the prepare statement was executed successfully
-      let _ = &conn.query("DEALLOCATE sqlx_stmt", &[]).await.unwrap();
+      let _ = &conn.query("DEALLOCATE sqlx_stmt", &[]).await;
     }
   }
 
   let mut ts_query = None;
 
   if should_generate_types == &true {
-    ts_query = Some(generate_ts_interface(sql, db_conn).await?);
+    let mut query = generate_ts_interface(sql, db_conn).await?;
+
+    if !failed && CONFIG.should_describe(sql) {
+      let conn = conn.lock().await;
+      let conn = conn.get().await;
+      if let Ok(conn) = &conn {
+        if let Some(described) = describe(conn, &sql.query).await {
+          query.apply_described_statement(described);
+        }
+      }
+    }
+
+    ts_query = Some(query);
   }
 
   Ok((failed, ts_query))
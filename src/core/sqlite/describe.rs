@@ -0,0 +1,25 @@
+use crate::ts_generator::types::ts_query::{DescribedStatement, TsFieldType};
+use rusqlite::Connection;
+
+/// Prepares the SQL and reads the declared types of the result columns
+/// SQLite params are untyped and expressions have no declared type, so both are left to the SQL walk
+pub fn describe(conn: &Connection, query: &str) -> Option<DescribedStatement> {
+  let statement = conn.prepare(query).ok()?;
+
+  let columns = statement
+    .columns()
+    .iter()
+    .map(|column| {
+      let ts_type = column
+        .decl_type()
+        .map(|decl_type| TsFieldType::get_ts_field_type_from_sqlite_field_type(decl_type.to_lowercase()))
+        .unwrap_or(TsFieldType::Any);
+      (column.name().to_string(), ts_type, None)
+    })
+    .collect();
+
+  Some(DescribedStatement {
+    params: vec![],
+    columns,
+  })
+}
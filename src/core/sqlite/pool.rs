@@ -0,0 +1,43 @@
+use rusqlite::{Connection, Error};
+
+#[derive(Clone, Debug)]
+pub struct SqliteConnectionManager {
+  // Path to the database file, or `:memory:` for an in-memory database that only lives as long as the connection
+  path: String,
+  // Statements that run on every new connection of the pool before it is handed out, e.g. `PRAGMA foreign_keys = ON`
+  init_sql: Vec<String>,
+}
+
+impl SqliteConnectionManager {
+  pub fn new(path: String, init_sql: Vec<String>) -> Self {
+    Self { path, init_sql }
+  }
+}
+
+impl bb8::ManageConnection for SqliteConnectionManager {
+  type Connection = Connection;
+  type Error = Error;
+
+  async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+    let path = self.path.clone();
+
+    let conn = Connection::open(&path)
+      .unwrap_or_else(|err| panic!("SQLite database connection error - path: {path:?}, error: {err}"));
+
+    for statement in &self.init_sql {
+      if let Err(err) = conn.execute_batch(statement) {
+        panic!("Failed to run the init statement {statement:?} on SQLite database {path:?} - error: {err}")
+      }
+    }
+
+    Ok(conn)
+  }
+
+  async fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+    conn.execute_batch("SELECT 1;")
+  }
+
+  fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+    false
+  }
+}
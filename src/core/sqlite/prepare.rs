@@ -0,0 +1,115 @@
+use crate::common::lazy::CONFIG;
+use crate::common::SQL;
+use crate::core::connection::DBConn;
+use crate::core::db_error::DbError;
+use crate::core::sqlite::describe::describe;
+use crate::ts_generator::generator::generate_ts_interface;
+use crate::ts_generator::types::ts_query::TsQuery;
+use color_eyre::eyre::Result;
+use regex::Regex;
+use rusqlite::Connection;
+
+use swc_common::errors::Handler;
+
+/// Turns the error of preparing the query into a diagnostic, along with "did you mean" suggestions for an unknown table
+/// or column
+fn get_db_error(conn: &Connection, err: &rusqlite::Error, query: &str) -> DbError {
+  let message = match err {
+    rusqlite::Error::SqlInputError { msg, .. } => msg.to_owned(),
+    rusqlite::Error::SqliteFailure(_, Some(msg)) => msg.to_owned(),
+    _ => err.to_string(),
+  };
+  let mut error = DbError::new(None, &message);
+
+  let tables = conn
+    .prepare("SELECT name FROM sqlite_master WHERE type IN ('table', 'view')")
+    .and_then(|mut stmt| {
+      stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()
+    })
+    .unwrap_or_default();
+
+  // e.g. `no such table: itmes` or `no such column: i.nme`
+  let unknown_table = Regex::new(r"^no such table: (?:\S+\.)?(\S+)$").unwrap();
+  let unknown_column = Regex::new(r"^no such column: (?:\S+\.)?(\S+)$").unwrap();
+
+  if let Some(captures) = unknown_table.captures(&message) {
+    error.suggest_table(&captures[1], &tables);
+  } else if let Some(captures) = unknown_column.captures(&message) {
+    let columns = tables
+      .iter()
+      .flat_map(|table| {
+        conn
+          .prepare("SELECT name FROM pragma_table_info(?1)")
+          .and_then(|mut stmt| {
+            stmt
+              .query_map([table], |row| row.get(0))?
+              .collect::<Result<Vec<String>, _>>()
+          })
+          .unwrap_or_default()
+          .into_iter()
+          .map(|column| (table.to_owned(), column))
+      })
+      .collect::<Vec<_>>();
+    error.suggest_column(&captures[1], &columns, query);
+  }
+
+  error
+}
+
+/// Runs the prepare statement on the input SQL.
+/// Validates the query is right by compiling it against the configured SQLite database, nothing is executed.
+/// It also processes ts interfaces if the configuration is set to generate_types = true
+pub async fn prepare(
+  db_conn: &DBConn,
+  sql: &SQL,
+  should_generate_types: &bool,
+  handler: &Handler,
+) -> Result<(bool, Option<TsQuery>)> {
+  let mut failed = false;
+
+  let conn = match &db_conn {
+    DBConn::SqliteConn(conn) => conn,
+    _ => panic!("Invalid connection type"),
+  };
+
+  {
+    let conn = conn.lock().await;
+    let conn = match conn.get().await {
+      Ok(conn) => conn,
+      Err(err) => {
+        DbError::connection_failure(err).emit(handler, sql.span.to_owned());
+        return Ok((true, None));
+      }
+    };
+    let result = conn.prepare(&sql.query).map(|_| ());
+
+    if let Err(err) = result {
+      let span = match &err {
+        rusqlite::Error::SqlInputError { offset, .. } => sql.span_at(*offset as usize),
+        _ => sql.span.to_owned(),
+      };
+      get_db_error(&conn, &err, &sql.query).emit(handler, span);
+      failed = true;
+    }
+  }
+
+  let mut ts_query = None;
+
+  if should_generate_types == &true {
+    let mut query = generate_ts_interface(sql, db_conn).await?;
+
+    if !failed && CONFIG.should_describe(sql) {
+      let conn = conn.lock().await;
+      let conn = conn.get().await?;
+      if let Some(described) = describe(&conn, &sql.query) {
+        query.apply_described_statement(described);
+      }
+    }
+
+    ts_query = Some(query);
+  }
+
+  Ok((failed, ts_query))
+}
@@ -0,0 +1,123 @@
+use serde_json::{json, Value};
+use std::ops::Range;
+use std::path::PathBuf;
+use swc_common::BytePos;
+
+/// A file opened in the editor, along with the types of its SQLs as of the last time it was checked
+#[derive(Debug, Default)]
+pub struct Document {
+  pub text: String,
+  /// Byte range of each SQL in the text, along with the Typescript types generated for it
+  pub query_types: Vec<(Range<usize>, String)>,
+}
+
+impl Document {
+  /// Types of the SQL at the byte offset of the text, if any
+  pub fn get_query_types_at(&self, offset: usize) -> Option<&(Range<usize>, String)> {
+    self.query_types.iter().find(|(range, _)| range.contains(&offset))
+  }
+}
+
+/// Path of a `file://` URI e.g. `file:///home/user/my%20app/index.ts` is `/home/user/my app/index.ts`
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+  let path = uri.strip_prefix("file://")?;
+  let mut bytes = vec![];
+  let mut chars = path.bytes();
+
+  while let Some(byte) = chars.next() {
+    if byte == b'%' {
+      let hex = [chars.next()?, chars.next()?];
+      bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+    } else {
+      bytes.push(byte);
+    }
+  }
+
+  let path = String::from_utf8(bytes).ok()?;
+  // `file:///C:/app/index.ts` is `C:/app/index.ts` on Windows
+  let path = match path.as_bytes() {
+    [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+    _ => path,
+  };
+
+  Some(PathBuf::from(path))
+}
+
+/// Byte offset of a position of the source file the SQLs were parsed from
+/// Every source file is parsed into a SourceMap of its own, where the file starts at BytePos(1)
+pub fn get_offset_of_byte_pos(pos: BytePos) -> usize {
+  (pos.0 as usize).saturating_sub(1)
+}
+
+/// LSP position, the 0-based line and the UTF-16 code unit within the line, of the byte offset of the text
+pub fn get_position(text: &str, offset: usize) -> Value {
+  let offset = offset.min(text.len());
+  let line_start = text[..offset].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+  let line = text[..line_start].matches('\n').count();
+  let character = text[line_start..offset].encode_utf16().count();
+
+  json!({ "line": line, "character": character })
+}
+
+/// LSP position of the 1-based line and column (counted in characters) of the text, as diagnostics report them
+pub fn get_position_at_line_column(text: &str, line: usize, column: usize) -> Value {
+  let line_text = text.split('\n').nth(line.saturating_sub(1)).unwrap_or("");
+  let character = line_text
+    .chars()
+    .take(column.saturating_sub(1))
+    .map(char::len_utf16)
+    .sum::<usize>();
+
+  json!({ "line": line.saturating_sub(1), "character": character })
+}
+
+/// Byte offset of the LSP position within the text
+pub fn get_offset(text: &str, line: usize, character: usize) -> Option<usize> {
+  let line_start = match line {
+    0 => 0,
+    _ => text.match_indices('\n').nth(line - 1)?.0 + 1,
+  };
+  let line_text = text[line_start..].split('\n').next()?;
+
+  let mut code_units = 0;
+  for (idx, char) in line_text.char_indices() {
+    if code_units >= character {
+      return Some(line_start + idx);
+    }
+    code_units += char.len_utf16();
+  }
+  Some(line_start + line_text.len())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_uri_to_path() {
+    assert_eq!(
+      uri_to_path("file:///home/user/my%20app/index.ts"),
+      Some(PathBuf::from("/home/user/my app/index.ts"))
+    );
+    assert_eq!(
+      uri_to_path("file:///c%3A/app/index.ts"),
+      Some(PathBuf::from("c:/app/index.ts"))
+    );
+    assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+  }
+
+  #[test]
+  fn test_positions_count_utf16_code_units() {
+    let text = "const a = '🦀';\nconst query = sql`SELECT 1`;";
+
+    assert_eq!(get_position(text, 19), json!({ "line": 1, "character": 1 }));
+    assert_eq!(get_position(text, 15), json!({ "line": 0, "character": 13 }));
+    assert_eq!(
+      get_position_at_line_column(text, 1, 13),
+      json!({ "line": 0, "character": 13 })
+    );
+    assert_eq!(get_offset(text, 0, 13), Some(15));
+    assert_eq!(get_offset(text, 1, 14), Some(32));
+    assert_eq!(get_offset(text, 2, 0), None);
+  }
+}
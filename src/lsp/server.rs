@@ -0,0 +1,199 @@
+use color_eyre::eyre::{eyre, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use tokio::io::{stdin, stdout, BufReader, Stdout};
+
+use crate::common::diagnostics::{get_num_diagnostics, set_query_name, Diagnostic};
+use crate::common::errors::DB_CONN_FROM_LOCAL_CACHE_ERROR;
+use crate::common::lazy::{DB_CONNECTIONS, DIAGNOSTICS};
+use crate::common::logger::*;
+use crate::lsp::document::{
+  get_offset, get_offset_of_byte_pos, get_position, get_position_at_line_column, uri_to_path, Document,
+};
+use crate::lsp::transport::{read_message, write_message};
+use crate::parser::parse_source;
+
+/// JSON-RPC error code of a request the server does not support
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Language server that checks the SQLs of a file as it is opened or saved in the editor
+/// Connection pools and the schema cache stay warm between checks, as the process lives as long as the editor session
+#[derive(Default)]
+struct Server {
+  documents: HashMap<String, Document>,
+  is_shutdown: bool,
+}
+
+impl Server {
+  /// Checks the SQLs of the saved file the same way as the CLI, and keeps the types generated for each SQL for hovers
+  async fn check_document(&mut self, uri: &str) -> Result<Vec<Diagnostic>> {
+    let path = uri_to_path(uri).ok_or_else(|| eyre!("Unsupported document URI - {uri}"))?;
+    let text = fs::read_to_string(&path)?;
+
+    DIAGNOSTICS.lock().unwrap().clear();
+    let (queries, handler) = parse_source(&path)?;
+
+    let mut query_types = vec![];
+    for sql in queries.values().flatten() {
+      let num_diagnostics = get_num_diagnostics();
+      let mut connections = DB_CONNECTIONS.lock().await;
+      let Some(connection) = connections.get_connection(sql) else {
+        let connection_name = connections.get_connection_name(sql);
+        let message = format!("{DB_CONN_FROM_LOCAL_CACHE_ERROR} - connection: {connection_name}");
+        handler.span_err(sql.span.to_owned(), message.as_str());
+        set_query_name(num_diagnostics, sql);
+        continue;
+      };
+      let connection = &connection.lock().await;
+
+      match connection.prepare(sql, &true, &handler).await {
+        Ok((_, Some(ts_query))) => {
+          if let Some(span) = sql.span.primary_span() {
+            let range = get_offset_of_byte_pos(span.lo)..get_offset_of_byte_pos(span.hi);
+            query_types.push((range, ts_query.to_string()));
+          }
+        }
+        Ok((_, None)) => {}
+        Err(err) => handler.span_err(sql.span.to_owned(), err.to_string().as_str()),
+      }
+      set_query_name(num_diagnostics, sql);
+    }
+
+    self.documents.insert(uri.to_string(), Document { text, query_types });
+    Ok(std::mem::take(&mut *DIAGNOSTICS.lock().unwrap()))
+  }
+
+  async fn publish_diagnostics(&mut self, uri: &str, writer: &mut Stdout) -> Result<()> {
+    let diagnostics = match self.check_document(uri).await {
+      Ok(diagnostics) => diagnostics,
+      Err(err) => {
+        error!("Failed to check {} - {}", uri, err);
+        vec![]
+      }
+    };
+
+    let text = self
+      .documents
+      .get(uri)
+      .map(|document| document.text.as_str())
+      .unwrap_or("");
+    let diagnostics = diagnostics
+      .iter()
+      .map(|diagnostic| {
+        json!({
+          "range": {
+            "start": get_position_at_line_column(text, diagnostic.line, diagnostic.column),
+            "end": get_position_at_line_column(text, diagnostic.end_line, diagnostic.end_column),
+          },
+          "severity": match diagnostic.severity.as_str() {
+            "warning" => 2,
+            "note" => 3,
+            _ => 1,
+          },
+          "code": diagnostic.code,
+          "source": "sqlx-ts",
+          "message": diagnostic.get_full_message(),
+        })
+      })
+      .collect::<Vec<_>>();
+
+    let notification = json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": { "uri": uri, "diagnostics": diagnostics },
+    });
+    write_message(writer, &notification).await
+  }
+
+  /// Generated types of the SQL under the cursor
+  fn hover(&self, params: &Value) -> Value {
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+    let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+    let character = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+
+    let document = match self.documents.get(uri) {
+      Some(document) => document,
+      None => return Value::Null,
+    };
+
+    let query_types =
+      get_offset(&document.text, line, character).and_then(|offset| document.get_query_types_at(offset));
+
+    match query_types {
+      Some((range, types)) => json!({
+        "contents": { "kind": "markdown", "value": format!("```typescript\n{types}```") },
+        "range": {
+          "start": get_position(&document.text, range.start),
+          "end": get_position(&document.text, range.end),
+        },
+      }),
+      None => Value::Null,
+    }
+  }
+}
+
+fn get_response(id: Value, result: Value) -> Value {
+  json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Serves the language server protocol over stdio until the client sends `exit`
+pub async fn run() -> Result<()> {
+  let mut reader = BufReader::new(stdin());
+  let mut writer = stdout();
+  let mut server = Server::default();
+
+  while let Some(message) = read_message(&mut reader).await? {
+    let method = message["method"].as_str().unwrap_or("");
+    let id = message.get("id").cloned();
+    let params = &message["params"];
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+
+    match (method, id) {
+      ("initialize", Some(id)) => {
+        let result = json!({
+          "capabilities": {
+            // SQLs are checked against the file on disk, which only changes as the document is saved
+            "textDocumentSync": { "openClose": true, "change": 0, "save": { "includeText": false } },
+            "hoverProvider": true,
+          },
+          "serverInfo": { "name": "sqlx-ts", "version": env!("CARGO_PKG_VERSION") },
+        });
+        write_message(&mut writer, &get_response(id, result)).await?;
+      }
+      ("textDocument/didOpen" | "textDocument/didSave", None) => {
+        server.publish_diagnostics(uri, &mut writer).await?;
+      }
+      ("textDocument/didClose", None) => {
+        server.documents.remove(uri);
+        let notification = json!({
+          "jsonrpc": "2.0",
+          "method": "textDocument/publishDiagnostics",
+          "params": { "uri": uri, "diagnostics": [] },
+        });
+        write_message(&mut writer, &notification).await?;
+      }
+      ("textDocument/hover", Some(id)) => {
+        write_message(&mut writer, &get_response(id, server.hover(params))).await?;
+      }
+      ("shutdown", Some(id)) => {
+        server.is_shutdown = true;
+        write_message(&mut writer, &get_response(id, Value::Null)).await?;
+      }
+      ("exit", None) => break,
+      // Requests the server does not support, notifications such as `initialized` or responses are ignored
+      (method, Some(id)) if !method.is_empty() => {
+        let response = json!({
+          "jsonrpc": "2.0",
+          "id": id,
+          "error": { "code": METHOD_NOT_FOUND, "message": format!("Unsupported method - {method}") },
+        });
+        write_message(&mut writer, &response).await?;
+      }
+      _ => {}
+    }
+  }
+
+  // The client is expected to shut the server down before it exits, anything else is treated as a crash
+  std::process::exit(if server.is_shutdown { 0 } else { 1 });
+}
@@ -0,0 +1,81 @@
+use color_eyre::eyre::{eyre, Result};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Reads the next JSON-RPC message, framed by a `Content-Length` header as the language server protocol specifies
+/// Returns None once the client closes the stream
+pub async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Value>> {
+  let mut content_length = None;
+
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header).await? == 0 {
+      return Ok(None);
+    }
+
+    // Headers end with an empty line
+    let header = header.trim_end();
+    if header.is_empty() {
+      break;
+    }
+
+    if let Some((name, value)) = header.split_once(':') {
+      if name.eq_ignore_ascii_case("Content-Length") {
+        content_length = Some(value.trim().parse::<usize>()?);
+      }
+    }
+  }
+
+  let content_length = content_length.ok_or_else(|| eyre!("Received a message without a Content-Length header"))?;
+  let mut content = vec![0; content_length];
+  reader.read_exact(&mut content).await?;
+
+  Ok(Some(serde_json::from_slice(&content)?))
+}
+
+pub async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &Value) -> Result<()> {
+  let content = serde_json::to_string(message)?;
+
+  writer
+    .write_all(format!("Content-Length: {}\r\n\r\n{content}", content.len()).as_bytes())
+    .await?;
+  writer.flush().await?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[tokio::test]
+  async fn test_write_and_read_messages() {
+    let mut stream = vec![];
+    write_message(&mut stream, &json!({ "jsonrpc": "2.0", "method": "initialized" }))
+      .await
+      .unwrap();
+    write_message(&mut stream, &json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" }))
+      .await
+      .unwrap();
+
+    let mut reader = stream.as_slice();
+    assert_eq!(
+      read_message(&mut reader).await.unwrap(),
+      Some(json!({ "jsonrpc": "2.0", "method": "initialized" }))
+    );
+    assert_eq!(read_message(&mut reader).await.unwrap().unwrap()["method"], "shutdown");
+    assert_eq!(read_message(&mut reader).await.unwrap(), None);
+  }
+
+  #[tokio::test]
+  async fn test_read_message_with_a_content_type_header() {
+    let content = r#"{"jsonrpc":"2.0","method":"exit"}"#;
+    let stream = format!(
+      "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\nContent-Length: {}\r\n\r\n{content}",
+      content.len()
+    );
+
+    let mut reader = stream.as_bytes();
+    assert_eq!(read_message(&mut reader).await.unwrap().unwrap()["method"], "exit");
+  }
+}
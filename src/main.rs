@@ -16,19 +16,26 @@
 #![allow(clippy::ptr_arg)]
 mod common;
 mod core;
+mod lsp;
 mod parser;
 mod scan_folder;
 mod ts_generator;
+mod watch;
 
 extern crate clap;
 extern crate dotenv;
 
-use crate::core::execute::execute;
+use crate::core::execute::{execute, Summary};
 
+use crate::common::diagnostics::print_diagnostics;
 use crate::common::lazy::*;
 use crate::common::logger::*;
 use crate::common::types::FileExtension;
-use crate::ts_generator::generator::clear_single_ts_file_if_exists;
+use crate::ts_generator::generator::write_generated_files;
+use crate::ts_generator::stale::{
+  get_orphan_candidates, get_orphaned_files, get_stale_files, print_stale_files, remove_orphaned_files,
+};
+use crate::watch::watch;
 use crate::{parser::parse_source, scan_folder::scan_folder};
 use color_eyre::eyre::Result;
 use std::env;
@@ -65,7 +72,9 @@ async fn main() -> Result<()> {
     set_default_env_var();
   }
 
-  let source_folder = &CLI_ARGS.path;
+  let Some(source_folder) = CLI_ARGS.path() else {
+    return lsp::server::run().await;
+  };
   // If no file extensions were provided
   let exts = if CLI_ARGS.ext.is_empty() {
     vec![FileExtension::Ts, FileExtension::Sql]
@@ -81,8 +90,9 @@ async fn main() -> Result<()> {
 
   info!("Scanning {:?} for SQLs with extensions [{}]", source_folder, exts_str);
 
-  // If CLI_ARGS.generate_types is true, it will clear the single TS file so `execute` will generate a new one from scratch
-  clear_single_ts_file_if_exists()?;
+  if CLI_ARGS.watch {
+    return watch(source_folder, &exts).await;
+  }
 
   let files: Vec<PathBuf> = exts.iter().flat_map(|ext| scan_folder(source_folder, ext)).collect();
 
@@ -91,30 +101,71 @@ async fn main() -> Result<()> {
       "No targets detected, is it an empty folder? - source_folder: {:?}, file extensions: [{}]",
       source_folder, exts_str,
     );
+    print_diagnostics();
     std::process::exit(0);
   }
 
-  let mut num_sqls = 0;
+  let mut summary = Summary::default();
   for file_path in files.iter() {
     let (sqls, handler) = parse_source(file_path)?;
-    let failed = execute(&sqls, &handler).await?;
+    let failed = execute(file_path, &sqls, &handler, &mut summary).await?;
+    summary.files_scanned += 1;
 
-    for sql in sqls {
-      num_sqls += sql.1.iter().len();
+    if failed && CLI_ARGS.fail_fast {
+      break;
     }
+  }
 
-    #[allow(clippy::print_stderr)]
-    if failed {
-      error!("SQLs failed to compile!\n");
+  summary.print();
+  print_diagnostics();
+
+  let orphaned_files = if CLI_ARGS.is_snapshot() {
+    vec![]
+  } else {
+    let orphan_candidates = get_orphan_candidates(source_folder, &files, &summary.files_without_sqls);
+    get_orphaned_files(&orphan_candidates, &summary.files_without_sqls)
+  };
+
+  // Types of the SQLs that compiled are written even if others failed, --check compares them with the files on disk instead
+  if CLI_ARGS.dry_run {
+    for (file_path, _) in get_stale_files(&summary.generated_types, &[])? {
+      info!("{:?} would be written", file_path);
+    }
+    for file_path in &orphaned_files {
+      info!("{:?} would be removed as its source file has no SQLs left", file_path);
+    }
+  } else if !CLI_ARGS.check && !CLI_ARGS.is_snapshot() {
+    write_generated_files(&summary.generated_types)?;
+    remove_orphaned_files(&orphaned_files)?;
+  }
+
+  if summary.has_failures() {
+    error!("SQLs failed to compile!\n");
+    std::process::exit(1)
+  }
+
+  if CLI_ARGS.check {
+    for file_path in &orphaned_files {
+      info!("{:?} would be removed as its source file has no SQLs left", file_path);
+    }
+
+    let stale_files = get_stale_files(&summary.generated_types, &orphaned_files)?;
+    print_stale_files(&stale_files);
+
+    if !stale_files.is_empty() {
+      error!(
+        "{} generated files are out of date, run sqlx-ts with -g to regenerate them\n",
+        stale_files.len()
+      );
       std::process::exit(1)
     }
+    info!("Generated files are up to date");
   }
 
-  if num_sqls == 0 {
-    info!("No SQL queries found");
-  } else {
-    let num_sqls_msg = format!("Found {num_sqls} SQL queries");
-    info!(num_sqls_msg);
+  if CLI_ARGS.is_snapshot() {
+    let snapshot_path = CLI_ARGS.snapshot_path();
+    DB_SCHEMA.lock().await.snapshot().write(&snapshot_path)?;
+    info!("Schema snapshot written to {:?}", snapshot_path);
   }
 
   info!("No SQL errors detected!\n");
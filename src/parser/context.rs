@@ -0,0 +1,305 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use swc_common::MultiSpan;
+use swc_ecma_ast::{
+  Constructor, Decl, Expr, Function, MemberProp, Module, ModuleDecl, ModuleItem, ObjectPatProp, ParamOrTsParamProp,
+  Pat, Stmt, Tpl, TsParamPropParam, VarDeclKind,
+};
+
+use crate::common::config::TagConfig;
+use crate::common::lazy::CONFIG;
+use crate::common::SQL;
+use crate::parser::import::{find_tag_bindings, sqlx_tag, TagBindings};
+
+/// A module level `const` whose value is a sql tagged template that can be inlined into other queries
+/// e.g. `const columns = sql`id, name`` used as sql`SELECT ${columns} FROM users`
+#[derive(Clone, Debug)]
+pub struct Fragment {
+  /// The fragment's SQL with any fragments it's composed of already inlined
+  pub query: String,
+  /// Span of the declaration, the same span the fragment would be reported with as a standalone query
+  pub span: MultiSpan,
+}
+
+/// State shared while finding the SQLs of a single JS/TS module
+///
+/// A nested scope that declares a variable with the same name as one of the module level bindings gets its own
+/// context through `enter_scope`, so a shadowed `sql` is not mistaken for the sqlx tag
+#[derive(Clone)]
+pub struct ParseContext {
+  pub tags: TagBindings,
+  pub fragments: Rc<HashMap<String, Fragment>>,
+  /// Module level bindings that are shadowed by a declaration in the current scope
+  shadowed: HashSet<String>,
+  /// Fragments that were inlined into another query, their declarations are not validated on their own
+  inlined_fragments: Rc<RefCell<HashSet<String>>>,
+}
+
+impl ParseContext {
+  pub fn new(module: &Module) -> Self {
+    let mut configured_tags = vec![sqlx_tag()];
+    configured_tags.extend(CONFIG.tags.to_owned());
+    let mut tags = find_tag_bindings(&module.body, &configured_tags);
+
+    // Without any import of a recognised tag, a global `sql` that the module does not declare itself is treated as
+    // the tag of sqlx-ts
+    if tags.is_empty() && !get_module_declared_names(module).contains("sql") {
+      tags.idents.insert("sql".to_string(), sqlx_tag());
+    }
+
+    let mut ctx = Self {
+      tags,
+      fragments: Rc::new(HashMap::new()),
+      shadowed: HashSet::new(),
+      inlined_fragments: Rc::new(RefCell::new(HashSet::new())),
+    };
+    ctx.fragments = Rc::new(ctx.collect_fragments(module));
+    ctx
+  }
+
+  /// Returns the context of a nested scope that declares the given names
+  pub fn enter_scope(&self, names: Vec<String>) -> Cow<'_, Self> {
+    let shadowing = names
+      .into_iter()
+      .filter(|name| !self.shadowed.contains(name))
+      .filter(|name| self.tags.local_names().any(|local| local == name) || self.fragments.contains_key(name))
+      .collect::<Vec<_>>();
+
+    if shadowing.is_empty() {
+      return Cow::Borrowed(self);
+    }
+
+    let mut ctx = self.clone();
+    ctx.shadowed.extend(shadowing);
+    Cow::Owned(ctx)
+  }
+
+  fn is_bound(&self, name: &str) -> bool {
+    !self.shadowed.contains(name)
+  }
+
+  /// Returns the recognised tag the tag of a tagged template refers to, e.g. `sql`, `sqlx.sql` or `Prisma.sql`
+  pub fn resolve_tag(&self, tag: &Expr) -> Option<&TagConfig> {
+    match tag {
+      Expr::Ident(ident) if self.is_bound(&ident.sym) => self.tags.idents.get(&*ident.sym),
+      Expr::Member(member) => match (&*member.obj, &member.prop) {
+        (Expr::Ident(obj), MemberProp::Ident(prop)) if self.is_bound(&obj.sym) => {
+          self.tags.members.get(&(obj.sym.to_string(), prop.sym.to_string()))
+        }
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /// Returns the SQL of the fragment if the interpolated expression refers to one
+  pub fn inline_fragment(&self, expr: &Expr) -> Option<String> {
+    let name = expr.as_ident()?.sym.to_string();
+    if !self.is_bound(&name) {
+      return None;
+    }
+    let fragment = self.fragments.get(&name)?;
+    self.inlined_fragments.borrow_mut().insert(name);
+    Some(fragment.query.to_owned())
+  }
+
+  /// Whether the SQL is the declaration of a fragment that has been inlined into another query
+  pub fn is_inlined_fragment(&self, sql: &SQL) -> bool {
+    let inlined_fragments = self.inlined_fragments.borrow();
+    sql.var_decl_name.as_ref().is_some_and(|name| {
+      inlined_fragments.contains(name)
+        && self
+          .fragments
+          .get(name)
+          .is_some_and(|fragment| fragment.span == sql.span)
+    })
+  }
+
+  /// Only `const` bindings at the top of the module are considered, and only if their value is statically known,
+  /// which means every interpolation within the template is another fragment
+  fn collect_fragments(&self, module: &Module) -> HashMap<String, Fragment> {
+    let mut candidates: HashMap<String, (&Tpl, MultiSpan)> = HashMap::new();
+
+    for item in &module.body {
+      let var = match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => match &export_decl.decl {
+          Decl::Var(var) => var,
+          _ => continue,
+        },
+        _ => continue,
+      };
+
+      if var.kind != VarDeclKind::Const {
+        continue;
+      }
+
+      for var_decl in &var.decls {
+        if let (Pat::Ident(ident), Some(Expr::TaggedTpl(tagged_tpl))) = (&var_decl.name, var_decl.init.as_deref()) {
+          if self.resolve_tag(&tagged_tpl.tag).is_some() {
+            candidates.insert(ident.id.sym.to_string(), (&tagged_tpl.tpl, var.span.into()));
+          }
+        }
+      }
+    }
+
+    let mut resolved: HashMap<String, Option<String>> = HashMap::new();
+    for name in candidates.keys() {
+      resolve_fragment(name, &candidates, &mut resolved, &mut HashSet::new());
+    }
+
+    resolved
+      .into_iter()
+      .filter_map(|(name, query)| {
+        let span = candidates.get(&name)?.1.clone();
+        query.map(|query| (name, Fragment { query, span }))
+      })
+      .collect()
+  }
+}
+
+fn resolve_fragment(
+  name: &str,
+  candidates: &HashMap<String, (&Tpl, MultiSpan)>,
+  resolved: &mut HashMap<String, Option<String>>,
+  visiting: &mut HashSet<String>,
+) -> Option<String> {
+  if let Some(query) = resolved.get(name) {
+    return query.to_owned();
+  }
+  let (tpl, _) = candidates.get(name)?;
+  // A fragment that ends up referring to itself can never be inlined
+  if !visiting.insert(name.to_string()) {
+    return None;
+  }
+
+  let mut query = String::new();
+  let mut is_static = true;
+  for (idx, quasi) in tpl.quasis.iter().enumerate() {
+    if idx > 0 {
+      let inner = tpl.exprs[idx - 1]
+        .as_ident()
+        .and_then(|ident| resolve_fragment(&ident.sym, candidates, resolved, visiting));
+
+      match inner {
+        Some(inner) => query.push_str(&inner),
+        None => {
+          is_static = false;
+          break;
+        }
+      }
+    }
+    query.push_str(&quasi.raw);
+  }
+
+  visiting.remove(name);
+  let query = is_static.then_some(query);
+  resolved.insert(name.to_string(), query.to_owned());
+  query
+}
+
+/// Collects the names a pattern binds e.g. `a`, `b` and `c` for `const [a, { b, c: [c] }] = ...`
+pub fn get_pat_names(pat: &Pat, names: &mut Vec<String>) {
+  match pat {
+    Pat::Ident(ident) => names.push(ident.id.sym.to_string()),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        get_pat_names(elem, names);
+      }
+    }
+    Pat::Rest(rest) => get_pat_names(&rest.arg, names),
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => get_pat_names(&key_value.value, names),
+          ObjectPatProp::Assign(assign) => names.push(assign.key.sym.to_string()),
+          ObjectPatProp::Rest(rest) => get_pat_names(&rest.arg, names),
+        }
+      }
+    }
+    Pat::Assign(assign) => get_pat_names(&assign.left, names),
+    Pat::Invalid(_) => {}
+    Pat::Expr(_) => {}
+  }
+}
+
+fn get_decl_names(decl: &Decl, names: &mut Vec<String>) {
+  match decl {
+    Decl::Var(var) => {
+      for var_decl in &var.decls {
+        get_pat_names(&var_decl.name, names);
+      }
+    }
+    Decl::Using(using) => {
+      for var_decl in &using.decls {
+        get_pat_names(&var_decl.name, names);
+      }
+    }
+    Decl::Fn(fun) => names.push(fun.ident.sym.to_string()),
+    Decl::Class(class) => names.push(class.ident.sym.to_string()),
+    Decl::TsEnum(ts_enum) => names.push(ts_enum.id.sym.to_string()),
+    Decl::TsInterface(_) | Decl::TsTypeAlias(_) | Decl::TsModule(_) => {}
+  }
+}
+
+/// Names declared directly within a block, which are visible anywhere in the block
+pub fn get_declared_names(stmts: &[Stmt]) -> Vec<String> {
+  let mut names = vec![];
+  for stmt in stmts {
+    if let Stmt::Decl(decl) = stmt {
+      get_decl_names(decl, &mut names);
+    }
+  }
+  names
+}
+
+/// Names declared by a function's params and its body
+pub fn get_function_names(function: &Function) -> Vec<String> {
+  let mut names = vec![];
+  for param in &function.params {
+    get_pat_names(&param.pat, &mut names);
+  }
+  if let Some(body) = &function.body {
+    names.extend(get_declared_names(&body.stmts));
+  }
+  names
+}
+
+/// Names declared by a constructor's params, including TypeScript parameter properties, and its body
+pub fn get_constructor_names(constructor: &Constructor) -> Vec<String> {
+  let mut names = vec![];
+  for param in &constructor.params {
+    match param {
+      ParamOrTsParamProp::Param(param) => get_pat_names(&param.pat, &mut names),
+      ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+        TsParamPropParam::Ident(ident) => names.push(ident.id.sym.to_string()),
+        TsParamPropParam::Assign(assign) => get_pat_names(&assign.left, &mut names),
+      },
+    }
+  }
+  if let Some(body) = &constructor.body {
+    names.extend(get_declared_names(&body.stmts));
+  }
+  names
+}
+
+fn get_module_declared_names(module: &Module) -> HashSet<String> {
+  let mut names = vec![];
+  for item in &module.body {
+    match item {
+      ModuleItem::Stmt(Stmt::Decl(decl)) => get_decl_names(decl, &mut names),
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => get_decl_names(&export_decl.decl, &mut names),
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => names.extend(
+        import_decl
+          .specifiers
+          .iter()
+          .map(|specifier| specifier.local().sym.to_string()),
+      ),
+      _ => {}
+    }
+  }
+  names.into_iter().collect()
+}
@@ -4,34 +4,39 @@ use swc_ecma_ast::{ClassDecl, ClassMember, Decl, DefaultDecl};
 
 use super::tag::{get_sql_from_expr, get_sql_from_var_decl};
 use crate::common::SQL;
+use crate::parser::context::{get_constructor_names, get_declared_names, get_function_names, ParseContext};
 use crate::parser::js_parser::{get_var_decl_name_from_key, recurse_and_find_sql};
 
-fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, import_alias: &String) -> Result<()> {
+fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, ctx: &ParseContext) -> Result<()> {
   match body_stmt {
     ClassMember::Constructor(constructor) => {
+      let ctx = &ctx.enter_scope(get_constructor_names(constructor));
       if let Some(body) = &constructor.body {
         for stmt in &body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     ClassMember::Method(class_method) => {
+      let ctx = &ctx.enter_scope(get_function_names(&class_method.function));
       if let Some(body) = &class_method.function.body {
         for stmt in &body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     ClassMember::PrivateMethod(private_method) => {
+      let ctx = &ctx.enter_scope(get_function_names(&private_method.function));
       if let Some(body) = &private_method.function.body {
         for stmt in &body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     ClassMember::StaticBlock(static_block) => {
+      let ctx = &ctx.enter_scope(get_declared_names(&static_block.body.stmts));
       for stmt in &static_block.body.stmts {
-        recurse_and_find_sql(sqls, stmt, import_alias)?;
+        recurse_and_find_sql(sqls, stmt, ctx)?;
       }
     }
     ClassMember::PrivateProp(private_prop) => {
@@ -39,7 +44,7 @@ fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, import_ali
       let name = name.clone().name.to_string();
       if let Some(expr) = &private_prop.value {
         let span: MultiSpan = private_prop.span.into();
-        get_sql_from_expr(sqls, &Some(name), &expr.clone(), &span, import_alias);
+        get_sql_from_expr(sqls, &Some(name), &expr.clone(), &span, ctx);
       }
     }
     ClassMember::ClassProp(class_prop) => {
@@ -47,7 +52,7 @@ fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, import_ali
       let name = name.clone().ident().map(|x| x.to_string());
       if let Some(expr) = &class_prop.value {
         let span: MultiSpan = class_prop.span.into();
-        get_sql_from_expr(sqls, &name, &expr.clone(), &span, import_alias);
+        get_sql_from_expr(sqls, &name, &expr.clone(), &span, ctx);
       }
     }
     ClassMember::AutoAccessor(auto_accessor) => {
@@ -57,7 +62,7 @@ fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, import_ali
       if let Some(expr) = &value {
         let span: MultiSpan = auto_accessor.span.into();
         let var_decl_name = get_var_decl_name_from_key(key);
-        get_sql_from_expr(sqls, &var_decl_name, expr, &span, import_alias);
+        get_sql_from_expr(sqls, &var_decl_name, expr, &span, ctx);
       }
     }
     ClassMember::TsIndexSignature(_) => {}
@@ -66,20 +71,21 @@ fn process_class_member(sqls: &mut Vec<SQL>, body_stmt: &ClassMember, import_ali
   Ok(())
 }
 
-pub fn process_default_decl(sqls: &mut Vec<SQL>, default_decl: &DefaultDecl, import_alias: &String) -> Result<()> {
+pub fn process_default_decl(sqls: &mut Vec<SQL>, default_decl: &DefaultDecl, ctx: &ParseContext) -> Result<()> {
   match default_decl {
     DefaultDecl::Class(class) => {
       let class_body = &class.class.body;
       for body_stmt in class_body {
-        process_class_member(sqls, body_stmt, import_alias)?;
+        process_class_member(sqls, body_stmt, ctx)?;
       }
     }
     DefaultDecl::Fn(func) => {
+      let ctx = &ctx.enter_scope(get_function_names(&func.function));
       let body = &func.function.body;
 
       if let Some(body) = body {
         for stmt in &body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
@@ -88,38 +94,39 @@ pub fn process_default_decl(sqls: &mut Vec<SQL>, default_decl: &DefaultDecl, imp
   Ok(())
 }
 
-pub fn process_class_decl(sqls: &mut Vec<SQL>, class: &ClassDecl, import_alias: &String) -> Result<()> {
+pub fn process_class_decl(sqls: &mut Vec<SQL>, class: &ClassDecl, ctx: &ParseContext) -> Result<()> {
   let class_body = &class.class.body;
   let class_decorators = &class.class.decorators;
 
   for decorator in class_decorators {
     let expr = &decorator.expr;
     let span: MultiSpan = decorator.span.into();
-    get_sql_from_expr(sqls, &None, expr, &span, import_alias);
+    get_sql_from_expr(sqls, &None, expr, &span, ctx);
   }
 
   for body_stmt in class_body {
-    process_class_member(sqls, body_stmt, import_alias)?;
+    process_class_member(sqls, body_stmt, ctx)?;
   }
   Ok(())
 }
 
-pub fn process_decl(sqls: &mut Vec<SQL>, decl: &Decl, import_alias: &String) -> Result<()> {
+pub fn process_decl(sqls: &mut Vec<SQL>, decl: &Decl, ctx: &ParseContext) -> Result<()> {
   match decl {
     Decl::Class(class) => {
-      process_class_decl(sqls, class, import_alias)?;
+      process_class_decl(sqls, class, ctx)?;
     }
     Decl::Fn(fun) => {
+      let ctx = &ctx.enter_scope(get_function_names(&fun.function));
       if let Some(body) = &fun.function.body {
         for stmt in &body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     Decl::Var(var) => {
       for var_decl in &var.decls {
         let span: MultiSpan = var.span.into();
-        let new_sqls = get_sql_from_var_decl(var_decl, &span, import_alias);
+        let new_sqls = get_sql_from_var_decl(var_decl, &span, ctx);
         let num_new_sqls = new_sqls.len();
 
         sqls.extend(new_sqls);
@@ -134,7 +141,7 @@ pub fn process_decl(sqls: &mut Vec<SQL>, decl: &Decl, import_alias: &String) ->
         // const [rows, i] = await connection.execute....
         if let Some(init) = &var_decl.init {
           let expr = *init.clone();
-          get_sql_from_expr(sqls, &name, &expr, &span, import_alias);
+          get_sql_from_expr(sqls, &name, &expr, &span, ctx);
         }
       }
     }
@@ -147,7 +154,7 @@ pub fn process_decl(sqls: &mut Vec<SQL>, decl: &Decl, import_alias: &String) ->
           for body in &block.body {
             let stmt = &body.clone().stmt();
             if let Some(stmt) = stmt {
-              recurse_and_find_sql(sqls, stmt, import_alias)?;
+              recurse_and_find_sql(sqls, stmt, ctx)?;
             }
           }
         }
@@ -158,7 +165,7 @@ pub fn process_decl(sqls: &mut Vec<SQL>, decl: &Decl, import_alias: &String) ->
         let init = &decl.init;
         if let Some(expr) = init {
           let span: &MultiSpan = &using.span.into();
-          get_sql_from_expr(sqls, &None, expr, span, import_alias);
+          get_sql_from_expr(sqls, &None, expr, span, ctx);
         }
       }
     }
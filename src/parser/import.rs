@@ -1,31 +1,190 @@
-use swc_ecma_ast::{ImportDecl, ImportSpecifier, ModuleExportName};
-
-pub fn find_sqlx_import_alias(import_decl: &ImportDecl) -> Option<String> {
-  let mut name: Option<String> = None;
-  let src = import_decl.src.value.to_string_lossy().to_string();
-
-  if src == "sqlx-ts" {
-    for specifier in &import_decl.specifiers {
-      if let ImportSpecifier::Named(import_named_specifier) = specifier {
-        // for exampleL
-        // import { sql: aliased } from 'sqlx-ts' <<< should satisfy following
-        if let Some(imported) = &import_named_specifier.imported {
-          match imported {
-            ModuleExportName::Ident(ident) => {
-              if ident.sym == "sql" {
-                name = Some(import_named_specifier.local.sym.to_string())
-              }
+use std::collections::HashMap;
+
+use swc_ecma_ast::{
+  Callee, Expr, ImportDecl, ImportSpecifier, Lit, ModuleDecl, ModuleExportName, ModuleItem, ObjectPatProp, Pat,
+  PropName, Stmt, VarDeclarator,
+};
+
+use crate::common::config::TagConfig;
+
+const SQLX_MODULE: &str = "sqlx-ts";
+const SQLX_TAG: &str = "sql";
+
+/// The `sql` tag of sqlx-ts itself, which is always recognised along with the tags configured in `.sqlxrc.json`
+pub fn sqlx_tag() -> TagConfig {
+  TagConfig {
+    module: SQLX_MODULE.to_string(),
+    name: SQLX_TAG.to_string(),
+    connection: None,
+  }
+}
+
+/// Local bindings that refer to one of the recognised sql tags within a module
+#[derive(Clone, Debug, Default)]
+pub struct TagBindings {
+  /// Names the tag is called by directly e.g. `sql` or `q` for `import { sql as q } from 'sqlx-ts'`
+  pub idents: HashMap<String, TagConfig>,
+  /// Object and property names of member tags e.g. `("sqlx", "sql")` for `import * as sqlx from 'sqlx-ts'`
+  pub members: HashMap<(String, String), TagConfig>,
+}
+
+impl TagBindings {
+  /// Names of the local variables the bindings depend on, a nested scope declaring any of them shadows the tag
+  pub fn local_names(&self) -> impl Iterator<Item = &String> {
+    self
+      .idents
+      .keys()
+      .chain(self.members.keys().map(|(object, _)| object))
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.idents.is_empty() && self.members.is_empty()
+  }
+
+  /// Binds a local name to the export it was imported as, for example `sql` or `Prisma` of the tag `Prisma.sql`
+  fn bind_export(&mut self, tag: &TagConfig, local: &str, export: &str) {
+    match tag.name.split_once('.') {
+      None if tag.name == export => {
+        self.idents.insert(local.to_string(), tag.clone());
+      }
+      Some((object, property)) if object == export => {
+        self
+          .members
+          .insert((local.to_string(), property.to_string()), tag.clone());
+      }
+      _ => {}
+    }
+  }
+
+  /// Binds a local name to the whole module, in which case the tag is accessed as its member e.g. `sqlx.sql`
+  fn bind_namespace(&mut self, tag: &TagConfig, local: &str) {
+    if !tag.name.contains('.') {
+      self
+        .members
+        .insert((local.to_string(), tag.name.to_owned()), tag.clone());
+    }
+  }
+}
+
+fn get_module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string_lossy().to_string(),
+  }
+}
+
+/// for example:
+/// import { sql } from 'sqlx-ts'
+/// import { sql as aliased } from 'sqlx-ts'
+/// import * as sqlx from 'sqlx-ts'    <<< used as sqlx.sql``
+/// import sqlx from 'sqlx-ts'         <<< used as sqlx.sql``, unless the tag is the default export itself
+fn find_import_bindings(import_decl: &ImportDecl, tag: &TagConfig, bindings: &mut TagBindings) {
+  if import_decl.src.value.to_string_lossy() != tag.module || import_decl.type_only {
+    return;
+  }
+
+  for specifier in &import_decl.specifiers {
+    match specifier {
+      ImportSpecifier::Named(named) => {
+        let local = named.local.sym.to_string();
+        let imported = named
+          .imported
+          .as_ref()
+          .map(get_module_export_name)
+          .unwrap_or_else(|| local.to_owned());
+
+        if !named.is_type_only {
+          bindings.bind_export(tag, &local, &imported);
+        }
+      }
+      ImportSpecifier::Namespace(namespace) => {
+        bindings.bind_namespace(tag, &namespace.local.sym);
+      }
+      ImportSpecifier::Default(default) => {
+        let is_default_export = tag.name == "default" || tag.name.starts_with("default.");
+        if is_default_export {
+          bindings.bind_export(tag, &default.local.sym, "default");
+        } else {
+          bindings.bind_namespace(tag, &default.local.sym);
+        }
+      }
+    }
+  }
+}
+
+fn is_require_of(expr: &Expr, module: &str) -> bool {
+  let Expr::Call(call) = expr else { return false };
+  let Callee::Expr(callee) = &call.callee else {
+    return false;
+  };
+  let is_require = callee.as_ident().is_some_and(|ident| ident.sym == "require");
+  let is_module = match call.args.first().map(|arg| &*arg.expr) {
+    Some(Expr::Lit(Lit::Str(str))) => str.value.to_string_lossy() == module,
+    _ => false,
+  };
+  is_require && is_module && call.args.len() == 1
+}
+
+/// for example:
+/// const { sql } = require('sqlx-ts')
+/// const { sql: aliased } = require('sqlx-ts')
+/// const sqlx = require('sqlx-ts')    <<< used as sqlx.sql``
+fn find_require_bindings(var_declarator: &VarDeclarator, tag: &TagConfig, bindings: &mut TagBindings) {
+  if !var_declarator
+    .init
+    .as_deref()
+    .is_some_and(|init| is_require_of(init, &tag.module))
+  {
+    return;
+  }
+
+  match &var_declarator.name {
+    Pat::Ident(ident) => bindings.bind_namespace(tag, &ident.id.sym),
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::Assign(assign) => {
+            bindings.bind_export(tag, &assign.key.sym, &assign.key.sym);
+          }
+          ObjectPatProp::KeyValue(key_value) => {
+            let export = match &key_value.key {
+              PropName::Ident(ident) => Some(ident.sym.to_string()),
+              PropName::Str(str) => Some(str.value.to_string_lossy().to_string()),
+              _ => None,
+            };
+            if let (Some(export), Pat::Ident(local)) = (export, &*key_value.value) {
+              bindings.bind_export(tag, &local.id.sym, &export);
+            }
+          }
+          ObjectPatProp::Rest(_) => {}
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Finds every binding of the given tags declared at the top of the module through ES imports or CommonJS require
+pub fn find_tag_bindings(module_items: &[ModuleItem], tags: &[TagConfig]) -> TagBindings {
+  let mut bindings = TagBindings::default();
+
+  for item in module_items {
+    for tag in tags {
+      match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+          find_import_bindings(import_decl, tag, &mut bindings)
+        }
+        ModuleItem::Stmt(Stmt::Decl(decl)) => {
+          if let Some(var) = decl.as_var() {
+            for var_declarator in &var.decls {
+              find_require_bindings(var_declarator, tag, &mut bindings);
             }
-            _ => continue,
           }
-          // for example:
-          // import { sql } from 'sqlx-ts' <<< should satisfy following
-        } else if import_named_specifier.local.sym == "sql" {
-          name = Some("sql".to_string())
         }
+        _ => {}
       }
     }
   }
 
-  name
+  bindings
 }
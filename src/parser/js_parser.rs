@@ -3,18 +3,14 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::rc::Rc;
-use swc_common::{
-  errors::{ColorConfig, Handler},
-  input::StringInput,
-  sync::Lrc,
-  FileName, MultiSpan, SourceMap,
-};
+use swc_common::{errors::Handler, input::StringInput, sync::Lrc, FileName, MultiSpan, SourceMap};
 
+use crate::common::diagnostics::create_handler;
 use crate::common::SQL;
+use crate::parser::context::{get_declared_names, get_pat_names, ParseContext};
 use crate::parser::decl::{process_decl, process_default_decl};
-use crate::parser::import::find_sqlx_import_alias;
 use crate::parser::tag::get_sql_from_expr;
-use swc_ecma_ast::{Key, ModuleDecl, ModuleItem, Stmt};
+use swc_ecma_ast::{ForHead, Key, ModuleDecl, ModuleItem, Stmt, VarDeclOrExpr};
 use swc_ecma_parser::TsSyntax;
 use swc_ecma_parser::{lexer::Lexer, Parser, Syntax};
 
@@ -41,85 +37,127 @@ pub fn get_var_decl_name_from_key(key: &Key) -> Option<String> {
   }
 }
 
-pub fn recurse_and_find_sql(sqls: &mut Vec<SQL>, stmt: &Stmt, import_alias: &String) -> color_eyre::Result<()> {
+/// Names declared by the head of `for (const x in ...)` or `for (const x of ...)`
+fn get_for_head_names(head: &ForHead) -> Vec<String> {
+  let mut names = vec![];
+  match head {
+    ForHead::VarDecl(var) => {
+      for var_decl in &var.decls {
+        get_pat_names(&var_decl.name, &mut names);
+      }
+    }
+    ForHead::UsingDecl(using) => {
+      for var_decl in &using.decls {
+        get_pat_names(&var_decl.name, &mut names);
+      }
+    }
+    ForHead::Pat(_) => {}
+  }
+  names
+}
+
+pub fn recurse_and_find_sql(sqls: &mut Vec<SQL>, stmt: &Stmt, ctx: &ParseContext) -> color_eyre::Result<()> {
   match stmt {
     Stmt::Block(block) => {
+      let ctx = &ctx.enter_scope(get_declared_names(&block.stmts));
       for stmt in &block.stmts {
-        recurse_and_find_sql(sqls, stmt, import_alias)?;
+        recurse_and_find_sql(sqls, stmt, ctx)?;
       }
     }
     Stmt::With(with_stmt) => {
       let stmt = *with_stmt.body.clone();
-      recurse_and_find_sql(sqls, &stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &stmt, ctx)?;
     }
     Stmt::Return(rtn) => {
       if let Some(expr) = &rtn.arg {
         let span: MultiSpan = rtn.span.into();
-        get_sql_from_expr(sqls, &None, &expr.clone(), &span, import_alias);
+        get_sql_from_expr(sqls, &None, &expr.clone(), &span, ctx);
       }
     }
     Stmt::If(if_stmt) => {
       let stmt = *if_stmt.cons.clone();
-      recurse_and_find_sql(sqls, &stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &stmt, ctx)?;
     }
     Stmt::Switch(switch_stmt) => {
+      // every case of a switch shares the same block scope
+      let stmts = switch_stmt
+        .cases
+        .iter()
+        .flat_map(|case| case.cons.clone())
+        .collect::<Vec<_>>();
+      let ctx = &ctx.enter_scope(get_declared_names(&stmts));
       for case in &switch_stmt.cases {
         for stmt in &case.cons {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     Stmt::Throw(throw_stmt) => {
       let span: MultiSpan = throw_stmt.span.into();
       let expr = *throw_stmt.arg.clone();
-      get_sql_from_expr(sqls, &None, &expr, &span, import_alias);
+      get_sql_from_expr(sqls, &None, &expr, &span, ctx);
     }
     Stmt::Try(try_stmt) => {
       // handles statements inside try {}
+      let try_ctx = &ctx.enter_scope(get_declared_names(&try_stmt.block.stmts));
       for stmt in &try_stmt.block.stmts {
-        recurse_and_find_sql(sqls, stmt, import_alias)?;
+        recurse_and_find_sql(sqls, stmt, try_ctx)?;
       }
 
       // handles statements inside catch {}
-      if let Some(stmt) = &try_stmt.handler {
-        for stmt in &stmt.body.stmts {
-          recurse_and_find_sql(sqls, stmt, import_alias)?;
+      if let Some(handler) = &try_stmt.handler {
+        let mut names = get_declared_names(&handler.body.stmts);
+        if let Some(param) = &handler.param {
+          get_pat_names(param, &mut names);
+        }
+        let ctx = &ctx.enter_scope(names);
+        for stmt in &handler.body.stmts {
+          recurse_and_find_sql(sqls, stmt, ctx)?;
         }
       }
     }
     Stmt::While(while_stmt) => {
       let body_stmt = *while_stmt.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::DoWhile(do_while_stmt) => {
       let body_stmt = *do_while_stmt.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::For(for_stmt) => {
+      let mut names = vec![];
+      if let Some(VarDeclOrExpr::VarDecl(var)) = &for_stmt.init {
+        for var_decl in &var.decls {
+          get_pat_names(&var_decl.name, &mut names);
+        }
+      }
+      let ctx = &ctx.enter_scope(names);
       let body_stmt = *for_stmt.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::ForIn(for_in_stmt) => {
+      let ctx = &ctx.enter_scope(get_for_head_names(&for_in_stmt.left));
       let body_stmt = *for_in_stmt.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::ForOf(for_of_stmt) => {
+      let ctx = &ctx.enter_scope(get_for_head_names(&for_of_stmt.left));
       let body_stmt = *for_of_stmt.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::Decl(decl) => {
-      process_decl(sqls, decl, import_alias)?;
+      process_decl(sqls, decl, ctx)?;
     }
     Stmt::Expr(expr) => {
       let span: MultiSpan = expr.span.into();
       let expr = *expr.expr.clone();
-      get_sql_from_expr(sqls, &None, &expr, &span, import_alias);
+      get_sql_from_expr(sqls, &None, &expr, &span, ctx);
     }
     Stmt::Empty(_) => {}
     Stmt::Debugger(_) => {}
     Stmt::Labeled(labeled) => {
       let body_stmt = *labeled.body.clone();
-      recurse_and_find_sql(sqls, &body_stmt, import_alias)?;
+      recurse_and_find_sql(sqls, &body_stmt, ctx)?;
     }
     Stmt::Break(_) => {}
     Stmt::Continue(_) => {}
@@ -129,14 +167,22 @@ pub fn recurse_and_find_sql(sqls: &mut Vec<SQL>, stmt: &Stmt, import_alias: &Str
 
 pub fn parse_js_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Handler)> {
   let contents = fs::read_to_string(path).unwrap();
+  // JSX can only be parsed from .tsx and .jsx files, elsewhere `<Type>expr` is a type assertion
+  let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+  let tsx = matches!(extension, "tsx" | "jsx");
 
-  let cm: Lrc<SourceMap> = Default::default();
-  let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+  parse_js_source(path, contents, tsx)
+}
 
+/// Parses JS/TS source code that belongs to the file of the given path, spans of the SQLs point at the file
+pub fn parse_js_source(path: &PathBuf, contents: String, tsx: bool) -> Result<(HashMap<PathBuf, Vec<SQL>>, Handler)> {
+  let cm: Lrc<SourceMap> = Default::default();
   let file_path = path.as_os_str().to_str().unwrap().to_string();
+  let handler = create_handler(cm.clone(), &file_path);
+
   let fm = cm.new_source_file(Rc::new(FileName::Custom(file_path)), contents);
   let ts_syntax = TsSyntax {
-    tsx: false,
+    tsx,
     decorators: true,
     dts: false,
     no_early_errors: false,
@@ -161,38 +207,30 @@ pub fn parse_js_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Hand
 
   let mut sqls_map: HashMap<PathBuf, Vec<SQL>> = HashMap::new();
 
-  let import_alias = _module
-    .body
-    .iter()
-    .filter_map(|line| match line {
-      ModuleItem::ModuleDecl(ModuleDecl::Import(module_import_decl)) => Some(module_import_decl),
-      _ => None,
-    })
-    .find_map(find_sqlx_import_alias)
-    .unwrap_or_else(|| "sql".to_string());
+  let ctx = ParseContext::new(&_module);
 
   for item in &_module.body {
     let mut sqls = vec![];
 
     match item {
       ModuleItem::Stmt(stmt) => {
-        recurse_and_find_sql(&mut sqls, stmt, &import_alias)?;
+        recurse_and_find_sql(&mut sqls, stmt, &ctx)?;
       }
       ModuleItem::ModuleDecl(decl) => match decl {
         ModuleDecl::Import(_) => {}
         ModuleDecl::ExportDecl(export_decl) => {
           let decl = export_decl.decl.clone();
-          process_decl(&mut sqls, &decl, &import_alias)?;
+          process_decl(&mut sqls, &decl, &ctx)?;
         }
         ModuleDecl::ExportNamed(_) => {}
         ModuleDecl::ExportDefaultDecl(export_default_decl) => {
           let decl = export_default_decl.decl.clone();
-          process_default_decl(&mut sqls, &decl, &import_alias)?;
+          process_default_decl(&mut sqls, &decl, &ctx)?;
         }
         ModuleDecl::ExportDefaultExpr(export_default_expr) => {
           let expr = export_default_expr.expr.clone();
           let span: MultiSpan = export_default_expr.span.into();
-          get_sql_from_expr(&mut sqls, &None, &expr, &span, &import_alias)
+          get_sql_from_expr(&mut sqls, &None, &expr, &span, &ctx)
         }
         ModuleDecl::ExportAll(_) => {}
         ModuleDecl::TsImportEquals(_) => {}
@@ -207,5 +245,10 @@ pub fn parse_js_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Hand
     insert_or_append_sqls(&mut sqls_map, &sqls, path);
   }
 
+  // Fragments are only known to be inlined once the whole module is processed, so their declarations are dropped last
+  if let Some(sqls) = sqls_map.get_mut(path) {
+    sqls.retain(|sql| !ctx.is_inlined_fragment(sql));
+  }
+
   Ok((sqls_map, handler))
 }
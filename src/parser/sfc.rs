@@ -0,0 +1,157 @@
+use color_eyre::eyre::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use swc_common::errors::Handler;
+
+use crate::common::SQL;
+use crate::parser::js_parser::parse_js_source;
+
+/// Byte ranges of the server side code blocks of a single-file component along with whether they contain JSX
+///
+/// - Vue and Svelte: the contents of every `<script>` block, e.g. `<script setup lang="ts">` and `<script context="module">`,
+///   except for blocks within comments, `<template>` elements or template strings and blocks of a non-JS `type`
+/// - Astro: the frontmatter between the leading `---` fences, `<script>` tags of Astro components run in the browser
+fn find_script_blocks(extension: &str, contents: &str) -> (Vec<Range<usize>>, bool) {
+  match extension {
+    "astro" => {
+      let frontmatter = Regex::new(r"^\s*---[ \t]*\r?\n((?s).*?)\r?\n---").unwrap();
+      let blocks = frontmatter
+        .captures(contents)
+        .and_then(|captures| captures.get(1))
+        .map(|block| vec![block.range()])
+        .unwrap_or_default();
+      (blocks, false)
+    }
+    _ => {
+      // Comments, `<template>` elements and template strings are matched as well so the scripts within them are skipped
+      let tag =
+        Regex::new(r#"(?s)<!--.*?-->|`[^`]*<script\b[^`]*`|<(/?)template\b[^>]*>|<script\b([^>]*)>(.*?)</script\s*>"#)
+          .unwrap();
+      let lang = Regex::new(r#"\blang\s*=\s*["']?(tsx|jsx)\b"#).unwrap();
+      let mut template_depth = 0usize;
+      let mut tsx = false;
+      let mut blocks = vec![];
+
+      for captures in tag.captures_iter(contents) {
+        if let Some(closing) = captures.get(1) {
+          template_depth = match closing.as_str() {
+            "/" => template_depth.saturating_sub(1),
+            _ => template_depth + 1,
+          };
+          continue;
+        }
+
+        let (Some(attributes), Some(block)) = (captures.get(2), captures.get(3)) else {
+          continue;
+        };
+        if template_depth > 0 || !is_js_script(attributes.as_str()) {
+          continue;
+        }
+
+        tsx = tsx || lang.is_match(attributes.as_str());
+        blocks.push(block.range());
+      }
+      (blocks, tsx)
+    }
+  }
+}
+
+/// Whether a `<script>` tag holds code, rather than data or markup such as `type="text/template"` or
+/// `type="application/json"`, judging by its attributes
+fn is_js_script(attributes: &str) -> bool {
+  let script_type = Regex::new(r#"\btype\s*=\s*["']?([^"'\s>]+)"#).unwrap();
+  match script_type.captures(attributes) {
+    Some(captures) => matches!(
+      captures[1].to_lowercase().as_str(),
+      "module" | "text/javascript" | "application/javascript" | "text/typescript" | "application/typescript"
+    ),
+    None => true,
+  }
+}
+
+/// Blanks out everything but the given blocks, line breaks are kept and every other character becomes a space
+/// so that the lines and columns of the code within the blocks are exactly the same as in the original file
+fn mask_outside_blocks(contents: &str, blocks: &[Range<usize>]) -> String {
+  contents
+    .char_indices()
+    .map(|(idx, char)| {
+      let is_in_block = blocks.iter().any(|block| block.contains(&idx));
+      if is_in_block || char == '\n' || char == '\r' {
+        char
+      } else {
+        ' '
+      }
+    })
+    .collect()
+}
+
+/// Parses the script blocks of Vue, Svelte and Astro single-file components through the JS/TS parser
+/// Spans of the SQLs found point at the original component file
+pub fn parse_sfc_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Handler)> {
+  let contents = fs::read_to_string(path)?;
+  let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+
+  let (blocks, tsx) = find_script_blocks(extension, &contents);
+  let script = mask_outside_blocks(&contents, &blocks);
+
+  parse_js_source(path, script, tsx)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_mask_outside_script_blocks_of_vue() {
+    let contents =
+      "<template>\n  <div>{{ rows }}</div>\n</template>\n<script setup lang=\"ts\">\nconst a = 1\n</script>\n";
+    let (blocks, tsx) = find_script_blocks("vue", contents);
+    let masked = mask_outside_blocks(contents, &blocks);
+
+    assert!(!tsx);
+    assert_eq!(masked.len(), contents.len());
+    assert_eq!(masked.lines().nth(4), Some("const a = 1"));
+    assert_eq!(masked.trim(), "const a = 1");
+  }
+
+  #[test]
+  fn test_find_frontmatter_of_astro() {
+    let contents = "---\nconst a = 1\n---\n<h1>{a}</h1>\n<script>\nconst b = 2\n</script>\n";
+    let (blocks, _) = find_script_blocks("astro", contents);
+    let masked = mask_outside_blocks(contents, &blocks);
+
+    assert_eq!(masked.lines().nth(1), Some("const a = 1"));
+    assert_eq!(masked.trim(), "const a = 1");
+  }
+
+  #[test]
+  fn test_detect_jsx_script_blocks() {
+    let contents = "<script lang=\"tsx\">\nconst a = <div />\n</script>\n";
+    let (_, tsx) = find_script_blocks("vue", contents);
+
+    assert!(tsx);
+  }
+
+  #[test]
+  fn test_skip_script_blocks_in_comments_templates_and_template_strings() {
+    let contents = "<!--\n<script>\nconst a = 1\n</script>\n-->\n<template>\n  <script>const b = 2</script>\n</template>\n{@html `<script>const d = 4</script>`}\n<script setup>\nconst c = 3\n</script>\n";
+    let (blocks, _) = find_script_blocks("vue", contents);
+    let masked = mask_outside_blocks(contents, &blocks);
+
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(masked.trim(), "const c = 3");
+  }
+
+  #[test]
+  fn test_skip_script_blocks_that_are_not_code() {
+    let contents = "<script type=\"text/template\">\nconst a = 1\n</script>\n<script type=\"application/json\">{}</script>\n<script type=\"module\">\nconst b = 2\n</script>\n";
+    let (blocks, _) = find_script_blocks("svelte", contents);
+    let masked = mask_outside_blocks(contents, &blocks);
+
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(masked.trim(), "const b = 2");
+  }
+}
@@ -1,22 +1,25 @@
+use crate::common::diagnostics::create_handler;
+use crate::common::lazy::CONFIG;
+use crate::common::query_span::QuerySpan;
 use crate::common::SQL;
 use color_eyre::eyre::Result;
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use swc_common::{
-  errors::{ColorConfig, Handler},
-  sync::Lrc,
-  SourceMap, DUMMY_SP,
-};
+use std::rc::Rc;
+use swc_common::{errors::Handler, sync::Lrc, BytePos, FileName, SourceMap, Span};
 
 pub fn parse_sql_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Handler)> {
   let contents = fs::read_to_string(path)?;
   let cm: Lrc<SourceMap> = Default::default();
-  let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+  let file_path = path.as_os_str().to_str().unwrap().to_string();
+  let handler = create_handler(cm.clone(), &file_path);
+
+  let fm = cm.new_source_file(Rc::new(FileName::Custom(file_path)), contents.to_owned());
 
   let mut sqls_map: HashMap<PathBuf, Vec<SQL>> = HashMap::new();
-  let sqls = extract_sql_queries_from_file(&contents, path)?;
+  let sqls = extract_sql_queries_from_file(&contents, path, fm.start_pos)?;
 
   if !sqls.is_empty() {
     sqls_map.insert(path.clone(), sqls);
@@ -27,13 +30,21 @@ pub fn parse_sql_file(path: &PathBuf) -> Result<(HashMap<PathBuf, Vec<SQL>>, Han
 
 /// Extract SQL queries from raw SQL file content
 /// Supports multiple queries separated by semicolons and annotations
-fn extract_sql_queries_from_file(content: &str, file_path: &Path) -> Result<Vec<SQL>> {
+/// `start_pos` is where the file starts in the source map, spans of the queries are relative to it
+fn extract_sql_queries_from_file(content: &str, file_path: &Path, start_pos: BytePos) -> Result<Vec<SQL>> {
   let mut queries = Vec::new();
 
+  // A `@db` annotation in the file header applies to every query in the file
+  let file_db_connection = extract_file_db_connection(content);
+
   // Split content by semicolons to handle multiple queries
   let query_blocks = split_sql_queries(content);
 
+  // Every character of the file belongs to one of the blocks, so a block starts where the previous one ends
+  let mut block_start = 0;
   for (index, block) in query_blocks.iter().enumerate() {
+    let trimmed_start = block_start + block.len() - block.trim_start().len();
+    block_start += block.len();
     let trimmed_block = block.trim();
 
     // Skip empty blocks or comment-only blocks
@@ -42,7 +53,12 @@ fn extract_sql_queries_from_file(content: &str, file_path: &Path) -> Result<Vec<
     }
 
     // Extract annotations and clean query
-    let (query_name, _db_connection, cleaned_query) = extract_annotations_from_sql(trimmed_block);
+    let (query_name, db_connection, cleaned_lines) = extract_annotations_from_sql(trimmed_block);
+    let cleaned_query = cleaned_lines
+      .iter()
+      .map(|(_, line)| *line)
+      .collect::<Vec<_>>()
+      .join("\n");
 
     // Skip if no actual SQL content after cleaning
     if cleaned_query.trim().is_empty() {
@@ -52,10 +68,24 @@ fn extract_sql_queries_from_file(content: &str, file_path: &Path) -> Result<Vec<
     // Generate default name if not provided via annotation
     let var_decl_name = query_name.or_else(|| generate_default_query_name(file_path, index));
 
+    // Each line kept in the query is traced back to the file, annotation lines in between are left out of the query
+    let mut query_spans = vec![];
+    let mut query_offset = 0;
+    for (line_offset, line) in &cleaned_lines {
+      let lo = start_pos + BytePos((trimmed_start + line_offset) as u32);
+      let span = Span::new(lo, lo + BytePos(line.len() as u32));
+      query_spans.push(QuerySpan::new(query_offset..query_offset + line.len(), span, true));
+      query_offset += line.len() + 1;
+    }
+
+    let lo = start_pos + BytePos(trimmed_start as u32);
     let sql = SQL {
       query: cleaned_query,
       var_decl_name,
-      span: DUMMY_SP.into(),
+      span: Span::new(lo, lo + BytePos(trimmed_block.len() as u32)).into(),
+      // The query's own annotation takes precedence over the file header
+      db_connection: db_connection.or_else(|| file_db_connection.clone()),
+      query_spans,
     };
 
     queries.push(sql);
@@ -66,6 +96,7 @@ fn extract_sql_queries_from_file(content: &str, file_path: &Path) -> Result<Vec<
 
 /// Split SQL content into individual queries
 /// Handles semicolons inside strings and comments properly
+#[allow(clippy::collapsible_match)]
 fn split_sql_queries(content: &str) -> Vec<String> {
   let mut queries = Vec::new();
   let mut current_query = String::new();
@@ -170,9 +201,35 @@ fn is_comment_only(block: &str) -> bool {
   true
 }
 
-/// Extract annotations from SQL content and return cleaned query
+/// Extract the `@db` annotation from the header of a SQL file
+///
+/// The header is the comment block at the very top of the file that is separated from the first query
+/// by an empty line, e.g.
+///
+/// -- @db: mysql
+///
+/// -- @name: getItems
+/// SELECT * FROM items;
+fn extract_file_db_connection(content: &str) -> Option<String> {
+  let mut lines = content.lines().skip_while(|line| line.trim().is_empty()).peekable();
+  let mut db_connection = None;
+
+  while let Some(line) = lines.next_if(|line| line.trim().starts_with("--")) {
+    if let Some(connection) = CONFIG.get_db_annotation(line) {
+      db_connection = Some(connection);
+    }
+  }
+
+  // Without an empty line after the comments, they belong to the first query
+  match lines.peek() {
+    Some(line) if line.trim().is_empty() => db_connection,
+    _ => None,
+  }
+}
+
+/// Extract annotations from SQL content and return the lines of the cleaned query along with their byte offsets
 /// Supports @name and @db annotations
-fn extract_annotations_from_sql(content: &str) -> (Option<String>, Option<String>, String) {
+fn extract_annotations_from_sql(content: &str) -> (Option<String>, Option<String>, Vec<(usize, &str)>) {
   let mut query_name = None;
   let mut db_connection = None;
   let mut cleaned_lines = Vec::new();
@@ -181,12 +238,15 @@ fn extract_annotations_from_sql(content: &str) -> (Option<String>, Option<String
   let name_re = Regex::new(r"@name:\s*(.+)").unwrap();
   let db_re = Regex::new(r"@db:\s*(.+)").unwrap();
 
+  let mut line_offset = 0;
   for line in content.lines() {
+    let offset = line_offset;
+    line_offset += content[offset..].find('\n').map(|idx| idx + 1).unwrap_or(line.len());
     let trimmed_line = line.trim();
 
     // Skip empty lines
     if trimmed_line.is_empty() {
-      cleaned_lines.push(line);
+      cleaned_lines.push((offset, line));
       continue;
     }
 
@@ -224,11 +284,10 @@ fn extract_annotations_from_sql(content: &str) -> (Option<String>, Option<String
       }
     }
 
-    cleaned_lines.push(line);
+    cleaned_lines.push((offset, line));
   }
 
-  let cleaned_query = cleaned_lines.join("\n");
-  (query_name, db_connection, cleaned_query)
+  (query_name, db_connection, cleaned_lines)
 }
 
 /// Generate a default query name from file path and index
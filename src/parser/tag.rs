@@ -1,7 +1,16 @@
+use crate::common::lazy::CONFIG;
+use crate::common::query_span::QuerySpan;
+use crate::common::types::DatabaseType;
 use crate::common::SQL;
+use crate::parser::context::{
+  get_constructor_names, get_declared_names, get_function_names, get_pat_names, ParseContext,
+};
 use crate::parser::js_parser::{get_var_decl_name_from_key, recurse_and_find_sql};
-use swc_common::MultiSpan;
-use swc_ecma_ast::{BlockStmt, ClassMember, Expr, OptChainBase, Pat, Prop, PropOrSpread, SuperProp, VarDeclarator};
+use swc_common::{MultiSpan, Spanned};
+use swc_ecma_ast::{
+  BlockStmt, ClassMember, Expr, JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementChild, JSXExpr, OptChainBase, Pat,
+  Prop, PropOrSpread, SuperProp, Tpl, VarDeclarator,
+};
 
 /// The method process block statement as expression
 /// It receives a block statement object from Class expression
@@ -11,17 +20,18 @@ pub fn process_block_stmt_as_expr(
   sqls: &mut Vec<SQL>,
   var_decl_name: &Option<String>,
   span: &MultiSpan,
-  import_alias: &String,
+  ctx: &ParseContext,
 ) {
   if let Some(body) = block_stmt {
+    let ctx = &ctx.enter_scope(get_declared_names(&body.stmts));
     for stmt in &body.stmts {
       let expr = stmt.as_expr();
       if let Some(expr) = expr {
         let expr = &expr.expr;
-        get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
       } else {
         // TODO: we should be using `?` and return Results
-        recurse_and_find_sql(sqls, stmt, import_alias).unwrap();
+        recurse_and_find_sql(sqls, stmt, ctx).unwrap();
       }
     }
   }
@@ -43,51 +53,159 @@ pub fn get_var_decl_name(var_declarator: &VarDeclarator) -> Option<String> {
   }
 }
 
+/// Joins the quasis of a tagged template back into a single query, each `${expr}` becomes a bind parameter
+/// This is how tagged template drivers such as postgres.js and slonik send the query, so params are in interpolation order
+/// An interpolated fragment (see `ParseContext::fragments`) is inlined instead and does not take up a param position
+///
+/// e.g. sql`SELECT * FROM items WHERE id = ${id} AND name = ${name}` becomes
+/// `SELECT * FROM items WHERE id = $1 AND name = $2` for PostgreSQL and `... id = ? AND name = ?` for MySQL
+///
+/// Along with the query, it returns where each piece of the query is within the template (see `SQL::query_spans`)
+pub fn get_query_from_tpl(tpl: &Tpl, db_connection: &Option<String>, ctx: &ParseContext) -> (String, Vec<QuerySpan>) {
+  let quasis = tpl
+    .quasis
+    .iter()
+    .map(|quasi| quasi.raw.to_string())
+    .collect::<Vec<_>>();
+
+  // The connection of the tag or the `@db` annotation decides which database, hence which placeholder style, the query is for
+  let connection = db_connection
+    .to_owned()
+    .unwrap_or_else(|| CONFIG.get_correct_db_connection(&quasis.concat()));
+  let db_type = CONFIG
+    .connections
+    .get(&connection)
+    .map(|conn| conn.db_type.to_owned())
+    .unwrap_or(DatabaseType::Postgres);
+
+  let mut query = String::new();
+  let mut query_spans = vec![];
+  let mut position = 0;
+  for (idx, quasi) in quasis.iter().enumerate() {
+    if idx > 0 {
+      let expr = &tpl.exprs[idx - 1];
+      let start = query.len();
+      match ctx.inline_fragment(expr) {
+        Some(fragment) => query.push_str(&fragment),
+        None => {
+          position += 1;
+          query.push_str(&db_type.get_placeholder(position));
+        }
+      }
+      // Errors within an inlined fragment are reported at the interpolation, as the query that composes it is at fault
+      query_spans.push(QuerySpan::new(start..query.len(), expr.span(), false));
+    }
+    let start = query.len();
+    query.push_str(quasi);
+    query_spans.push(QuerySpan::new(start..query.len(), tpl.quasis[idx].span, true));
+  }
+  (query, query_spans)
+}
+
+/// Finds SQLs within the `{...}` expressions of a JSX element's attributes and children
+/// e.g. `<Items rows={await db.query(sql`SELECT * FROM items`)} />` of a React server component
+fn process_jsx_element(
+  jsx_element: &JSXElement,
+  sqls: &mut Vec<SQL>,
+  var_decl_name: &Option<String>,
+  span: &MultiSpan,
+  ctx: &ParseContext,
+) {
+  for attr in &jsx_element.opening.attrs {
+    match attr {
+      JSXAttrOrSpread::JSXAttr(attr) => match &attr.value {
+        Some(JSXAttrValue::JSXExprContainer(container)) => {
+          if let JSXExpr::Expr(expr) = &container.expr {
+            get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
+          }
+        }
+        Some(JSXAttrValue::JSXElement(element)) => process_jsx_element(element, sqls, var_decl_name, span, ctx),
+        Some(JSXAttrValue::JSXFragment(fragment)) => {
+          process_jsx_children(&fragment.children, sqls, var_decl_name, span, ctx)
+        }
+        Some(JSXAttrValue::Str(_)) | None => {}
+      },
+      JSXAttrOrSpread::SpreadElement(spread) => get_sql_from_expr(sqls, var_decl_name, &spread.expr, span, ctx),
+    }
+  }
+
+  process_jsx_children(&jsx_element.children, sqls, var_decl_name, span, ctx);
+}
+
+fn process_jsx_children(
+  children: &[JSXElementChild],
+  sqls: &mut Vec<SQL>,
+  var_decl_name: &Option<String>,
+  span: &MultiSpan,
+  ctx: &ParseContext,
+) {
+  for child in children {
+    match child {
+      JSXElementChild::JSXExprContainer(container) => {
+        if let JSXExpr::Expr(expr) = &container.expr {
+          get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
+        }
+      }
+      JSXElementChild::JSXSpreadChild(spread) => get_sql_from_expr(sqls, var_decl_name, &spread.expr, span, ctx),
+      JSXElementChild::JSXElement(element) => process_jsx_element(element, sqls, var_decl_name, span, ctx),
+      JSXElementChild::JSXFragment(fragment) => {
+        process_jsx_children(&fragment.children, sqls, var_decl_name, span, ctx)
+      }
+      JSXElementChild::JSXText(_) => {}
+    }
+  }
+}
+
 pub fn get_sql_from_expr(
   sqls: &mut Vec<SQL>,
   var_decl_name: &Option<String>,
   expr: &Expr,
   span: &MultiSpan,
-  import_alias: &String,
+  ctx: &ParseContext,
 ) {
   match &expr {
     Expr::TaggedTpl(tagged_tpl) => {
-      let tag = &*tagged_tpl.tag;
-      if let Expr::Ident(ident) = tag {
-        let ident = ident.to_string();
+      if let Some(tag) = ctx.resolve_tag(&tagged_tpl.tag) {
+        // The `@db` annotation of the query takes precedence over the connection configured for its tag
+        let raw_sql = tagged_tpl
+          .tpl
+          .quasis
+          .iter()
+          .map(|quasi| quasi.raw.to_string())
+          .collect::<String>();
+        let db_connection = tag
+          .connection
+          .to_owned()
+          .filter(|_| CONFIG.get_db_annotation(&raw_sql).is_none());
 
-        if ident.contains(import_alias) {
-          let new_sqls: Vec<SQL> = tagged_tpl
-            .tpl
-            .quasis
-            .iter()
-            .map(|tpl_element| SQL {
-              var_decl_name: var_decl_name.to_owned(),
-              query: tpl_element.raw.to_string(),
-              span: span.clone(),
-            })
-            .collect();
-          sqls.extend(new_sqls.clone());
-        }
+        let (query, query_spans) = get_query_from_tpl(&tagged_tpl.tpl, &db_connection, ctx);
+
+        sqls.push(SQL {
+          var_decl_name: var_decl_name.to_owned(),
+          query,
+          span: span.clone(),
+          db_connection,
+          query_spans,
+        });
       }
     }
     Expr::TsNonNull(expr) => {
-      get_sql_from_expr(sqls, var_decl_name, &expr.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &expr.expr, span, ctx);
     }
     Expr::Call(call_expr) => {
       // Always traverse the callee to detect SQL queries in method chains
       if let Some(callee_expr) = &call_expr.callee.as_expr() {
-        get_sql_from_expr(sqls, var_decl_name, callee_expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, callee_expr, span, ctx);
       }
       // Also traverse all arguments
       for arg in &call_expr.args {
-        get_sql_from_expr(sqls, var_decl_name, &arg.expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, &arg.expr, span, ctx);
       }
     }
     Expr::This(_) => {}
     Expr::Array(a) => {
       for expr in a.elems.iter().flatten() {
-        get_sql_from_expr(sqls, var_decl_name, &expr.expr, span, import_alias)
+        get_sql_from_expr(sqls, var_decl_name, &expr.expr, span, ctx)
       }
     }
     Expr::Object(object) => {
@@ -98,23 +216,27 @@ pub fn get_sql_from_expr(
             Prop::Shorthand(_) => {}
             Prop::KeyValue(key_val) => {
               let value = &key_val.value;
-              get_sql_from_expr(sqls, var_decl_name, value, span, import_alias);
+              get_sql_from_expr(sqls, var_decl_name, value, span, ctx);
             }
             Prop::Assign(assign) => {
               let value = &assign.value;
-              get_sql_from_expr(sqls, var_decl_name, value, span, import_alias);
+              get_sql_from_expr(sqls, var_decl_name, value, span, ctx);
             }
             Prop::Getter(getter) => {
               let body = &getter.body;
-              process_block_stmt_as_expr(body, sqls, var_decl_name, span, import_alias);
+              process_block_stmt_as_expr(body, sqls, var_decl_name, span, ctx);
             }
             Prop::Setter(setter) => {
+              let mut names = vec![];
+              get_pat_names(&setter.param, &mut names);
+              let ctx = &ctx.enter_scope(names);
               let body = &setter.body;
-              process_block_stmt_as_expr(body, sqls, var_decl_name, span, import_alias);
+              process_block_stmt_as_expr(body, sqls, var_decl_name, span, ctx);
             }
             Prop::Method(method) => {
+              let ctx = &ctx.enter_scope(get_function_names(&method.function));
               let body = &method.function.body;
-              process_block_stmt_as_expr(body, sqls, var_decl_name, span, import_alias);
+              process_block_stmt_as_expr(body, sqls, var_decl_name, span, ctx);
             }
           },
         }
@@ -123,21 +245,21 @@ pub fn get_sql_from_expr(
     Expr::Fn(_) => {}
     Expr::Unary(unary) => {
       let expr = &unary.arg;
-      get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+      get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
     }
     Expr::Update(update) => {
       let expr = &update.arg;
-      get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+      get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
     }
     Expr::Bin(bin) => {
       let left = &bin.left;
       let right = &bin.right;
-      get_sql_from_expr(sqls, var_decl_name, left, span, import_alias);
-      get_sql_from_expr(sqls, var_decl_name, right, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, left, span, ctx);
+      get_sql_from_expr(sqls, var_decl_name, right, span, ctx);
     }
     Expr::Assign(assign) => {
       let right_expr = &assign.right;
-      get_sql_from_expr(sqls, var_decl_name, right_expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, right_expr, span, ctx);
       /*
       We will be ignore processing assign.left. The patterns include
 
@@ -150,7 +272,7 @@ pub fn get_sql_from_expr(
     }
     Expr::Member(member) => {
       let obj = &member.obj;
-      get_sql_from_expr(sqls, var_decl_name, obj, span, import_alias)
+      get_sql_from_expr(sqls, var_decl_name, obj, span, ctx)
     }
     Expr::SuperProp(s) => {
       let super_prop = &s.prop;
@@ -158,7 +280,7 @@ pub fn get_sql_from_expr(
         SuperProp::Ident(_) => {}
         SuperProp::Computed(comp) => {
           let expr = &comp.expr;
-          get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+          get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
         }
       }
     }
@@ -166,47 +288,52 @@ pub fn get_sql_from_expr(
       let test = &cond.test;
       let cons = &cond.cons;
       let alt = &cond.alt;
-      get_sql_from_expr(sqls, var_decl_name, test, span, import_alias);
-      get_sql_from_expr(sqls, var_decl_name, cons, span, import_alias);
-      get_sql_from_expr(sqls, var_decl_name, alt, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, test, span, ctx);
+      get_sql_from_expr(sqls, var_decl_name, cons, span, ctx);
+      get_sql_from_expr(sqls, var_decl_name, alt, span, ctx);
     }
     Expr::New(expr) => {
       let args = &expr.args;
       let expr = &expr.callee;
       if let Some(args) = &args {
         for arg in args {
-          get_sql_from_expr(sqls, var_decl_name, &arg.expr, span, import_alias);
+          get_sql_from_expr(sqls, var_decl_name, &arg.expr, span, ctx);
         }
       }
 
-      get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
     }
     Expr::Seq(seq) => {
       let exprs = &seq.exprs;
       for expr in exprs {
-        get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
       }
     }
     Expr::Ident(_ident) => {}
     Expr::Lit(_lit) => {}
     Expr::Tpl(tpl) => {
       for expr in &tpl.exprs {
-        get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
       }
     }
     Expr::Arrow(arrow) => {
+      let mut names = vec![];
+      for param in &arrow.params {
+        get_pat_names(param, &mut names);
+      }
+      let ctx = &ctx.enter_scope(names);
       let expr = &arrow.clone().body.expr();
       let block_stmt = &arrow.clone().body.block_stmt();
-      process_block_stmt_as_expr(block_stmt, sqls, var_decl_name, span, import_alias);
+      process_block_stmt_as_expr(block_stmt, sqls, var_decl_name, span, ctx);
 
       if let Some(expr) = expr {
-        get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+        get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
       }
 
       for param in &arrow.params {
         let param = param.as_expr();
         if let Some(expr) = &param {
-          get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+          get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
         }
       }
     }
@@ -215,41 +342,44 @@ pub fn get_sql_from_expr(
       for body_stmt in class_body {
         match body_stmt {
           ClassMember::Constructor(constructor) => {
+            let ctx = &ctx.enter_scope(get_constructor_names(constructor));
             if let Some(body) = &constructor.body {
               for stmt in &body.stmts {
                 let expr = stmt.as_expr();
                 if let Some(expr) = expr {
                   let expr = &expr.expr;
-                  return get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+                  return get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
                 }
               }
             }
           }
           ClassMember::Method(method) => {
+            let ctx = &ctx.enter_scope(get_function_names(&method.function));
             let body = &method.function.body;
-            process_block_stmt_as_expr(body, sqls, var_decl_name, span, import_alias);
+            process_block_stmt_as_expr(body, sqls, var_decl_name, span, ctx);
           }
           ClassMember::PrivateMethod(private_method) => {
+            let ctx = &ctx.enter_scope(get_function_names(&private_method.function));
             let body = &private_method.function.body;
-            process_block_stmt_as_expr(body, sqls, var_decl_name, span, import_alias);
+            process_block_stmt_as_expr(body, sqls, var_decl_name, span, ctx);
           }
           ClassMember::ClassProp(class_prop) => {
             let body = &class_prop.value;
             if let Some(body) = body {
-              return get_sql_from_expr(sqls, var_decl_name, body, span, import_alias);
+              return get_sql_from_expr(sqls, var_decl_name, body, span, ctx);
             }
           }
           ClassMember::PrivateProp(private_prop) => {
             let body = &private_prop.value;
             if let Some(body) = body {
-              return get_sql_from_expr(sqls, var_decl_name, body, span, import_alias);
+              return get_sql_from_expr(sqls, var_decl_name, body, span, ctx);
             }
           }
           ClassMember::TsIndexSignature(_) => {}
           ClassMember::Empty(_) => {}
           ClassMember::StaticBlock(static_block) => {
             let body = &static_block.body;
-            process_block_stmt_as_expr(&Some(body.clone()), sqls, var_decl_name, span, import_alias);
+            process_block_stmt_as_expr(&Some(body.clone()), sqls, var_decl_name, span, ctx);
           }
           ClassMember::AutoAccessor(auto_accessor) => {
             let value = &auto_accessor.value;
@@ -257,7 +387,7 @@ pub fn get_sql_from_expr(
 
             if let Some(expr) = &value {
               let var_decl_name = &get_var_decl_name_from_key(key);
-              get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+              get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
             }
           }
         }
@@ -266,33 +396,33 @@ pub fn get_sql_from_expr(
     Expr::Yield(yield_expr) => {
       let expr = &yield_expr.arg;
       if let Some(expr) = expr {
-        get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+        get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
       }
     }
     Expr::MetaProp(_) => {}
     Expr::Await(await_expr) => {
       let expr = &await_expr.arg;
-      get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+      get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
     }
     Expr::Paren(paren) => {
       let expr = &paren.expr;
-      get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias)
+      get_sql_from_expr(sqls, var_decl_name, expr, span, ctx)
     }
     Expr::OptChain(opt_chain) => {
       let expr = &*opt_chain.base;
       match &expr {
         OptChainBase::Member(member) => {
           let obj = &member.obj;
-          get_sql_from_expr(sqls, var_decl_name, obj, span, import_alias);
+          get_sql_from_expr(sqls, var_decl_name, obj, span, ctx);
         }
         OptChainBase::Call(call) => {
           let expr = &call.callee;
-          get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+          get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
 
           let args = &call.args;
           for arg in args.iter() {
             let expr = &arg.expr;
-            get_sql_from_expr(sqls, var_decl_name, expr, span, import_alias);
+            get_sql_from_expr(sqls, var_decl_name, expr, span, ctx);
           }
         }
       }
@@ -300,36 +430,40 @@ pub fn get_sql_from_expr(
     Expr::JSXMember(_) => {}
     Expr::JSXNamespacedName(_) => {}
     Expr::JSXEmpty(_) => {}
-    Expr::JSXElement(_) => {}
-    Expr::JSXFragment(_) => {}
+    Expr::JSXElement(jsx_element) => {
+      process_jsx_element(jsx_element, sqls, var_decl_name, span, ctx);
+    }
+    Expr::JSXFragment(jsx_fragment) => {
+      process_jsx_children(&jsx_fragment.children, sqls, var_decl_name, span, ctx);
+    }
     Expr::TsTypeAssertion(ts_type_assertion) => {
       // Traverse into type assertions like `<Type>expr`
-      get_sql_from_expr(sqls, var_decl_name, &ts_type_assertion.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &ts_type_assertion.expr, span, ctx);
     }
     Expr::TsConstAssertion(ts_const_assertion) => {
       // Traverse into const assertions like `expr as const`
-      get_sql_from_expr(sqls, var_decl_name, &ts_const_assertion.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &ts_const_assertion.expr, span, ctx);
     }
     Expr::TsAs(ts_as) => {
       // Traverse into type assertions like `expr as Type`
-      get_sql_from_expr(sqls, var_decl_name, &ts_as.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &ts_as.expr, span, ctx);
     }
     Expr::TsInstantiation(ts_instantiation) => {
       // Traverse into generic instantiations like `expr<Type>`
-      get_sql_from_expr(sqls, var_decl_name, &ts_instantiation.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &ts_instantiation.expr, span, ctx);
     }
     Expr::PrivateName(_) => {}
     Expr::Invalid(_) => {}
     Expr::TsSatisfies(ts_satisfies) => {
       // Traverse into satisfies expressions like `expr satisfies Type`
-      get_sql_from_expr(sqls, var_decl_name, &ts_satisfies.expr, span, import_alias);
+      get_sql_from_expr(sqls, var_decl_name, &ts_satisfies.expr, span, ctx);
     }
   }
 }
 
 /// you would normally pass in any var declarator such as
 /// const sql = sql`SELECT * FROM xxx;`
-pub fn get_sql_from_var_decl(var_declarator: &VarDeclarator, span: &MultiSpan, import_alias: &String) -> Vec<SQL> {
+pub fn get_sql_from_var_decl(var_declarator: &VarDeclarator, span: &MultiSpan, ctx: &ParseContext) -> Vec<SQL> {
   let mut bag_of_sqls: Vec<SQL> = vec![];
   let var_decl_name = get_var_decl_name(var_declarator);
 
@@ -340,7 +474,7 @@ pub fn get_sql_from_var_decl(var_declarator: &VarDeclarator, span: &MultiSpan, i
 
   if let Some(init) = &var_declarator.init {
     // TODO: make it understand `const someQuery = SQLX.sql`SELECT * FROM lazy_unknown2`;` in js_failure_path1/lazy-loaded.js
-    get_sql_from_expr(&mut bag_of_sqls, &var_decl_name, &init.clone(), span, import_alias);
+    get_sql_from_expr(&mut bag_of_sqls, &var_decl_name, &init.clone(), span, ctx);
   }
 
   bag_of_sqls
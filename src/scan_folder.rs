@@ -30,32 +30,34 @@ fn is_match(pattern: &str, path: &Path) -> bool {
   }
 }
 
-pub fn scan_folder<'a>(folder: &'a PathBuf, file_extension: &'a FileExtension) -> Vec<PathBuf> {
+/// Whether the file would be picked up by scanning the folder for the file extension
+pub fn is_scanned_file(folder: &Path, file_path: &Path, file_extension: &FileExtension) -> bool {
   let ignore_paths = &CONFIG.ignore_patterns;
   let node_modules_path = folder.join(Path::new("node_modules"));
+
+  // 1. ignore node modules
+  if file_path.starts_with(node_modules_path.as_path()) {
+    return false;
+  }
+
+  // 2. any custom ignore paths set by user should be ignored
+  let should_ignore = ignore_paths.iter().any(|ignore| is_match(ignore.as_str(), file_path));
+  if should_ignore {
+    return false;
+  }
+
+  let f_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+
+  f_name.ends_with(file_extension.to_string().as_str())
+}
+
+pub fn scan_folder<'a>(folder: &'a PathBuf, file_extension: &'a FileExtension) -> Vec<PathBuf> {
   let path = Path::new(folder);
   let result: Vec<_> = WalkDir::new(path)
     .follow_links(true)
     .into_iter()
     .filter_map(|e| e.ok())
-    .filter(|entry| {
-      // 1. ignore node modules
-      if entry.path().starts_with(node_modules_path.as_path()) {
-        return false;
-      }
-
-      // 2. any custom ignore paths set by user should be ignored
-      let should_ignore = ignore_paths
-        .iter()
-        .any(|ignore| is_match(ignore.as_str(), entry.path()));
-      if should_ignore {
-        return false;
-      }
-
-      let f_name = entry.file_name().to_string_lossy();
-
-      f_name.ends_with(file_extension.to_string().as_str())
-    })
+    .filter(|entry| is_scanned_file(folder, entry.path(), file_extension))
     .map(|entry| entry.path().to_owned())
     .collect();
 
@@ -1,12 +1,12 @@
 use crate::ts_generator::types::ts_query::TsFieldType;
 use regex::Regex;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
-pub fn extract_result_annotations(query: &str) -> HashMap<String, Vec<TsFieldType>> {
+pub fn extract_result_annotations(query: &str) -> BTreeMap<String, Vec<TsFieldType>> {
   let re = Regex::new(r"@result (\w+): ([^\n]+)").unwrap();
   let captures = re.captures_iter(query);
 
-  let mut result: HashMap<String, Vec<TsFieldType>> = HashMap::new();
+  let mut result: BTreeMap<String, Vec<TsFieldType>> = BTreeMap::new();
   for capture in captures {
     let name = capture.get(1);
     let types = capture.get(2);
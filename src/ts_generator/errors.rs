@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::common::suggestion::{format_suggestions, get_suggestions};
+
 #[derive(Error, Debug)]
 pub enum TsGeneratorError {
   #[error("[E001] Unable to infer an appropriate name for the query - query: `{0}`")]
@@ -34,11 +36,11 @@ pub enum TsGeneratorError {
   UnknownErrorWhileProcessingTableWithJoins(String),
   #[error("[E015] Table expressions are not supported in INSERT statements - query: `{0}`")]
   TableExpressionInInsertStatement(String),
-  #[error("[E016] Column '{column}' not found in table '{table}'. Available columns: {available_columns}")]
+  #[error("[E016] Column '{column}' not found in table '{table}'{did_you_mean}")]
   ColumnNotFoundInTable {
     column: String,
     table: String,
-    available_columns: String,
+    did_you_mean: String,
   },
   #[error("[E017] Failed to process INSERT statement: {reason}. Query: `{query}`")]
   InsertStatementProcessingFailed { reason: String, query: String },
@@ -51,3 +53,19 @@ pub enum TsGeneratorError {
   #[error("Unknown error: `{0}`")]
   Unknown(String),
 }
+
+impl TsGeneratorError {
+  /// E016 along with the columns of the table that are closest to the unknown column
+  pub fn column_not_found<'a>(column: &str, table: &str, columns: impl IntoIterator<Item = &'a String>) -> Self {
+    let suggestions = get_suggestions(column, columns.into_iter().map(String::as_str));
+    let did_you_mean = format_suggestions(&suggestions)
+      .map(|did_you_mean| format!(", {did_you_mean}"))
+      .unwrap_or_default();
+
+    TsGeneratorError::ColumnNotFoundInTable {
+      column: column.to_string(),
+      table: table.to_string(),
+      did_you_mean,
+    }
+  }
+}
@@ -1,10 +1,10 @@
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::{fs, path::Path};
 
 use super::annotations::extract_param_annotations;
 
-use crate::common::lazy::CONFIG;
+use crate::common::lazy::{CLI_ARGS, CONFIG};
 use crate::common::SQL;
 use crate::core::connection::DBConn;
 use crate::ts_generator::annotations::extract_result_annotations;
@@ -16,8 +16,9 @@ use color_eyre::eyre::eyre;
 use color_eyre::eyre::Result;
 use convert_case::{Case, Casing};
 use regex::Regex;
+use sha2::{Digest, Sha256};
 use sqlparser::{
-  dialect::{Dialect, MySqlDialect, PostgreSqlDialect},
+  dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect},
   parser::Parser,
 };
 
@@ -52,80 +53,107 @@ pub fn get_query_name(sql: &SQL) -> Result<String> {
   Err(TsGeneratorError::EmptyQueryNameFromVarDecl(sql.query.to_string()).into())
 }
 
-/// Write colocated Type definition file next to the TS source code
-pub fn write_colocated_ts_file(file_path: &Path, sqls_to_write: String) -> Result<()> {
+/// Path of the colocated Type definition file of the TS source code e.g. `index.queries.ts` of `index.ts`
+pub fn get_colocated_ts_file_path(file_path: &Path) -> PathBuf {
   let path = file_path.parent().unwrap();
   let file = file_path.file_stem().unwrap();
   let file_name = file.to_str().unwrap();
-  let query_ts_file_path = path.join(Path::new(format!("{file_name}.queries.ts").as_str()));
-
-  if query_ts_file_path.exists() {
-    fs::remove_file(&query_ts_file_path)?;
-  }
-
-  let mut file_to_write = fs::File::create(query_ts_file_path)?;
-
-  file_to_write.write_all(sqls_to_write.as_ref())?;
-  Ok(())
+  path.join(Path::new(format!("{file_name}.queries.ts").as_str()))
 }
 
-/// Write a single TS file to a target destination according to CLI_ARGS.generate_path
-pub fn write_single_ts_file(sqls_to_write: String) -> Result<()> {
+/// Path of the single TS file that types are generated into, according to CLI_ARGS.generate_path
+pub fn get_single_ts_file_path() -> Result<PathBuf> {
   let generate_path = CONFIG.generate_types_config.clone().and_then(|x| x.generate_path);
-  let output = generate_path.ok_or(eyre!(
+  generate_path.ok_or(eyre!(
     "TS generation path (--generate-path=) is required if you want to generate the SQL at a single path"
-  ))?;
+  ))
+}
 
-  let parent_output_path: Option<&Path> = output.parent();
-  if let Some(parent_output_path) = parent_output_path {
-    fs::create_dir_all(parent_output_path)?;
+/// First line of every file generated by sqlx-ts, which tells them apart from files written by hand
+pub const GENERATED_FILE_MARKER: &str = "// @generated by sqlx-ts - do not edit";
+
+/// Header of a generated file, recording the source files its types come from and a hash of the types
+/// Source files are recorded relative to the generated file when they are in the same folder or below
+pub fn get_generated_file_header(generated_file_path: &Path, source_files: &[&PathBuf], types: &str) -> String {
+  let folder = generated_file_path.parent().unwrap_or(Path::new(""));
+
+  let mut header = format!("{GENERATED_FILE_MARKER}\n");
+  for source_file in source_files {
+    let source_file = source_file.strip_prefix(folder).unwrap_or(source_file);
+    // Separators are normalised so that the header is the same on every platform
+    header.push_str(&format!(
+      "// source: {}\n",
+      source_file.to_string_lossy().replace('\\', "/")
+    ));
   }
-
-  let mut file_to_write = OpenOptions::new()
-    .create(true)
-    .read(true)
-    .append(true)
-    .open(&output)
-    .unwrap_or_else(|_| {
-      panic!(
-        "Failed to write to file {:?} - check if the --generate-path provided is an existing folder",
-        &output
-      )
-    });
-
-  file_to_write.write_all(sqls_to_write.as_ref())?;
-  Ok(())
+  header.push_str(&format!("// hash: sha256:{:x}\n\n", Sha256::digest(types)));
+  header
 }
 
-/// clears the target single TS file if it exists
-pub fn clear_single_ts_file_if_exists() -> Result<()> {
-  let generate_types_config = CONFIG.generate_types_config.clone();
-  if generate_types_config.is_none() {
-    return Ok(());
+/// Contents of each file that generating the types of each source file would write
+/// With --generate-path, the types of every source file make up a single file, ordered by the path of the source file
+pub fn get_generated_files(types: &BTreeMap<PathBuf, String>) -> Result<Vec<(PathBuf, String)>> {
+  if CLI_ARGS.generate_path.is_none() {
+    let generated_files = types
+      .iter()
+      .map(|(file_path, types)| {
+        let generated_file_path = get_colocated_ts_file_path(file_path);
+        let header = get_generated_file_header(&generated_file_path, &[file_path], types);
+        (generated_file_path, format!("{header}{types}"))
+      })
+      .collect();
+    return Ok(generated_files);
   }
 
-  let generate_path = generate_types_config.and_then(|x| x.generate_path);
-  if generate_path.is_none() {
-    return Ok(());
+  if types.is_empty() {
+    return Ok(vec![]);
   }
 
-  let mut target = generate_path.unwrap();
-  if target.is_dir() {
-    target = target.join("types.queries.ts");
+  let generated_file_path = get_single_ts_file_path()?;
+  let source_files = types.keys().collect::<Vec<_>>();
+  let types = types.values().cloned().collect::<Vec<_>>().join("\n");
+  let header = get_generated_file_header(&generated_file_path, &source_files, &types);
+  Ok(vec![(generated_file_path, format!("{header}{types}"))])
+}
+
+/// Writes the types generated for each source file, next to the source file or into the single file of --generate-path
+/// Files whose content has not changed are left untouched, so that they do not trigger rebuilds of file watchers
+pub fn write_generated_files(types: &BTreeMap<PathBuf, String>) -> Result<()> {
+  // The single file is removed once there are no types left to generate into it
+  if CLI_ARGS.generate_path.is_some() && types.is_empty() {
+    let generated_file_path = get_single_ts_file_path()?;
+    if generated_file_path.is_file() {
+      fs::remove_file(generated_file_path)?;
+    }
+    return Ok(());
   }
 
-  if target.exists() {
-    fs::remove_file(target)?
+  for (generated_file_path, content) in get_generated_files(types)? {
+    if fs::read_to_string(&generated_file_path).is_ok_and(|on_disk| on_disk == content) {
+      continue;
+    }
+
+    if let Some(parent_path) = generated_file_path.parent() {
+      fs::create_dir_all(parent_path)?;
+    }
+    fs::write(&generated_file_path, content)
+      .map_err(|err| eyre!("Failed to write to file {:?} - {}", generated_file_path, err))?;
   }
   Ok(())
 }
 
-pub async fn generate_ts_interface(sql: &SQL, db_conn: &DBConn) -> Result<TsQuery> {
-  // Use the appropriate SQL dialect based on the database type
-  let dialect: Box<dyn Dialect> = match db_conn.get_db_type() {
+/// Returns the SQL dialect used to parse queries of the database type
+pub fn get_sql_dialect(db_type: &DatabaseType) -> Box<dyn Dialect> {
+  match db_type {
     DatabaseType::Postgres => Box::new(PostgreSqlDialect {}),
     DatabaseType::Mysql => Box::new(MySqlDialect {}),
-  };
+    DatabaseType::Sqlite => Box::new(SQLiteDialect {}),
+  }
+}
+
+pub async fn generate_ts_interface(sql: &SQL, db_conn: &DBConn) -> Result<TsQuery> {
+  // Use the appropriate SQL dialect based on the database type
+  let dialect = get_sql_dialect(&db_conn.get_db_type());
 
   let sql_ast = Parser::parse_sql(&*dialect, &sql.query)?;
   let mut ts_query = TsQuery::new(get_query_name(sql)?);
@@ -2,10 +2,13 @@ use crate::common::errors::{DB_CONN_POOL_RETRIEVE_ERROR, DB_SCHEME_READ_ERROR};
 use crate::common::logger::*;
 use crate::core::connection::DBConn;
 use crate::core::mysql::pool::MySqlConnectionManager;
+use crate::core::offline::snapshot::{ColumnSnapshot, SchemaSnapshot, TableSnapshot};
 use crate::core::postgres::pool::PostgresConnectionManager;
+use crate::core::sqlite::pool::SqliteConnectionManager;
+use crate::ts_generator::sql_parser::quoted_strings::split_qualified_name;
 use bb8::Pool;
 use mysql_async::prelude::Queryable;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use tokio::sync::Mutex;
 
 use super::types::ts_query::TsFieldType;
@@ -21,6 +24,11 @@ pub type Fields = HashMap<String, Field>;
 pub struct DBSchema {
   // Holds cache details for table / columns of the target database
   tables_cache: HashMap<String, Fields>,
+  // Holds the raw column details of each table fetched, used to write the schema snapshot
+  columns_cache: HashMap<String, TableSnapshot>,
+  // Tables that were fetched since the last `record_snapshot` call
+  touched_tables: BTreeSet<String>,
+  snapshot: SchemaSnapshot,
 }
 
 impl Default for DBSchema {
@@ -33,6 +41,9 @@ impl DBSchema {
   pub fn new() -> DBSchema {
     DBSchema {
       tables_cache: HashMap::new(),
+      columns_cache: HashMap::new(),
+      touched_tables: BTreeSet::new(),
+      snapshot: SchemaSnapshot::default(),
     }
   }
 
@@ -43,7 +54,21 @@ impl DBSchema {
   ///
   /// # PostgreSQL Notes
   /// - PostgresSQL would utilise SEARCH_PATH option to search for the table in the database https://www.postgresql.org/docs/current/ddl-schemas.html#DDL-SCHEMAS-PATH
+  /// - A schema-qualified table such as `billing.invoices` is only looked up from its schema, an unqualified table is
+  ///   looked up from the first schema of the connection's search_path that contains it
+  ///
+  /// # SQLite Notes
+  /// - SQLite has no information_schema, columns are read from `PRAGMA table_info` of the table
+  ///
+  /// # Offline Notes
+  /// - Tables are read from the schema snapshot and nothing is cached, the snapshot is already in memory
   pub async fn fetch_table(&mut self, table_name: &Vec<&str>, conn: &DBConn) -> Option<Fields> {
+    if let DBConn::Offline(snapshot) = &conn {
+      return snapshot.fetch_table(table_name);
+    }
+
+    self.touched_tables.extend(table_name.iter().map(|x| x.to_string()));
+
     let table_key: String = table_name.join(",");
     let cached_table_result = self.tables_cache.get(table_key.as_str());
 
@@ -53,7 +78,9 @@ impl DBSchema {
 
     let result = match &conn {
       DBConn::MySQLPooledConn(conn) => Self::mysql_fetch_table(self, table_name, conn).await,
-      DBConn::PostgresConn(conn) => Self::postgres_fetch_table(self, &"public".to_string(), table_name, conn).await,
+      DBConn::PostgresConn(conn) => Self::postgres_fetch_table(self, table_name, conn).await,
+      DBConn::SqliteConn(conn) => Self::sqlite_fetch_table(self, table_name, conn).await,
+      DBConn::Offline(_) => unreachable!("offline connections are read from the snapshot"),
     };
 
     if let Some(result) = &result {
@@ -63,39 +90,31 @@ impl DBSchema {
     result
   }
 
+  /// Moves the tables fetched since the last call into the schema snapshot of the connection
+  pub fn record_snapshot(&mut self, connection: &str, conn: &DBConn) {
+    let tables: BTreeMap<String, TableSnapshot> = std::mem::take(&mut self.touched_tables)
+      .into_iter()
+      .filter_map(|table| self.columns_cache.get(&table).map(|columns| (table, columns.clone())))
+      .collect();
+
+    self.snapshot.add_tables(connection, conn.get_db_type(), tables);
+  }
+
+  pub fn snapshot(&self) -> &SchemaSnapshot {
+    &self.snapshot
+  }
+
   async fn postgres_fetch_table(
-    &self,
-    schema: &String,
+    &mut self,
     table_names: &Vec<&str>,
     conn: &Mutex<Pool<PostgresConnectionManager>>,
   ) -> Option<Fields> {
-    let table_names = table_names
+    // Each table is selected separately so the columns are reported under the exact name the query used
+    let query = table_names
       .iter()
-      .map(|x| format!("'{x}'"))
+      .map(|x| Self::postgres_table_columns_query(x))
       .collect::<Vec<_>>()
-      .join(",");
-
-    let query = format!(
-      r"
-        SELECT
-          COLUMN_NAME as column_name,
-          DATA_TYPE as data_type,
-          IS_NULLABLE as is_nulalble,
-          TABLE_NAME as table_name,
-          (
-            select string_agg(e.enumlabel, ',')
-          from pg_type t
-              join pg_enum e on t.oid = e.enumtypid
-              join pg_catalog.pg_namespace n ON n.oid = t.typnamespace
-          where n.nspname = '{schema}'
-          and t.typname = udt_name
-          group by n.nspname, t.typname
-          ) as enum_values
-      FROM information_schema.COLUMNS
-      WHERE TABLE_SCHEMA = '{schema}'
-      AND TABLE_NAME IN ({table_names});
-                "
-    );
+      .join(" UNION ALL ");
 
     let mut fields: HashMap<String, Field> = HashMap::new();
 
@@ -114,6 +133,15 @@ impl DBSchema {
           .ok()
           .map(|val: String| val.split(",").map(|x| x.to_string()).collect());
 
+        self.columns_cache.entry(table_name.to_owned()).or_default().insert(
+          field_name.to_owned(),
+          ColumnSnapshot {
+            data_type: field_type.to_owned(),
+            is_nullable: is_nullable == "YES",
+            enum_values: enum_values.to_owned(),
+          },
+        );
+
         let field = Field {
           field_type: TsFieldType::get_ts_field_type_from_postgres_field_type(
             field_type.to_owned(),
@@ -138,41 +166,61 @@ impl DBSchema {
     None
   }
 
+  /// Builds the query selecting columns of a single table, the table name column is the name used by the query
+  ///
+  /// current_schemas(false) is the search_path of the connection with the schemas that do not exist left out,
+  /// which is what Postgres itself walks to resolve an unqualified table name
+  fn postgres_table_columns_query(table_name: &str) -> String {
+    let (schema, table) = split_qualified_name(table_name);
+    let table_schema = match schema {
+      Some(schema) => format!("'{schema}'"),
+      None => format!(
+        r"(
+          SELECT search_path.schema_name
+          FROM unnest(current_schemas(false)) WITH ORDINALITY AS search_path(schema_name, position)
+          JOIN information_schema.TABLES tables
+            ON tables.TABLE_SCHEMA = search_path.schema_name
+            AND tables.TABLE_NAME = '{table}'
+          ORDER BY search_path.position
+          LIMIT 1
+        )"
+      ),
+    };
+
+    // Enum types are looked up from the schema the column's type is defined in, which is not always the table's schema
+    format!(
+      r"
+        SELECT
+          COLUMN_NAME as column_name,
+          DATA_TYPE as data_type,
+          IS_NULLABLE as is_nulalble,
+          '{table_name}' as table_name,
+          (
+            select string_agg(e.enumlabel, ',')
+          from pg_type t
+              join pg_enum e on t.oid = e.enumtypid
+              join pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+          where n.nspname = udt_schema
+          and t.typname = udt_name
+          group by n.nspname, t.typname
+          ) as enum_values
+      FROM information_schema.COLUMNS
+      WHERE TABLE_SCHEMA = {table_schema}
+      AND TABLE_NAME = '{table}'
+                "
+    )
+  }
+
   async fn mysql_fetch_table(
-    &self,
+    &mut self,
     table_names: &Vec<&str>,
     conn: &Mutex<Pool<MySqlConnectionManager>>,
   ) -> Option<Fields> {
-    let table_names = table_names
+    let query = table_names
       .iter()
-      .map(|x| format!("'{x}'"))
+      .map(|x| Self::mysql_table_columns_query(x))
       .collect::<Vec<_>>()
-      .join(",");
-    let query = format!(
-      r"
-        SELECT
-            COLUMN_NAME as column_name,
-            DATA_TYPE as data_type,
-            IS_NULLABLE as is_nulalble,
-            TABLE_NAME,
-            (
-              SELECT REPLACE(
-                  TRIM(TRAILING ')' FROM
-                  TRIM(LEADING '(' from
-                  TRIM(LEADING 'enum' FROM COLUMN_TYPE)))
-                , '\''
-                , ''
-              )
-              FROM information_schema.COLUMNS subcols
-              WHERE subcols.TABLE_SCHEMA = (SELECT DATABASE())
-                AND subcols.TABLE_NAME = C.TABLE_NAME
-                AND subcols.COLUMN_NAME = C.COLUMN_NAME
-            ) AS enums
-        FROM information_schema.COLUMNS C
-        WHERE TABLE_SCHEMA = (SELECT DATABASE())
-        AND TABLE_NAME IN ({table_names})
-                "
-    );
+      .join(" UNION ALL ");
 
     let mut fields: HashMap<String, Field> = HashMap::new();
     let conn = conn.lock().await;
@@ -193,6 +241,15 @@ impl DBSchema {
         } else {
           None
         };
+        self.columns_cache.entry(table_name.to_owned()).or_default().insert(
+          field_name.to_owned(),
+          ColumnSnapshot {
+            data_type: field_type.to_owned(),
+            is_nullable: is_nullable == "YES",
+            enum_values: enum_values.to_owned(),
+          },
+        );
+
         let field = Field {
           field_type: TsFieldType::get_ts_field_type_from_mysql_field_type(
             field_type.to_owned(),
@@ -210,4 +267,89 @@ impl DBSchema {
 
     None
   }
+
+  /// Builds the query selecting columns of a single table, the table name column is the name used by the query
+  /// A qualified table such as `other_db.items` is looked up from that database rather than the connected one
+  fn mysql_table_columns_query(table_name: &str) -> String {
+    let (schema, table) = split_qualified_name(table_name);
+    let table_schema = schema
+      .map(|schema| format!("'{schema}'"))
+      .unwrap_or("(SELECT DATABASE())".to_string());
+
+    format!(
+      r"
+        SELECT
+            COLUMN_NAME as column_name,
+            DATA_TYPE as data_type,
+            IS_NULLABLE as is_nulalble,
+            '{table_name}' as requested_table_name,
+            (
+              SELECT REPLACE(
+                  TRIM(TRAILING ')' FROM
+                  TRIM(LEADING '(' from
+                  TRIM(LEADING 'enum' FROM COLUMN_TYPE)))
+                , '\''
+                , ''
+              )
+              FROM information_schema.COLUMNS subcols
+              WHERE subcols.TABLE_SCHEMA = C.TABLE_SCHEMA
+                AND subcols.TABLE_NAME = C.TABLE_NAME
+                AND subcols.COLUMN_NAME = C.COLUMN_NAME
+            ) AS enums
+        FROM information_schema.COLUMNS C
+        WHERE TABLE_SCHEMA = {table_schema}
+        AND TABLE_NAME = '{table}'
+                "
+    )
+  }
+
+  async fn sqlite_fetch_table(
+    &mut self,
+    table_names: &Vec<&str>,
+    conn: &Mutex<Pool<SqliteConnectionManager>>,
+  ) -> Option<Fields> {
+    let mut fields: HashMap<String, Field> = HashMap::new();
+    let conn = conn.lock().await;
+    let conn = conn.get().await.expect(DB_CONN_POOL_RETRIEVE_ERROR);
+
+    for table_name in table_names {
+      // A qualified table such as `main.items` is looked up from the attached database of the same name
+      let query = match split_qualified_name(table_name) {
+        (Some(schema), table) => format!("PRAGMA \"{schema}\".table_info(\"{table}\")"),
+        (None, table) => format!("PRAGMA table_info(\"{table}\")"),
+      };
+
+      let mut statement = conn.prepare(&query).ok()?;
+      let columns = statement
+        .query_map([], |row| {
+          let field_name: String = row.get(1)?;
+          let field_type: String = row.get(2)?;
+          let not_null: bool = row.get(3)?;
+          let primary_key: i64 = row.get(5)?;
+          Ok((field_name, field_type.to_lowercase(), !not_null && primary_key == 0))
+        })
+        .ok()?;
+
+      for column in columns {
+        let (field_name, field_type, is_nullable) = column.expect(DB_SCHEME_READ_ERROR);
+
+        self.columns_cache.entry(table_name.to_string()).or_default().insert(
+          field_name.to_owned(),
+          ColumnSnapshot {
+            data_type: field_type.to_owned(),
+            is_nullable,
+            enum_values: None,
+          },
+        );
+
+        let field = Field {
+          field_type: TsFieldType::get_ts_field_type_from_sqlite_field_type(field_type),
+          is_nullable,
+        };
+        fields.insert(field_name, field);
+      }
+    }
+
+    Some(fields)
+  }
 }
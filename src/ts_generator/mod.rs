@@ -3,4 +3,5 @@ pub mod errors;
 pub mod generator;
 pub mod information_schema;
 pub mod sql_parser;
+pub mod stale;
 pub mod types;
@@ -191,9 +191,10 @@ pub async fn handle_json_agg_function(
       {
         let object_type = TsFieldType::StructuredObject(object_fields);
         let array_type = TsFieldType::Array(Box::new(object_type));
+        // Aggregating an empty set of rows evaluates to NULL rather than an empty array
         return ctx
           .ts_query
-          .insert_result(Some(ctx.alias), &[array_type], ctx.is_selection, false, expr_log);
+          .insert_result(Some(ctx.alias), &[array_type], ctx.is_selection, true, expr_log);
       }
     }
   }
@@ -1,11 +1,23 @@
-use crate::common::lazy::DB_SCHEMA;
 use crate::ts_generator::errors::TsGeneratorError;
 use crate::ts_generator::sql_parser::expressions::function_handlers::FunctionHandlersContext;
-use crate::ts_generator::sql_parser::expressions::translate_data_type::translate_value;
-use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::translate_table_from_expr;
-use crate::ts_generator::sql_parser::quoted_strings::DisplayIndent;
+use crate::ts_generator::sql_parser::expressions::functions::is_aggregate_nullable;
+use crate::ts_generator::sql_parser::expressions::translate_expr::translate_expr_types;
 use crate::ts_generator::types::ts_query::TsFieldType;
-use sqlparser::ast::{Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments};
+use sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, FunctionArguments};
+
+/// Whether the result of a type-polymorphic function can be null, given whether each of its arguments can be null
+fn is_polymorphic_function_nullable(function_name: &str, nullable_args: &[bool], has_rows: bool) -> bool {
+  match function_name.to_uppercase().as_str() {
+    "MIN" | "MAX" => is_aggregate_nullable(function_name, nullable_args.first().copied().unwrap_or(true), has_rows),
+    // The first argument that is not null is returned, so it's only null if all of them can be
+    "COALESCE" | "IFNULL" | "NVL" => nullable_args.iter().all(|is_nullable| *is_nullable),
+    // NULLIF(a, b) returns null when a equals b
+    "NULLIF" | "NTH_VALUE" => true,
+    // LAG / LEAD return the default (third argument) or null when the offset goes out of the window frame
+    "LAG" | "LEAD" => nullable_args.first().copied().unwrap_or(true) || nullable_args.get(2).copied().unwrap_or(true),
+    _ => nullable_args.first().copied().unwrap_or(true),
+  }
+}
 
 pub async fn handle_polymorphic_functions(
   func_obj: &Function,
@@ -13,85 +25,52 @@ pub async fn handle_polymorphic_functions(
 ) -> Result<(), TsGeneratorError> {
   let expr_log = ctx.expr_for_logging.unwrap_or("");
   // In sqlparser 0.59.0, args is a FunctionArguments enum
-  // Extract the first argument from the appropriate variant
-  let first_arg = match &func_obj.args {
-    FunctionArguments::List(arg_list) => arg_list.args.first(),
-    FunctionArguments::None => None,
-    FunctionArguments::Subquery(_) => None, // Can't infer type from subquery easily
+  let args = match &func_obj.args {
+    FunctionArguments::List(arg_list) => arg_list.args.iter().collect(),
+    FunctionArguments::None => vec![],
+    FunctionArguments::Subquery(_) => vec![], // Can't infer type from subquery easily
   };
 
-  if let Some(first_arg) = first_arg {
-    let first_expr = match first_arg {
-      FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Some(expr),
+  let mut value = None;
+  let mut nullable_args = vec![];
+  for arg in args {
+    let arg_expr = match arg {
+      FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => expr,
       FunctionArg::Named {
         arg: FunctionArgExpr::Expr(expr),
         ..
-      } => Some(expr),
-      _ => None,
+      } => expr,
+      _ => {
+        nullable_args.push(true);
+        continue;
+      }
     };
 
-    if let Some(arg_expr) = first_expr {
-      // Try to infer type from the first argument
-      match arg_expr {
-        Expr::Identifier(ident) => {
-          let column_name = DisplayIndent(ident).to_string();
-          if let Some(table_name) = ctx.single_table_name {
-            let table_details = &DB_SCHEMA
-              .lock()
-              .await
-              .fetch_table(&vec![table_name], ctx.db_conn)
-              .await;
+    let arg_types = translate_expr_types(
+      arg_expr,
+      ctx.single_table_name,
+      ctx.table_with_joins,
+      ctx.ts_query,
+      ctx.db_conn,
+    )
+    .await?;
+    nullable_args.push(arg_types.contains(&TsFieldType::Null));
 
-            if let Some(table_details) = table_details {
-              if let Some(field) = table_details.get(&column_name) {
-                return ctx.ts_query.insert_result(
-                  Some(ctx.alias),
-                  &[field.field_type.to_owned()],
-                  ctx.is_selection,
-                  false, // IFNULL/COALESCE removes nullability
-                  expr_log,
-                );
-              }
-            }
-          }
-        }
-        Expr::CompoundIdentifier(idents) if idents.len() == 2 => {
-          let column_name = DisplayIndent(&idents[1]).to_string();
-          if let Ok(table_name) = translate_table_from_expr(ctx.table_with_joins, arg_expr) {
-            let table_details = &DB_SCHEMA
-              .lock()
-              .await
-              .fetch_table(&vec![table_name.as_str()], ctx.db_conn)
-              .await;
-
-            if let Some(table_details) = table_details {
-              if let Some(field) = table_details.get(&column_name) {
-                return ctx.ts_query.insert_result(
-                  Some(ctx.alias),
-                  &[field.field_type.to_owned()],
-                  ctx.is_selection,
-                  false, // IFNULL/COALESCE removes nullability
-                  expr_log,
-                );
-              }
-            }
-          }
-        }
-        Expr::Value(val) => {
-          // If first arg is a literal value, infer from that
-          if let Some(ts_field_type) = translate_value(&val.value) {
-            return ctx
-              .ts_query
-              .insert_result(Some(ctx.alias), &[ts_field_type], ctx.is_selection, false, expr_log);
-          }
-        }
-        _ => {}
-      }
+    // The function returns the type of the first argument that we can infer the type of
+    let arg_types: Vec<TsFieldType> = arg_types.into_iter().filter(|t| *t != TsFieldType::Null).collect();
+    if value.is_none() && !arg_types.is_empty() && arg_types != [TsFieldType::Any] {
+      value = Some(arg_types);
     }
   }
 
-  // Fallback to Any if we couldn't infer the type
+  let function_name = func_obj.name.to_string();
+  // A window function is computed over a frame that has at least the current row
+  let has_rows = ctx.ts_query.has_group_by || func_obj.over.is_some();
+  let is_nullable =
+    value.is_some() && is_polymorphic_function_nullable(function_name.as_str(), &nullable_args, has_rows);
+  let value = value.unwrap_or(vec![TsFieldType::Any]);
+
   ctx
     .ts_query
-    .insert_result(Some(ctx.alias), &[TsFieldType::Any], ctx.is_selection, false, expr_log)
+    .insert_result(Some(ctx.alias), &value, ctx.is_selection, is_nullable, expr_log)
 }
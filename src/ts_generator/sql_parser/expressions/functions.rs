@@ -153,6 +153,43 @@ pub fn is_date_function(func_name: &str) -> bool {
   DATE_FUNCTIONS.contains(&func_name.to_uppercase().as_str())
 }
 
+// Aggregate functions that evaluate to NULL when there are no rows (or only NULL values) to aggregate
+pub static NULLABLE_AGGREGATE_FUNCTIONS: &[&str] = &[
+  "AVG",
+  "MAX",
+  "MIN",
+  "SUM",
+  "ARRAY_AGG",
+  "STRING_AGG",
+  "GROUP_CONCAT",
+  "BIT_AND",
+  "BIT_OR",
+  "BOOL_AND",
+  "BOOL_OR",
+  "EVERY",
+  "STDDEV",
+  "STDDEV_POP",
+  "STDDEV_SAMP",
+  "VARIANCE",
+  "VAR_POP",
+  "VAR_SAMP",
+];
+
+pub fn is_nullable_aggregate_function(func_name: &str) -> bool {
+  NULLABLE_AGGREGATE_FUNCTIONS.contains(&func_name.to_uppercase().as_str())
+}
+
+/// Whether a nullable aggregate function can evaluate to NULL, given whether its argument can be null and whether it
+/// is always computed over at least one row (a GROUP BY group or a window frame), rather than over a whole table that
+/// may be empty
+pub fn is_aggregate_nullable(func_name: &str, is_arg_nullable: bool, has_rows: bool) -> bool {
+  match func_name.to_uppercase().as_str() {
+    // Sample statistics evaluate to NULL over a single row
+    "STDDEV" | "STDDEV_SAMP" | "VARIANCE" | "VAR_SAMP" => true,
+    _ => is_arg_nullable || !has_rows,
+  }
+}
+
 // Type-polymorphic functions that return the type of their first argument
 pub static TYPE_POLYMORPHIC_FUNCTIONS: &[&str] = &[
   "IFNULL",
@@ -165,6 +202,9 @@ pub static TYPE_POLYMORPHIC_FUNCTIONS: &[&str] = &[
   "FIRST_VALUE",
   "LAST_VALUE",
   "NTH_VALUE",
+  // Aggregates that return the smallest or largest value of their argument
+  "MIN",
+  "MAX",
 ];
 
 pub fn is_type_polymorphic_function(func_name: &str) -> bool {
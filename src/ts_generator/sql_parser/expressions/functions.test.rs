@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
   use crate::ts_generator::sql_parser::expressions::functions::{
-    is_date_function, is_numeric_function, is_string_function, is_type_polymorphic_function,
+    is_aggregate_nullable, is_date_function, is_numeric_function, is_string_function, is_type_polymorphic_function,
   };
 
   #[test]
@@ -82,4 +82,29 @@ mod tests {
     assert!(is_type_polymorphic_function("NULLIF"));
     assert!(is_type_polymorphic_function("nullif"));
   }
+
+  #[test]
+  fn should_recognize_min_and_max_as_type_polymorphic() {
+    assert!(is_type_polymorphic_function("MIN"));
+    assert!(is_type_polymorphic_function("max"));
+  }
+
+  #[test]
+  fn should_make_aggregates_over_a_whole_table_nullable() {
+    assert!(is_aggregate_nullable("SUM", false, false));
+    assert!(is_aggregate_nullable("max", false, false));
+  }
+
+  #[test]
+  fn should_make_aggregates_over_rows_follow_their_argument() {
+    assert!(!is_aggregate_nullable("SUM", false, true));
+    assert!(!is_aggregate_nullable("MIN", false, true));
+    assert!(is_aggregate_nullable("AVG", true, true));
+  }
+
+  #[test]
+  fn should_make_sample_statistics_always_nullable() {
+    assert!(is_aggregate_nullable("STDDEV_SAMP", false, true));
+    assert!(is_aggregate_nullable("variance", false, true));
+  }
 }
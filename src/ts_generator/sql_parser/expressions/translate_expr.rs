@@ -1,7 +1,8 @@
 use super::function_handlers::json_functions::{handle_json_agg_function, handle_json_build_function};
 use super::function_handlers::polymorphic_functions::handle_polymorphic_functions;
 use super::functions::{
-  is_date_function, is_json_agg_function, is_json_build_function, is_numeric_function, is_type_polymorphic_function,
+  is_aggregate_nullable, is_date_function, is_json_agg_function, is_json_build_function,
+  is_nullable_aggregate_function, is_numeric_function, is_type_polymorphic_function,
 };
 use crate::common::lazy::DB_SCHEMA;
 use crate::common::logger::{error, warning};
@@ -9,17 +10,21 @@ use crate::core::connection::DBConn;
 use crate::ts_generator::errors::TsGeneratorError;
 use crate::ts_generator::sql_parser::expressions::function_handlers::FunctionHandlersContext;
 use crate::ts_generator::sql_parser::expressions::translate_data_type::translate_value;
-use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::translate_table_from_expr;
+use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::{
+  get_default_table_reference, translate_table_from_expr,
+};
 use crate::ts_generator::sql_parser::expressions::{
   functions::is_string_function, translate_data_type::translate_data_type,
 };
-use crate::ts_generator::sql_parser::quoted_strings::DisplayIndent;
+use crate::ts_generator::sql_parser::quoted_strings::{unqualified_name, DisplayIndent};
 use crate::ts_generator::sql_parser::translate_query::translate_query;
 use crate::ts_generator::types::ts_query::{TsFieldType, TsQuery};
 use async_recursion::async_recursion;
 use color_eyre::Result;
 use regex::Regex;
-use sqlparser::ast::{Assignment, Expr, TableWithJoins, Value};
+use sqlparser::ast::{
+  Assignment, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, TableWithJoins, Value,
+};
 use std::slice::from_ref;
 
 /// Given an expression
@@ -171,14 +176,9 @@ pub async fn get_sql_query_param(
         })?;
 
       // get column and return TsFieldType
-      let column = columns.get(column_name.as_str()).ok_or_else(|| {
-        let available_columns = columns.keys().map(|k| k.as_str()).collect::<Vec<_>>().join(", ");
-        TsGeneratorError::ColumnNotFoundInTable {
-          column: column_name.clone(),
-          table: table_name.clone(),
-          available_columns,
-        }
-      })?;
+      let column = columns
+        .get(column_name.as_str())
+        .ok_or_else(|| TsGeneratorError::column_not_found(&column_name, &table_name, columns.keys()))?;
 
       Ok(Some((
         column.field_type.to_owned(),
@@ -190,6 +190,111 @@ pub async fn get_sql_query_param(
   }
 }
 
+/// Returns the type a function evaluates to, based on the category of the function
+fn get_function_return_type(function_name: &str) -> TsFieldType {
+  if is_string_function(function_name) {
+    TsFieldType::String
+  } else if is_numeric_function(function_name) {
+    TsFieldType::Number
+  } else if is_date_function(function_name) {
+    TsFieldType::String
+  } else {
+    TsFieldType::Any
+  }
+}
+
+/// Walks the arguments of a function to pick up any placeholders
+/// e.g. `lower($1)` or `date_trunc('day', created_at) = $1`
+async fn translate_function_args(
+  func_obj: &Function,
+  single_table_name: &Option<&str>,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  ts_query: &mut TsQuery,
+  db_conn: &DBConn,
+) -> Result<(), TsGeneratorError> {
+  let FunctionArguments::List(arg_list) = &func_obj.args else {
+    return Ok(());
+  };
+
+  for arg in &arg_list.args {
+    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(arg_expr))
+    | FunctionArg::Named {
+      arg: FunctionArgExpr::Expr(arg_expr),
+      ..
+    } = arg
+    {
+      if let Some(placeholder) = get_expr_placeholder(arg_expr) {
+        // We cannot tell what the function expects from its arguments
+        ts_query.insert_param(&TsFieldType::Any, &false, &Some(placeholder))?;
+      } else if !matches!(arg_expr, Expr::Identifier(_) | Expr::CompoundIdentifier(_)) {
+        translate_expr(
+          arg_expr,
+          single_table_name,
+          table_with_joins,
+          None,
+          ts_query,
+          db_conn,
+          false,
+        )
+        .await?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Translates an expression on its own to find out the types it evaluates to, including `TsFieldType::Null` if it can
+/// be null. Placeholders within the expression are still picked up by the query
+#[async_recursion]
+pub async fn translate_expr_types(
+  expr: &Expr,
+  single_table_name: &Option<&'async_recursion str>,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  ts_query: &mut TsQuery,
+  db_conn: &DBConn,
+) -> Result<Vec<TsFieldType>, TsGeneratorError> {
+  // A column cannot be resolved without a table in scope
+  if single_table_name.is_none() && matches!(expr, Expr::Identifier(_)) {
+    return Ok(vec![TsFieldType::Any]);
+  }
+
+  let mut expr_query = ts_query.new_branch();
+  expr_query.annotated_results.clear();
+  translate_expr(
+    expr,
+    single_table_name,
+    table_with_joins,
+    Some("expr"),
+    &mut expr_query,
+    db_conn,
+    true,
+  )
+  .await?;
+
+  let types = std::mem::take(&mut expr_query.result).into_values().next();
+  ts_query.merge_branch(expr_query, false);
+  Ok(types.unwrap_or(vec![TsFieldType::Any]))
+}
+
+/// Combines types of expressions that a single field may evaluate to (e.g. branches of CASE) into the field's type
+/// and whether it is nullable. Any of the branches being `any` makes the whole field `any`, which already covers null
+pub fn union_types(branch_types: Vec<Vec<TsFieldType>>) -> (Vec<TsFieldType>, bool) {
+  let mut value = vec![];
+  let mut is_nullable = false;
+  for ts_type in branch_types.into_iter().flatten() {
+    if ts_type == TsFieldType::Null {
+      is_nullable = true;
+    } else if !value.contains(&ts_type) {
+      value.push(ts_type);
+    }
+  }
+
+  if value.is_empty() || value.contains(&TsFieldType::Any) {
+    return (vec![TsFieldType::Any], false);
+  }
+  (value, is_nullable)
+}
+
 #[async_recursion]
 pub async fn translate_expr(
   expr: &Expr,
@@ -209,16 +314,19 @@ pub async fn translate_expr(
     Expr::Identifier(ident) => {
       let column_name = DisplayIndent(ident).to_string();
       let table_name = single_table_name.expect("Missing table name for identifier");
+      let table_reference = get_default_table_reference(table_name, table_with_joins);
 
       // First check if this is a table-valued function column
       if let Some(tvf_columns) = ts_query.table_valued_function_columns.get(table_name) {
         if let Some(ts_type) = tvf_columns.get(&column_name) {
           let field_name = alias.unwrap_or(column_name.as_str());
+          // Table-valued function columns are not nullable by default
+          let is_nullable = ts_query.is_column_nullable(&table_reference, &column_name, false);
           ts_query.insert_result(
             Some(field_name),
             &[ts_type.to_owned()],
             is_selection,
-            false, // Table-valued function columns are not nullable by default
+            is_nullable,
             expr_for_logging,
           )?;
           return Ok(());
@@ -231,13 +339,21 @@ pub async fn translate_expr(
       if let Some(table_details) = table_details {
         if let Some(field) = table_details.get(&column_name) {
           let field_name = alias.unwrap_or(column_name.as_str());
+          let is_nullable = ts_query.is_column_nullable(&table_reference, &column_name, field.is_nullable);
           ts_query.insert_result(
             Some(field_name),
             &[field.field_type.to_owned()],
             is_selection,
-            field.is_nullable,
+            is_nullable,
             expr_for_logging,
           )?
+        } else if db_conn.is_offline() {
+          // There is no database to PREPARE the query against, so a missing column must fail here
+          return Err(TsGeneratorError::column_not_found(
+            &column_name,
+            table_name,
+            table_details.keys(),
+          ));
         } else {
           error!(
             "Column '{}' not found in table '{}'. If '{}' is a table-valued function, verify that the column is defined in its alias. Otherwise, the column may not exist in the table.",
@@ -255,6 +371,7 @@ pub async fn translate_expr(
     Expr::CompoundIdentifier(idents) => {
       if idents.len() == 2 {
         let ident = DisplayIndent(&idents[1]).to_string();
+        let table_reference = DisplayIndent(&idents[0]).to_string();
 
         let table_name = translate_table_from_expr(table_with_joins, expr)?;
 
@@ -262,7 +379,7 @@ pub async fn translate_expr(
         if let Some(tvf_columns) = ts_query.table_valued_function_columns.get(&table_name) {
           if let Some(ts_type) = tvf_columns.get(&ident) {
             // if the select item is a compound identifier and does not has an alias, we should use `table_name.ident` as the key name
-            let key_name = format!("{table_name}_{ident}");
+            let key_name = format!("{}_{ident}", unqualified_name(&table_name));
             let key_name = &alias.unwrap_or_else(|| {
                           warning!(
                               "Missing an alias for a compound identifier, using {} as the key name. Prefer adding an alias for example: `{} AS {}`",
@@ -271,11 +388,13 @@ pub async fn translate_expr(
                           key_name.as_str()
                       });
 
+            // Table-valued function columns are not nullable by default
+            let is_nullable = ts_query.is_column_nullable(&table_reference, &ident, false);
             ts_query.insert_result(
               Some(key_name),
               &[ts_type.to_owned()],
               is_selection,
-              false, // Table-valued function columns are not nullable by default
+              is_nullable,
               expr_for_logging,
             )?;
             return Ok(());
@@ -292,7 +411,7 @@ pub async fn translate_expr(
         if let Some(table_details) = table_details {
           if let Some(field) = table_details.get(&ident) {
             // if the select item is a compound identifier and does not has an alias, we should use `table_name.ident` as the key name
-            let key_name = format!("{table_name}_{ident}");
+            let key_name = format!("{}_{ident}", unqualified_name(&table_name));
             let key_name = &alias.unwrap_or_else(|| {
                           warning!(
                               "Missing an alias for a compound identifier, using {} as the key name. Prefer adding an alias for example: `{} AS {}`",
@@ -301,13 +420,20 @@ pub async fn translate_expr(
                           key_name.as_str()
                       });
 
+            let is_nullable = ts_query.is_column_nullable(&table_reference, &ident, field.is_nullable);
             ts_query.insert_result(
               Some(key_name),
               &[field.field_type.to_owned()],
               is_selection,
-              field.is_nullable,
+              is_nullable,
               expr_for_logging,
             )?;
+          } else if db_conn.is_offline() {
+            return Err(TsGeneratorError::column_not_found(
+              &ident,
+              &table_name,
+              table_details.keys(),
+            ));
           } else {
             error!(
               "Column '{}' not found in table '{}' for compound identifier '{}.{}'. This may be a table-valued function.",
@@ -339,6 +465,25 @@ pub async fn translate_expr(
       if let Some((value, is_nullable, index)) = param {
         let _ = ts_query.insert_param(&value, &is_nullable, &index);
         Ok(())
+      } else if let (Expr::Function(func_obj), Some(placeholder)) = (left.as_ref(), get_expr_placeholder(right)) {
+        // Comparing a function against a placeholder e.g. HAVING COUNT(*) > $1
+        // the placeholder takes the return type of the function
+        translate_expr(
+          left,
+          single_table_name,
+          table_with_joins,
+          alias,
+          ts_query,
+          db_conn,
+          is_selection,
+        )
+        .await?;
+        let function_name = func_obj.name.to_string();
+        ts_query.insert_param(
+          &get_function_return_type(function_name.as_str()),
+          &false,
+          &Some(placeholder),
+        )
       } else {
         translate_expr(
           left,
@@ -584,12 +729,83 @@ pub async fn translate_expr(
     // Note: AggregateExpressionWithFilter was removed in sqlparser 0.59.0
     // Aggregate functions with filters are now part of the Function variant
     Expr::Case {
-      operand: _,
-      conditions: _,
-      else_result: _,
+      operand,
+      conditions,
+      else_result,
       case_token: _,
       end_token: _,
-    } => ts_query.insert_result(alias, &[TsFieldType::Any], is_selection, false, expr_for_logging),
+    } => {
+      // Placeholders are picked up in the order they appear, e.g. CASE $1 WHEN $2 THEN $3 ELSE $4 END
+      if let Some(operand) = operand {
+        if !matches!(operand.as_ref(), Expr::Identifier(_) | Expr::CompoundIdentifier(_)) {
+          translate_expr(
+            operand,
+            single_table_name,
+            table_with_joins,
+            None,
+            ts_query,
+            db_conn,
+            false,
+          )
+          .await?;
+        }
+      }
+
+      let mut branch_types = vec![];
+      for case_when in conditions {
+        let condition_param = match operand {
+          // WHEN of a simple CASE is compared against the operand, e.g. CASE rarity WHEN $1 THEN ...
+          Some(operand) => {
+            get_sql_query_param(
+              operand,
+              &case_when.condition,
+              single_table_name,
+              table_with_joins,
+              db_conn,
+              &ts_query.table_valued_function_columns,
+            )
+            .await?
+          }
+          None => None,
+        };
+        match condition_param {
+          Some((value, is_nullable, placeholder)) => ts_query.insert_param(&value, &is_nullable, &placeholder)?,
+          None => {
+            translate_expr(
+              &case_when.condition,
+              single_table_name,
+              table_with_joins,
+              None,
+              ts_query,
+              db_conn,
+              false,
+            )
+            .await?
+          }
+        }
+
+        branch_types.push(
+          translate_expr_types(
+            &case_when.result,
+            single_table_name,
+            table_with_joins,
+            ts_query,
+            db_conn,
+          )
+          .await?,
+        );
+      }
+
+      match else_result {
+        Some(else_result) => branch_types
+          .push(translate_expr_types(else_result, single_table_name, table_with_joins, ts_query, db_conn).await?),
+        // CASE without ELSE evaluates to NULL when none of the conditions match
+        None => branch_types.push(vec![TsFieldType::Null]),
+      }
+
+      let (value, is_nullable) = union_types(branch_types);
+      ts_query.insert_result(alias, &value, is_selection, is_nullable, expr_for_logging)
+    }
     Expr::Exists { subquery, negated: _ } => {
       ts_query.insert_result(alias, &[TsFieldType::Boolean], is_selection, false, expr_for_logging)?;
       translate_query(ts_query, &None, subquery, db_conn, alias, false).await
@@ -629,6 +845,13 @@ pub async fn translate_expr(
     Expr::Function(func_obj) => {
       let function_name = func_obj.name.to_string();
       let function_name_str = function_name.as_str();
+
+      // Functions outside of the select list (e.g. WHERE, HAVING, ORDER BY) do not produce any result field
+      // so we only need to pick up placeholders from their arguments
+      if !is_selection && alias.is_none() {
+        return translate_function_args(func_obj, single_table_name, table_with_joins, ts_query, db_conn).await;
+      }
+
       let alias = alias.ok_or(TsGeneratorError::FunctionWithoutAliasInSelectClause(expr.to_string()))?;
 
       // Handle type-polymorphic functions (IFNULL, COALESCE, etc.)
@@ -698,35 +921,30 @@ pub async fn translate_expr(
       }
 
       // Handle other function types
-      if is_string_function(function_name_str) {
-        ts_query.insert_result(
-          Some(alias),
-          &[TsFieldType::String],
-          is_selection,
-          false,
-          expr_for_logging,
-        )?;
-      } else if is_numeric_function(function_name_str) {
-        ts_query.insert_result(
-          Some(alias),
-          &[TsFieldType::Number],
-          is_selection,
-          false,
-          expr_for_logging,
-        )?;
-      } else if is_date_function(function_name_str) {
-        ts_query.insert_result(
-          Some(alias),
-          &[TsFieldType::String],
-          is_selection,
-          false,
-          expr_for_logging,
-        )?;
+      // Aggregates such as SUM evaluate to NULL over an empty set of rows, or when their argument is only ever NULL
+      let return_type = get_function_return_type(function_name_str);
+      let is_nullable = if is_nullable_aggregate_function(function_name_str) && return_type != TsFieldType::Any {
+        let arg_expr = match &func_obj.args {
+          FunctionArguments::List(arg_list) => arg_list.args.first(),
+          _ => None,
+        };
+        let is_arg_nullable = match arg_expr {
+          Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(arg_expr))) => {
+            // The argument is only looked at for its nullability, placeholders within it are not picked up here
+            let mut arg_query = ts_query.new_branch();
+            translate_expr_types(arg_expr, single_table_name, table_with_joins, &mut arg_query, db_conn)
+              .await?
+              .contains(&TsFieldType::Null)
+          }
+          _ => false,
+        };
+        // A window function is computed over a frame that has at least the current row
+        let has_rows = ts_query.has_group_by || func_obj.over.is_some();
+        is_aggregate_nullable(function_name_str, is_arg_nullable, has_rows)
       } else {
-        ts_query.insert_result(Some(alias), &[TsFieldType::Any], is_selection, false, expr_for_logging)?;
-      }
-
-      Ok(())
+        false
+      };
+      ts_query.insert_result(Some(alias), &[return_type], is_selection, is_nullable, expr_for_logging)
     }
     /////////////////////
     // FUNCTIONS END //
@@ -786,18 +1004,9 @@ pub async fn translate_assignment(
       }
     })?;
 
-    let field = table_details.get(&column_name).ok_or_else(|| {
-      let available_columns = table_details
-        .keys()
-        .map(|k| k.as_str())
-        .collect::<Vec<_>>()
-        .join(", ");
-      TsGeneratorError::ColumnNotFoundInTable {
-        column: column_name.clone(),
-        table: table_name.to_string(),
-        available_columns,
-      }
-    })?;
+    let field = table_details
+      .get(&column_name)
+      .ok_or_else(|| TsGeneratorError::column_not_found(&column_name, table_name, table_details.keys()))?;
 
     let _ = ts_query.insert_param(&field.field_type, &field.is_nullable, &value);
   }
@@ -1,7 +1,10 @@
 use crate::ts_generator::errors::TsGeneratorError;
 use crate::ts_generator::sql_parser::quoted_strings::*;
 use color_eyre::eyre::Result;
-use sqlparser::ast::{Assignment, AssignmentTarget, Expr, Join, SelectItem, TableFactor, TableWithJoins};
+use sqlparser::ast::{Assignment, AssignmentTarget, Expr, Join, JoinOperator, SelectItem, TableFactor, TableWithJoins};
+use std::collections::HashSet;
+use std::iter::once;
+use std::slice::from_ref;
 
 /// Check if the given table name corresponds to a table-valued function alias
 /// by examining the table_with_joins to see if it's a TableFactor::Function
@@ -25,37 +28,136 @@ pub fn is_table_function(table_name: &str, table_with_joins: &[TableWithJoins])
   false
 }
 
-pub fn get_default_table(table_with_joins: &[TableWithJoins]) -> String {
-  table_with_joins
-    .first()
-    .and_then(|x| match &x.relation {
-      TableFactor::Table {
-        name,
-        alias,
-        args,
-        with_hints: _,
-        version: _,
-        partitions: _,
-        with_ordinality: _,
-        json_path: _,
-        sample: _,
-        index_hints: _,
-      } => {
-        // If args is Some, it's a table-valued function (e.g., jsonb_to_recordset($1))
-        // In that case, use the alias name if available
-        if args.is_some() {
-          alias.as_ref().map(|a| DisplayTableAlias(a).to_string())
-        } else {
-          Some(DisplayObjectName(name).to_string())
+/// Returns the name a relation is referred to by within the query, which is its alias if it has one
+/// An unaliased schema-qualified table such as `billing.invoices` is referred to as `invoices`
+pub fn get_relation_reference(relation: &TableFactor) -> Option<String> {
+  match relation {
+    TableFactor::Table { alias: Some(alias), .. }
+    | TableFactor::Function { alias: Some(alias), .. }
+    | TableFactor::Derived { alias: Some(alias), .. } => Some(DisplayTableAlias(alias).to_string()),
+    TableFactor::Table { name, .. } => Some(unqualified_name(&DisplayObjectName(name).to_string()).to_string()),
+    _ => None,
+  }
+}
+
+/// Returns references of the relations whose columns can be null because they are on the nullable side of an outer join
+///
+/// e.g. `FROM characters LEFT JOIN guilds ON ...` makes columns of `guilds` nullable, `RIGHT JOIN` makes columns of
+/// every relation before the join nullable and `FULL JOIN` does both
+pub fn get_nullable_relations(table_with_joins: &[TableWithJoins]) -> HashSet<String> {
+  let mut nullable_relations = HashSet::new();
+
+  for twj in table_with_joins {
+    let mut preceding_relations: Vec<String> = get_relation_reference(&twj.relation).into_iter().collect();
+
+    for join in &twj.joins {
+      let reference = get_relation_reference(&join.relation);
+      let (left_nullable, right_nullable) = match join.join_operator {
+        JoinOperator::Left(_) | JoinOperator::LeftOuter(_) | JoinOperator::OuterApply => (false, true),
+        JoinOperator::Right(_) | JoinOperator::RightOuter(_) => (true, false),
+        JoinOperator::FullOuter(_) => (true, true),
+        _ => (false, false),
+      };
+
+      if left_nullable {
+        nullable_relations.extend(preceding_relations.iter().cloned());
+      }
+      if let Some(reference) = reference {
+        if right_nullable {
+          nullable_relations.insert(reference.to_owned());
         }
+        preceding_relations.push(reference);
       }
-      TableFactor::Function { alias, .. } => {
-        // For LATERAL functions, use the alias name as the table name
-        alias.as_ref().map(|a| DisplayTableAlias(a).to_string())
+    }
+  }
+
+  nullable_relations
+}
+
+/// Returns the reference of the relation that a qualifier such as `c`, `characters` or `public.characters` refers to,
+/// matching it against aliases and table names the same way as `find_table_name_from_identifier`
+pub fn find_relation_reference(table_with_joins: &[TableWithJoins], qualifier: &str) -> Option<String> {
+  get_relations(table_with_joins)
+    .find(|relation| match relation {
+      TableFactor::Table { name, alias, args, .. } => {
+        let alias = alias.as_ref().map(|alias| DisplayTableAlias(alias).to_string());
+        alias.as_deref() == Some(qualifier)
+          || (args.is_none() && is_table_reference(qualifier, &DisplayObjectName(name).to_string()))
+      }
+      TableFactor::Function { alias: Some(alias), .. } | TableFactor::Derived { alias: Some(alias), .. } => {
+        DisplayTableAlias(alias).to_string() == qualifier
+      }
+      _ => false,
+    })
+    .and_then(get_relation_reference)
+}
+
+/// Returns the name and the reference of every table in the FROM clause and its joins, the default table first
+pub fn get_table_relations(table_with_joins: &[TableWithJoins]) -> Vec<(String, String)> {
+  get_relations(table_with_joins)
+    .filter_map(|relation| match relation {
+      TableFactor::Table { name, args: None, .. } => {
+        get_relation_reference(relation).map(|reference| (DisplayObjectName(name).to_string(), reference))
       }
       _ => None,
     })
-    .expect("The query does not have a default table, impossible to generate types")
+    .collect()
+}
+
+fn get_relations(table_with_joins: &[TableWithJoins]) -> impl Iterator<Item = &TableFactor> {
+  table_with_joins
+    .iter()
+    .flat_map(|twj| once(&twj.relation).chain(twj.joins.iter().map(|join| &join.relation)))
+}
+
+/// Returns the reference of the table that unqualified columns of `table_name` are looked up from
+/// It's the alias of the default table if it has one, e.g. `c` for `SELECT name FROM characters c`
+pub fn get_default_table_reference(table_name: &str, table_with_joins: &Option<Vec<TableWithJoins>>) -> String {
+  table_with_joins
+    .as_ref()
+    .and_then(|twj| twj.first())
+    .filter(|twj| find_default_table(from_ref(*twj)).as_deref() == Some(table_name))
+    .and_then(|twj| get_relation_reference(&twj.relation))
+    .unwrap_or_else(|| table_name.to_string())
+}
+
+pub fn get_default_table(table_with_joins: &[TableWithJoins]) -> String {
+  find_default_table(table_with_joins).expect("The query does not have a default table, impossible to generate types")
+}
+
+fn find_default_table(table_with_joins: &[TableWithJoins]) -> Option<String> {
+  table_with_joins.first().and_then(|x| match &x.relation {
+    TableFactor::Table {
+      name,
+      alias,
+      args,
+      with_hints: _,
+      version: _,
+      partitions: _,
+      with_ordinality: _,
+      json_path: _,
+      sample: _,
+      index_hints: _,
+    } => {
+      // If args is Some, it's a table-valued function (e.g., jsonb_to_recordset($1))
+      // In that case, use the alias name if available
+      if args.is_some() {
+        alias.as_ref().map(|a| DisplayTableAlias(a).to_string())
+      } else {
+        Some(DisplayObjectName(name).to_string())
+      }
+    }
+    TableFactor::Function { alias, .. } => {
+      // For LATERAL functions, use the alias name as the table name
+      alias.as_ref().map(|a| DisplayTableAlias(a).to_string())
+    }
+    _ => None,
+  })
+}
+
+/// Whether the identifier refers to the table, either by its qualified name or by its name without the schema
+fn is_table_reference(identifier: &str, table_name: &str) -> bool {
+  identifier == table_name || identifier == unqualified_name(table_name)
 }
 
 pub fn find_table_name_from_identifier(
@@ -96,7 +198,7 @@ pub fn find_table_name_from_identifier(
           }
         } else {
           // Regular table
-          if Some(left.to_string()) == alias_str || left == name_str {
+          if Some(left.to_string()) == alias_str || is_table_reference(&left, &name_str) {
             return Ok(name_str.to_owned());
           }
         }
@@ -138,7 +240,7 @@ pub fn find_table_name_from_identifier(
       } => {
         let alias = alias.clone().map(|alias| DisplayTableAlias(&alias).to_string());
         let name = DisplayObjectName(name).to_string();
-        if Some(left.to_owned()) == alias || left == name {
+        if Some(left.to_owned()) == alias || is_table_reference(&left, &name) {
           return Ok(name);
         }
       }
@@ -159,7 +261,7 @@ pub fn find_table_name_from_identifier(
     }
   }
   // if the identifier of a compound identifier is exactly same as the default table name, we just return it
-  if left == default_table_name || right.is_none() {
+  if is_table_reference(&left, &default_table_name) || right.is_none() {
     // If right is none, it means we cannot further assume and try to find the table name
     // we should simply return the default table name
     return Ok(default_table_name);
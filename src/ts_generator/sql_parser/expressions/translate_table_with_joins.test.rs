@@ -6,7 +6,9 @@ mod tests {
     parser::Parser,
   };
 
-  use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::translate_table_with_joins;
+  use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::{
+    find_relation_reference, get_nullable_relations, translate_table_with_joins,
+  };
 
   #[test]
   fn should_select_default_for_unnamed_expr() {
@@ -106,4 +108,134 @@ mod tests {
       }
     }
   }
+
+  #[test]
+  fn should_select_schema_qualified_table_for_unqualified_reference() {
+    let sql = "
+            SELECT invoices.id, users.email
+            FROM billing.invoices
+            JOIN auth.users ON users.id = invoices.user_id
+        ";
+
+    let dialect = GenericDialect {};
+
+    let sql_ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let stmt = sql_ast[0].clone();
+    if let Statement::Query(query) = stmt {
+      let body = *query.body;
+      if let SetExpr::Select(select) = body {
+        let table_with_joins = Some(select.from);
+
+        let invoices = translate_table_with_joins(&table_with_joins, &select.projection[0]);
+        let users = translate_table_with_joins(&table_with_joins, &select.projection[1]);
+
+        assert_eq!("billing.invoices".to_string(), invoices.unwrap());
+        assert_eq!("auth.users".to_string(), users.unwrap());
+      }
+    }
+  }
+
+  #[test]
+  fn should_find_relations_on_nullable_side_of_outer_joins() {
+    let sql = "
+            SELECT c.id
+            FROM characters c
+            JOIN races ON races.id = c.race_id
+            LEFT JOIN guilds g ON g.id = c.guild_id
+            RIGHT JOIN classes ON classes.id = c.class_id
+            FULL OUTER JOIN items ON items.id = c.id
+        ";
+
+    let dialect = GenericDialect {};
+
+    let sql_ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let stmt = sql_ast[0].clone();
+    if let Statement::Query(query) = stmt {
+      let body = *query.body;
+      if let SetExpr::Select(select) = body {
+        let mut result = get_nullable_relations(&select.from)
+          .into_iter()
+          .collect::<Vec<String>>();
+        result.sort();
+
+        assert_eq!(vec!["c", "classes", "g", "items", "races"], result)
+      }
+    }
+  }
+
+  #[test]
+  fn should_not_find_nullable_relations_without_outer_joins() {
+    let sql = "
+            SELECT c.id
+            FROM characters c
+            JOIN races ON races.id = c.race_id
+            CROSS JOIN guilds
+        ";
+
+    let dialect = GenericDialect {};
+
+    let sql_ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let stmt = sql_ast[0].clone();
+    if let Statement::Query(query) = stmt {
+      let body = *query.body;
+      if let SetExpr::Select(select) = body {
+        assert!(get_nullable_relations(&select.from).is_empty())
+      }
+    }
+  }
+
+  #[test]
+  fn should_find_unaliased_schema_qualified_relations_by_table_name() {
+    let sql = "
+            SELECT invoices.id
+            FROM billing.invoices
+            LEFT JOIN auth.users ON users.id = invoices.user_id
+        ";
+
+    let dialect = GenericDialect {};
+
+    let sql_ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let stmt = sql_ast[0].clone();
+    if let Statement::Query(query) = stmt {
+      let body = *query.body;
+      if let SetExpr::Select(select) = body {
+        let result = get_nullable_relations(&select.from)
+          .into_iter()
+          .collect::<Vec<String>>();
+
+        assert_eq!(vec!["users"], result)
+      }
+    }
+  }
+
+  #[test]
+  fn should_find_relation_reference_from_alias_or_table_name() {
+    let sql = "
+            SELECT c.id
+            FROM characters c
+            LEFT JOIN public.guilds ON guilds.id = c.guild_id
+        ";
+
+    let dialect = GenericDialect {};
+
+    let sql_ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let stmt = sql_ast[0].clone();
+    if let Statement::Query(query) = stmt {
+      let body = *query.body;
+      if let SetExpr::Select(select) = body {
+        let table_with_joins = select.from;
+
+        assert_eq!(Some("c".to_string()), find_relation_reference(&table_with_joins, "c"));
+        assert_eq!(
+          Some("guilds".to_string()),
+          find_relation_reference(&table_with_joins, "guilds")
+        );
+        assert_eq!(
+          Some("guilds".to_string()),
+          find_relation_reference(&table_with_joins, "public.guilds")
+        );
+        assert_eq!(None, find_relation_reference(&table_with_joins, "races"));
+      }
+    }
+  }
 }
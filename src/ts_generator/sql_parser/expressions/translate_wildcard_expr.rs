@@ -2,6 +2,7 @@ use crate::common::lazy::DB_SCHEMA;
 use crate::common::logger::warning;
 use crate::core::connection::DBConn;
 use crate::ts_generator::errors::TsGeneratorError;
+use crate::ts_generator::sql_parser::expressions::translate_table_with_joins::get_relation_reference;
 use crate::ts_generator::sql_parser::quoted_strings::DisplayObjectName;
 use crate::ts_generator::types::ts_query::TsFieldType;
 use crate::ts_generator::types::ts_query::TsQuery;
@@ -81,18 +82,31 @@ pub async fn translate_wildcard_expr(
     warning!("Impossible to calculate appropriate field names of a wildcard query with multiple tables. Please use explicit field names instead. Query: {}", select.to_string());
   }
 
-  let table_refs = table_names.iter().map(|s| s.as_ref()).collect();
-  let all_fields = DB_SCHEMA.lock().await.fetch_table(&table_refs, db_conn).await;
+  // Columns of tables on the nullable side of an outer join are nullable, the tables are referred to by their aliases
+  let table_references = select
+    .from
+    .first()
+    .into_iter()
+    .flat_map(|twj| std::iter::once(&twj.relation).chain(twj.joins.iter().map(|join| &join.relation)))
+    .filter_map(get_relation_reference)
+    .collect::<Vec<String>>();
 
-  if let Some(all_fields) = all_fields {
-    for key in all_fields.keys() {
-      let field = all_fields.get(key).unwrap();
-      let mut field_types = vec![field.field_type.clone()];
-      if field.is_nullable {
-        field_types.push(TsFieldType::Null);
-      }
+  for (table_name, table_reference) in table_names.iter().zip(&table_references) {
+    let fields = DB_SCHEMA
+      .lock()
+      .await
+      .fetch_table(&vec![table_name.as_str()], db_conn)
+      .await;
 
-      ts_query.result.insert(key.to_owned(), field_types);
+    if let Some(fields) = fields {
+      for (key, field) in fields {
+        let mut field_types = vec![field.field_type.clone()];
+        if ts_query.is_column_nullable(table_reference, &key, field.is_nullable) {
+          field_types.push(TsFieldType::Null);
+        }
+
+        ts_query.result.insert(key, field_types);
+      }
     }
   }
   Ok(())
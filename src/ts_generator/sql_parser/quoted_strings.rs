@@ -29,24 +29,42 @@ pub struct DisplayObjectName<'a>(pub &'a ObjectName);
 impl fmt::Display for DisplayObjectName<'_> {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     // In sqlparser 0.59.0, ObjectName contains Vec<ObjectNamePart> instead of Vec<Ident>
-    // For qualified table names (e.g., database.schema.table), we want the last identifier (table name)
-    let last_part = self.0 .0.last().expect(
+    // For qualified table names (e.g., database.schema.table), we keep the schema and the table name
+    // so the table can be looked up from the exact schema it is qualified with
+    let parts = &self.0 .0;
+    let last_part = parts.last().expect(
       "ObjectName must contain at least one part (sqlparser invariant).\
        If you're seeing this, it's a bug in sqlparser or the SQL parsing logic.",
     );
 
-    if let Some(ident) = last_part.as_ident() {
-      let quote_style = &ident.quote_style;
-      let name = &ident.value;
-      let name = trim_table_name(name, quote_style);
-      write!(f, "{name}")
-    } else {
+    let Some(ident) = last_part.as_ident() else {
       // Fallback: if it's a function-based name, use the default Display implementation
-      write!(f, "{}", last_part)
+      return write!(f, "{}", last_part);
+    };
+    let name = trim_table_name(&ident.value, &ident.quote_style);
+
+    let schema = parts.len().checked_sub(2).and_then(|i| parts[i].as_ident());
+    match schema {
+      Some(schema) => write!(f, "{}.{name}", trim_table_name(&schema.value, &schema.quote_style)),
+      None => write!(f, "{name}"),
     }
   }
 }
 
+/// Splits a table name produced by `DisplayObjectName` into its schema qualifier and the table name
+/// e.g. `billing.invoices` becomes `(Some("billing"), "invoices")` and `invoices` becomes `(None, "invoices")`
+pub fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+  match name.rsplit_once('.') {
+    Some((schema, table)) => (Some(schema), table),
+    None => (None, name),
+  }
+}
+
+/// Returns the table name without its schema qualifier, which is how columns refer to an unaliased table
+pub fn unqualified_name(name: &str) -> &str {
+  split_qualified_name(name).1
+}
+
 pub struct DisplayTableAlias<'a>(pub &'a TableAlias);
 
 impl fmt::Display for DisplayTableAlias<'_> {
@@ -92,18 +110,18 @@ mod tests {
 
   #[test]
   fn test_display_object_name_qualified_schema_table() {
-    // Test qualified name: "public.users" -> should return "users"
+    // Test qualified name: "public.users" -> should keep the schema
     let object_name = parse_table_name("SELECT * FROM public.users");
     let display = DisplayObjectName(&object_name);
-    assert_eq!(display.to_string(), "users");
+    assert_eq!(display.to_string(), "public.users");
   }
 
   #[test]
   fn test_display_object_name_qualified_database_schema_table() {
-    // Test fully qualified name: "database.schema.table" -> should return "table"
+    // Test fully qualified name: "database.schema.table" -> should return "schema.table"
     let object_name = parse_table_name("SELECT * FROM mydb.public.users");
     let display = DisplayObjectName(&object_name);
-    assert_eq!(display.to_string(), "users");
+    assert_eq!(display.to_string(), "public.users");
   }
 
   #[test]
@@ -116,9 +134,16 @@ mod tests {
 
   #[test]
   fn test_display_object_name_qualified_with_quotes() {
-    // Test qualified with quotes: `"public"."my_table"` -> should return "my_table"
+    // Test qualified with quotes: `"public"."my_table"` -> should return "public.my_table"
     let object_name = parse_table_name("SELECT * FROM \"public\".\"my_table\"");
     let display = DisplayObjectName(&object_name);
-    assert_eq!(display.to_string(), "my_table");
+    assert_eq!(display.to_string(), "public.my_table");
+  }
+
+  #[test]
+  fn test_split_qualified_name() {
+    assert_eq!(split_qualified_name("billing.invoices"), (Some("billing"), "invoices"));
+    assert_eq!(split_qualified_name("invoices"), (None, "invoices"));
+    assert_eq!(unqualified_name("billing.invoices"), "invoices");
   }
 }
@@ -55,18 +55,9 @@ pub async fn translate_insert(
               })?
               .value;
 
-            let field = table_details.get(match_col.as_str()).ok_or_else(|| {
-              let available_columns = table_details
-                .keys()
-                .map(|k| k.as_str())
-                .collect::<Vec<_>>()
-                .join(", ");
-              TsGeneratorError::ColumnNotFoundInTable {
-                column: match_col.clone(),
-                table: table_name.to_string(),
-                available_columns,
-              }
-            })?;
+            let field = table_details
+              .get(match_col.as_str())
+              .ok_or_else(|| TsGeneratorError::column_not_found(match_col, table_name, table_details.keys()))?;
 
             if value.to_string() == "?" {
               // If the placeholder is `'?'`, we can process it using insert_value_params and generate nested params type
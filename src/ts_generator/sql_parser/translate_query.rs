@@ -1,17 +1,25 @@
 use async_recursion::async_recursion;
-use sqlparser::ast::{FunctionArg, FunctionArgExpr, Query, Select, SelectItem, SetExpr, TableFactor, TableWithJoins};
+use sqlparser::ast::{
+  BinaryOperator, Expr, FunctionArg, FunctionArgExpr, GroupByExpr, JoinConstraint, JoinOperator, LimitClause,
+  OrderByKind, Query, Select, SelectItem, SetExpr, SetOperator, TableFactor, TableWithJoins,
+};
 use std::collections::HashMap;
 
 use super::expressions::{
-  translate_expr::translate_expr, translate_table_with_joins::translate_table_with_joins,
+  translate_expr::{get_expr_placeholder, translate_expr},
+  translate_table_with_joins::translate_table_with_joins,
   translate_wildcard_expr::translate_wildcard_expr,
 };
 use crate::ts_generator::sql_parser::quoted_strings::{DisplayIndent, DisplayTableAlias};
 use crate::{
+  common::{lazy::DB_SCHEMA, types::DatabaseType},
   core::connection::DBConn,
   ts_generator::{
     errors::TsGeneratorError,
-    sql_parser::expressions::translate_table_with_joins::get_default_table,
+    sql_parser::expressions::translate_table_with_joins::{
+      find_relation_reference, get_default_table, get_default_table_reference, get_nullable_relations,
+      get_table_relations,
+    },
     types::ts_query::{TsFieldType, TsQuery},
   },
 };
@@ -41,6 +49,34 @@ pub async fn translate_select(
 
   let full_table_with_joins = &Some(full_table_with_joins.clone());
 
+  // Outer joins and `IS NOT NULL` filters of this select affect nullability of the columns within its scope
+  // (including subqueries), they're restored once the select is translated
+  let outer_nullable_tables = ts_query.nullable_tables.clone();
+  let outer_not_null_columns = ts_query.not_null_columns.clone();
+  let outer_has_group_by = ts_query.has_group_by;
+  ts_query.has_group_by = match &select.group_by {
+    GroupByExpr::All(_) => true,
+    GroupByExpr::Expressions(group_by_exprs, _) => !group_by_exprs.is_empty(),
+  };
+  ts_query
+    .nullable_tables
+    .extend(get_nullable_relations(&child_table_with_joins));
+  if let Some(selection) = &select.selection {
+    let default_table_reference = (!child_table_with_joins.is_empty()).then(|| {
+      get_default_table_reference(
+        &get_default_table(&child_table_with_joins),
+        &Some(child_table_with_joins.clone()),
+      )
+    });
+    let mut not_null_identifiers = vec![];
+    collect_not_null_identifiers(selection, &mut not_null_identifiers);
+    for identifiers in &not_null_identifiers {
+      let column =
+        resolve_not_null_column(identifiers, &child_table_with_joins, &default_table_reference, db_conn).await;
+      ts_query.not_null_columns.extend(column);
+    }
+  }
+
   // Process table functions in FROM clause to extract parameters and column definitions
   // Note: Table-valued functions (e.g., jsonb_to_recordset) can be parsed as either TableFactor::Table with args or TableFactor::Function.
   for twj in &child_table_with_joins {
@@ -177,6 +213,43 @@ pub async fn translate_select(
     }
   }
 
+  // Process JOIN ... ON constraints, unqualified columns are resolved against the joined table
+  // e.g. `JOIN items ON rarity = $1` should look up `rarity` from `items` rather than the FROM table
+  for twj in &child_table_with_joins {
+    for join in &twj.joins {
+      let Some(constraint) = get_join_constraint_expr(&join.join_operator) else {
+        continue;
+      };
+
+      // Only joins against tables (or aliased table functions) can be resolved to a table name
+      match &join.relation {
+        TableFactor::Table { args: None, .. }
+        | TableFactor::Table { alias: Some(_), .. }
+        | TableFactor::Function { alias: Some(_), .. } => {}
+        _ => continue,
+      }
+
+      // The joined table goes first so it becomes the default table within the constraint
+      let mut join_table_with_joins = vec![TableWithJoins {
+        relation: join.relation.clone(),
+        joins: vec![],
+      }];
+      join_table_with_joins.extend(full_table_with_joins.clone().unwrap_or_default());
+      let join_table_name = get_default_table(&join_table_with_joins);
+
+      translate_expr(
+        constraint,
+        &Some(join_table_name.as_str()),
+        &Some(join_table_with_joins),
+        None,
+        ts_query,
+        db_conn,
+        false,
+      )
+      .await?;
+    }
+  }
+
   // If there's any WHERE statements, process it
   if let Some(selection) = &select.selection {
     let current_scope_table_name = get_default_table(&child_table_with_joins);
@@ -192,6 +265,210 @@ pub async fn translate_select(
     )
     .await?;
   }
+
+  let current_scope_table_name =
+    (!child_table_with_joins.is_empty()).then(|| get_default_table(&child_table_with_joins));
+  let current_scope_table_name = current_scope_table_name.as_deref();
+
+  // GROUP BY can only contribute params, e.g. GROUP BY date_trunc($1, created_at)
+  if let GroupByExpr::Expressions(group_by_exprs, _) = &select.group_by {
+    for group_by_expr in group_by_exprs {
+      translate_clause_expr(
+        group_by_expr,
+        &current_scope_table_name,
+        full_table_with_joins,
+        ts_query,
+        db_conn,
+      )
+      .await?;
+    }
+  }
+
+  if let Some(having) = &select.having {
+    translate_clause_expr(
+      having,
+      &current_scope_table_name,
+      full_table_with_joins,
+      ts_query,
+      db_conn,
+    )
+    .await?;
+  }
+
+  ts_query.nullable_tables = outer_nullable_tables;
+  ts_query.not_null_columns = outer_not_null_columns;
+  ts_query.has_group_by = outer_has_group_by;
+  Ok(())
+}
+
+/// Collects identifiers of the columns that a WHERE clause guarantees to be not null
+/// e.g. `WHERE guilds.name IS NOT NULL AND level > 10`, columns under OR or NOT cannot be narrowed
+fn collect_not_null_identifiers(selection: &Expr, identifiers: &mut Vec<Vec<String>>) {
+  match selection {
+    Expr::BinaryOp {
+      left,
+      op: BinaryOperator::And,
+      right,
+    } => {
+      collect_not_null_identifiers(left, identifiers);
+      collect_not_null_identifiers(right, identifiers);
+    }
+    Expr::Nested(expr) => collect_not_null_identifiers(expr, identifiers),
+    Expr::IsNotNull(expr) => match expr.as_ref() {
+      Expr::Identifier(ident) => identifiers.push(vec![DisplayIndent(ident).to_string()]),
+      Expr::CompoundIdentifier(idents) => {
+        identifiers.push(idents.iter().map(|ident| DisplayIndent(ident).to_string()).collect())
+      }
+      _ => {}
+    },
+    _ => {}
+  }
+}
+
+/// Resolves the relation of a column filtered by `IS NOT NULL` and returns the column as `table.column`, where the
+/// table is referred to by its alias if it has one
+///
+/// A qualified column such as `g.name` or `public.guilds.name` belongs to the relation its qualifier refers to, while an
+/// unqualified column belongs to the first table that has it, the default table first
+async fn resolve_not_null_column(
+  identifiers: &[String],
+  table_with_joins: &[TableWithJoins],
+  default_table_reference: &Option<String>,
+  db_conn: &DBConn,
+) -> Option<String> {
+  let (column, qualifier) = identifiers.split_last()?;
+  let table_reference = if qualifier.is_empty() {
+    let mut table_reference = None;
+    for (table_name, reference) in get_table_relations(table_with_joins) {
+      let fields = DB_SCHEMA
+        .lock()
+        .await
+        .fetch_table(&vec![table_name.as_str()], db_conn)
+        .await;
+      if fields.is_some_and(|fields| fields.contains_key(column)) {
+        table_reference = Some(reference);
+        break;
+      }
+    }
+    table_reference.or_else(|| default_table_reference.to_owned())
+  } else {
+    find_relation_reference(table_with_joins, &qualifier.join("."))
+  }?;
+
+  Some(format!("{table_reference}.{column}"))
+}
+
+/// Returns the `ON` expression of a join if there's any
+/// Joins with `USING`, `NATURAL` or without any constraints cannot have placeholders
+fn get_join_constraint_expr(join_operator: &JoinOperator) -> Option<&Expr> {
+  let constraint = match join_operator {
+    JoinOperator::Join(constraint)
+    | JoinOperator::Inner(constraint)
+    | JoinOperator::Left(constraint)
+    | JoinOperator::LeftOuter(constraint)
+    | JoinOperator::Right(constraint)
+    | JoinOperator::RightOuter(constraint)
+    | JoinOperator::FullOuter(constraint)
+    | JoinOperator::CrossJoin(constraint)
+    | JoinOperator::Semi(constraint)
+    | JoinOperator::LeftSemi(constraint)
+    | JoinOperator::RightSemi(constraint)
+    | JoinOperator::Anti(constraint)
+    | JoinOperator::LeftAnti(constraint)
+    | JoinOperator::RightAnti(constraint)
+    | JoinOperator::StraightJoin(constraint)
+    | JoinOperator::AsOf { constraint, .. } => constraint,
+    JoinOperator::CrossApply | JoinOperator::OuterApply => return None,
+  };
+
+  match constraint {
+    JoinConstraint::On(expr) => Some(expr),
+    _ => None,
+  }
+}
+
+/// Translates an expression from GROUP BY, HAVING or ORDER BY clauses
+/// These clauses never produce result fields, so the expression is only walked to pick up placeholders.
+/// Bare column references are skipped as they cannot hold a placeholder and they may refer to
+/// an alias of the select list (e.g. `ORDER BY total_quantity`) rather than an actual table column
+async fn translate_clause_expr(
+  expr: &Expr,
+  single_table_name: &Option<&str>,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  ts_query: &mut TsQuery,
+  db_conn: &DBConn,
+) -> Result<(), TsGeneratorError> {
+  match expr {
+    Expr::Identifier(_) | Expr::CompoundIdentifier(_) => Ok(()),
+    _ => {
+      translate_expr(
+        expr,
+        single_table_name,
+        table_with_joins,
+        None,
+        ts_query,
+        db_conn,
+        false,
+      )
+      .await
+    }
+  }
+}
+
+/// LIMIT and OFFSET placeholders are always numbers
+async fn translate_limit_expr(
+  expr: &Expr,
+  single_table_name: &Option<&str>,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  ts_query: &mut TsQuery,
+  db_conn: &DBConn,
+) -> Result<(), TsGeneratorError> {
+  if let Some(placeholder) = get_expr_placeholder(expr) {
+    return ts_query.insert_param(&TsFieldType::Number, &false, &Some(placeholder));
+  }
+  translate_clause_expr(expr, single_table_name, table_with_joins, ts_query, db_conn).await
+}
+
+/// Translates ORDER BY, LIMIT and OFFSET of a query
+/// Placeholders are picked up in the order they appear in the query, so MySQL's `LIMIT ?, ?` (offset, limit)
+/// is processed as it is written
+async fn translate_query_modifiers(
+  ts_query: &mut TsQuery,
+  single_table_name: &Option<&str>,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  query: &Query,
+  db_conn: &DBConn,
+) -> Result<(), TsGeneratorError> {
+  if let Some(order_by) = &query.order_by {
+    if let OrderByKind::Expressions(order_by_exprs) = &order_by.kind {
+      for order_by_expr in order_by_exprs {
+        translate_clause_expr(
+          &order_by_expr.expr,
+          single_table_name,
+          table_with_joins,
+          ts_query,
+          db_conn,
+        )
+        .await?;
+      }
+    }
+  }
+
+  match &query.limit_clause {
+    Some(LimitClause::LimitOffset { limit, offset, .. }) => {
+      if let Some(limit) = limit {
+        translate_limit_expr(limit, single_table_name, table_with_joins, ts_query, db_conn).await?;
+      }
+      if let Some(offset) = offset {
+        translate_limit_expr(&offset.value, single_table_name, table_with_joins, ts_query, db_conn).await?;
+      }
+    }
+    Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+      translate_limit_expr(offset, single_table_name, table_with_joins, ts_query, db_conn).await?;
+      translate_limit_expr(limit, single_table_name, table_with_joins, ts_query, db_conn).await?;
+    }
+    None => {}
+  }
   Ok(())
 }
 
@@ -244,10 +521,119 @@ pub async fn translate_query(
   let body = *query.body.clone();
   match body {
     SetExpr::Select(select) => {
-      translate_select(ts_query, table_with_joins, &select, db_conn, alias, is_selection).await
+      translate_select(ts_query, table_with_joins, &select, db_conn, alias, is_selection).await?;
+
+      // ORDER BY, LIMIT and OFFSET belong to the query but they are resolved within the scope of the select
+      let mut full_table_with_joins = select.from.clone();
+      if let Some(table_with_joins) = table_with_joins {
+        full_table_with_joins.extend(table_with_joins.clone());
+      }
+      let current_scope_table_name = (!select.from.is_empty()).then(|| get_default_table(&select.from));
+
+      translate_query_modifiers(
+        ts_query,
+        &current_scope_table_name.as_deref(),
+        &Some(full_table_with_joins),
+        query,
+        db_conn,
+      )
+      .await
+    }
+    body => {
+      translate_set_expr(ts_query, table_with_joins, &body, db_conn, alias, is_selection).await?;
+
+      // ORDER BY of a set operation can only refer to the result columns, so there's no table in scope
+      translate_query_modifiers(ts_query, &None, table_with_joins, query, db_conn).await
+    }
+  }
+}
+
+/// Translates the body of a query
+///
+/// Branches of set operations (UNION, INTERSECT, EXCEPT) are translated separately and then merged, so each result
+/// field is typed as the union of the branches' types. Params are collected from every branch in order
+#[async_recursion]
+pub async fn translate_set_expr(
+  ts_query: &mut TsQuery,
+  table_with_joins: &Option<Vec<TableWithJoins>>,
+  set_expr: &SetExpr,
+  db_conn: &DBConn,
+  alias: Option<&'async_recursion str>,
+  is_selection: bool,
+) -> Result<(), TsGeneratorError> {
+  match set_expr {
+    SetExpr::Select(select) => translate_select(ts_query, table_with_joins, select, db_conn, alias, is_selection).await,
+    SetExpr::Query(query) => translate_query(ts_query, table_with_joins, query, db_conn, alias, is_selection).await,
+    SetExpr::SetOperation { left, right, op, .. } => {
+      let mut left_query = ts_query.new_branch();
+      translate_set_expr(&mut left_query, table_with_joins, left, db_conn, alias, is_selection).await?;
+
+      let mut right_query = left_query.new_branch();
+      translate_set_expr(&mut right_query, table_with_joins, right, db_conn, alias, is_selection).await?;
+
+      // Rows of EXCEPT and INTERSECT are rows of the first operand, only UNION returns rows of both
+      if matches!(op, SetOperator::Except | SetOperator::Intersect | SetOperator::Minus) {
+        left_query.merge_branch_params(right_query);
+      } else {
+        left_query.merge_branch(right_query, true);
+      }
+      ts_query.merge_branch(left_query, false);
+      Ok(())
+    }
+    SetExpr::Values(values) => {
+      // Each row is merged like a branch of UNION ALL, e.g. VALUES (1, 'a'), (2, NULL)
+      for row in &values.rows {
+        let mut row_query = ts_query.new_branch();
+        for (idx, expr) in row.iter().enumerate() {
+          let column_name = get_values_column_name(db_conn, idx);
+          let expr_for_logging = expr.to_string();
+
+          if let Some(placeholder) = get_expr_placeholder(expr) {
+            row_query.insert_param(&TsFieldType::Any, &false, &Some(placeholder))?;
+            row_query.insert_result(
+              Some(column_name.as_str()),
+              &[TsFieldType::Any],
+              is_selection,
+              false,
+              &expr_for_logging,
+            )?;
+          } else if let Expr::Identifier(_) | Expr::CompoundIdentifier(_) = expr {
+            // Columns cannot be referenced without a table in scope
+            row_query.insert_result(
+              Some(column_name.as_str()),
+              &[TsFieldType::Any],
+              is_selection,
+              false,
+              &expr_for_logging,
+            )?;
+          } else {
+            translate_expr(
+              expr,
+              &None,
+              table_with_joins,
+              Some(column_name.as_str()),
+              &mut row_query,
+              db_conn,
+              is_selection,
+            )
+            .await?;
+          }
+        }
+        ts_query.merge_branch(row_query, false);
+      }
+      Ok(())
     }
     _ => Err(TsGeneratorError::Unknown(format!(
-      "Unknown query type while processing query: {query}"
+      "Unknown query type while processing query: {set_expr}"
     ))),
   }
 }
+
+/// Returns the name the database gives to a column of a VALUES body
+/// PostgreSQL and SQLite name them `column1`, `column2` ... and MySQL names them `column_0`, `column_1` ...
+fn get_values_column_name(db_conn: &DBConn, idx: usize) -> String {
+  match db_conn.get_db_type() {
+    DatabaseType::Mysql => format!("column_{idx}"),
+    DatabaseType::Postgres | DatabaseType::Sqlite => format!("column{}", idx + 1),
+  }
+}
@@ -0,0 +1,225 @@
+use color_eyre::eyre::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::common::lazy::CLI_ARGS;
+use crate::common::logger::*;
+use crate::ts_generator::generator::{get_colocated_ts_file_path, get_generated_files, GENERATED_FILE_MARKER};
+
+/// Number of unchanged lines shown around each change of a diff
+const CONTEXT_LINES: usize = 3;
+
+/// Colocated files of the source files, which may be left over if the source files have no SQLs left or no longer exist
+pub fn get_colocated_candidates<'a>(source_files: impl IntoIterator<Item = &'a PathBuf>) -> BTreeSet<PathBuf> {
+  // The single file of --generate-path is written from scratch on every run
+  if CLI_ARGS.generate_path.is_some() {
+    return BTreeSet::new();
+  }
+
+  source_files
+    .into_iter()
+    .map(|file_path| get_colocated_ts_file_path(file_path))
+    .collect()
+}
+
+/// Colocated files that may be left over from source files which have no SQLs left or no longer exist
+/// These are the colocated files of the source files without SQLs, along with the `.queries.ts` files that no scanned
+/// file is next to, which are only listed here and not read
+pub fn get_orphan_candidates(
+  source_folder: &Path,
+  scanned_files: &[PathBuf],
+  files_without_sqls: &BTreeSet<PathBuf>,
+) -> BTreeSet<PathBuf> {
+  if CLI_ARGS.generate_path.is_some() {
+    return BTreeSet::new();
+  }
+
+  let colocated_files = get_colocated_candidates(scanned_files);
+  let node_modules_path = source_folder.join("node_modules");
+
+  WalkDir::new(source_folder)
+    .follow_links(true)
+    .into_iter()
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.into_path())
+    .filter(|file_path| {
+      !file_path.starts_with(&node_modules_path)
+        && file_path.to_string_lossy().ends_with(".queries.ts")
+        && !colocated_files.contains(file_path)
+    })
+    .chain(get_colocated_candidates(files_without_sqls))
+    .collect()
+}
+
+/// Candidates that were generated for source files which have no SQLs left or no longer exist
+/// Only files that start with the header of generated files are considered, so files written by hand are never removed
+pub fn get_orphaned_files(candidates: &BTreeSet<PathBuf>, files_without_sqls: &BTreeSet<PathBuf>) -> Vec<PathBuf> {
+  candidates
+    .iter()
+    .filter(|file_path| {
+      let Ok(content) = fs::read_to_string(file_path) else {
+        return false;
+      };
+      let mut lines = content.lines();
+      if lines.next() != Some(GENERATED_FILE_MARKER) {
+        return false;
+      }
+
+      // A colocated file is generated from a single source file next to it
+      let source_files = lines
+        .map_while(|line| line.strip_prefix("// source: "))
+        .collect::<Vec<_>>();
+      match source_files.as_slice() {
+        [source_file] => {
+          let source_file = file_path.with_file_name(source_file);
+          !source_file.exists() || files_without_sqls.contains(&source_file)
+        }
+        _ => false,
+      }
+    })
+    .cloned()
+    .collect()
+}
+
+/// Removes the colocated files that are left over from source files which have no SQLs left or no longer exist
+pub fn remove_orphaned_files(orphaned_files: &[PathBuf]) -> Result<()> {
+  for file_path in orphaned_files {
+    fs::remove_file(file_path)?;
+    info!("Removed {:?} as its source file has no SQLs left", file_path);
+  }
+  Ok(())
+}
+
+/// Generated files that differ from what is on disk, along with a unified diff from the file on disk to the generated one
+/// Orphaned files are compared as empty files, as they would be removed
+pub fn get_stale_files(
+  types: &BTreeMap<PathBuf, String>,
+  orphaned_files: &[PathBuf],
+) -> Result<Vec<(PathBuf, String)>> {
+  let mut stale_files = vec![];
+
+  for (file_path, generated) in get_generated_files(types)? {
+    // A file that was never generated is compared as an empty file
+    let on_disk = fs::read_to_string(&file_path).unwrap_or_default();
+
+    if let Some(diff) = get_unified_diff(&file_path, &on_disk, &generated) {
+      stale_files.push((file_path, diff));
+    }
+  }
+
+  for file_path in orphaned_files {
+    let on_disk = fs::read_to_string(file_path).unwrap_or_default();
+    if let Some(diff) = get_unified_diff(file_path, &on_disk, "") {
+      stale_files.push((file_path.to_owned(), diff));
+    }
+  }
+
+  Ok(stale_files)
+}
+
+/// Prints the diff of each stale file, so that it can be applied or reviewed
+#[allow(clippy::print_stdout)]
+pub fn print_stale_files(stale_files: &[(PathBuf, String)]) {
+  for (_, diff) in stale_files {
+    print!("{diff}");
+  }
+}
+
+/// Unified diff of two versions of a file e.g. `@@ -1,3 +1,4 @@` hunks with the unchanged lines around each change
+/// Returns None when they are the same
+pub fn get_unified_diff(file_path: &Path, old: &str, new: &str) -> Option<String> {
+  if old == new {
+    return None;
+  }
+
+  // Unlike `diff::lines`, a file that ends with a newline does not get an empty last line
+  let old_lines = old.lines().collect::<Vec<_>>();
+  let new_lines = new.lines().collect::<Vec<_>>();
+  let lines = diff::slice(&old_lines, &new_lines);
+
+  // Changes that are close enough for their unchanged lines to overlap are shown in the same hunk
+  let mut hunks: Vec<Range<usize>> = vec![];
+  for (idx, line) in lines.iter().enumerate() {
+    if let diff::Result::Both(..) = line {
+      continue;
+    }
+
+    let start = idx.saturating_sub(CONTEXT_LINES);
+    let end = (idx + CONTEXT_LINES + 1).min(lines.len());
+    match hunks.last_mut() {
+      Some(hunk) if start <= hunk.end => hunk.end = end,
+      _ => hunks.push(start..end),
+    }
+  }
+
+  let is_old_line = |line: &&diff::Result<&&str>| !matches!(line, diff::Result::Right(_));
+  let is_new_line = |line: &&diff::Result<&&str>| !matches!(line, diff::Result::Left(_));
+
+  let mut unified_diff = format!("--- {}\n+++ {}\n", file_path.display(), file_path.display());
+  for hunk in hunks {
+    let old_start = lines[..hunk.start].iter().filter(is_old_line).count();
+    let new_start = lines[..hunk.start].iter().filter(is_new_line).count();
+    let old_len = lines[hunk.clone()].iter().filter(is_old_line).count();
+    let new_len = lines[hunk.clone()].iter().filter(is_new_line).count();
+
+    // An empty side of a hunk starts at the line before it
+    unified_diff.push_str(&format!(
+      "@@ -{},{old_len} +{},{new_len} @@\n",
+      old_start + usize::from(old_len > 0),
+      new_start + usize::from(new_len > 0),
+    ));
+
+    for line in &lines[hunk] {
+      let line = match line {
+        diff::Result::Left(line) => format!("-{line}\n"),
+        diff::Result::Right(line) => format!("+{line}\n"),
+        diff::Result::Both(line, _) => format!(" {line}\n"),
+      };
+      unified_diff.push_str(&line);
+    }
+  }
+
+  Some(unified_diff)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_unified_diff() {
+    let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+
+    assert_eq!(
+      get_unified_diff(Path::new("index.queries.ts"), old, new).unwrap(),
+      r#"--- index.queries.ts
++++ index.queries.ts
+@@ -1,5 +1,5 @@
+ a
+-b
++B
+ c
+ d
+ e
+@@ -8,3 +8,4 @@
+ h
+ i
+ j
++k
+"#
+    );
+  }
+
+  #[test]
+  fn test_get_unified_diff_of_a_missing_file() {
+    assert_eq!(get_unified_diff(Path::new("index.queries.ts"), "a\n", "a\n"), None);
+    assert_eq!(
+      get_unified_diff(Path::new("index.queries.ts"), "", "a\n").unwrap(),
+      "--- index.queries.ts\n+++ index.queries.ts\n@@ -0,0 +1,1 @@\n+a\n"
+    );
+  }
+}
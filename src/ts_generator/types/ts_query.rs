@@ -2,7 +2,7 @@ use crate::common::logger::*;
 use color_eyre::eyre::Result;
 use convert_case::{Case, Casing};
 use regex::Regex;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self};
 
 use crate::common::lazy::CONFIG;
@@ -240,6 +240,27 @@ impl TsFieldType {
     }
   }
 
+  /// SQLite columns can hold any type, so the declared type is mapped by the type affinity rules of SQLite
+  /// https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+  ///
+  /// Dates and times are stored as text by the SQLite date and time functions, so they are strings
+  pub fn get_ts_field_type_from_sqlite_field_type(sqlite_field_type: String) -> Self {
+    let field_type = sqlite_field_type.as_str();
+
+    if field_type.contains("int") {
+      Self::Number
+    } else if field_type.contains("char") || field_type.contains("clob") || field_type.contains("text") {
+      Self::String
+    } else if field_type.contains("blob") || field_type.is_empty() {
+      Self::Any
+    } else if field_type.contains("date") || field_type.contains("time") {
+      Self::String
+    } else {
+      // REAL and NUMERIC affinity
+      Self::Number
+    }
+  }
+
   pub fn get_ts_field_from_annotation(annotated_type: &str) -> Self {
     if annotated_type == "string" {
       return Self::String;
@@ -305,6 +326,14 @@ impl TsFieldType {
   }
 }
 
+/// Param and result column types of a prepared statement as the database describes them
+#[derive(Debug, Clone, Default)]
+pub struct DescribedStatement {
+  pub params: Vec<TsFieldType>,
+  // (column name, column type, whether the column is nullable if the database knows it)
+  pub columns: Vec<(String, TsFieldType, Option<bool>)>,
+}
+
 /// TsQuery holds information required to generate typescript type definition
 /// of the target SQL query
 ///
@@ -324,14 +353,24 @@ pub struct TsQuery {
   // Holds any annotated @param and perform replacement when generated TS types
   pub annotated_insert_params: BTreeMap<usize, BTreeMap<usize, Vec<TsFieldType>>>,
 
-  pub result: HashMap<String, Vec<TsFieldType>>,
+  // We use BTreeMap here so that the fields of the result are always generated in the same order
+  pub result: BTreeMap<String, Vec<TsFieldType>>,
+  // Names of the result fields in the order they were selected, used to line up columns of set operations
+  result_order: Vec<String>,
   // Holds any annotated @result and perform replacement when generating TS types
-  pub annotated_results: HashMap<String, Vec<TsFieldType>>,
+  pub annotated_results: BTreeMap<String, Vec<TsFieldType>>,
 
   // Stores column type definitions from table-valued functions like:
   // jsonb_to_recordset($1) AS t(id INT, name TEXT)
   // Maps table alias name -> (column name -> type)
   pub table_valued_function_columns: HashMap<String, HashMap<String, TsFieldType>>,
+
+  // Tables (by their alias if they have one) on the nullable side of outer joins within the scope being translated
+  pub nullable_tables: HashSet<String>,
+  // Columns as `table.column` that the WHERE clause in scope guarantees to be not null, e.g. `WHERE col IS NOT NULL`
+  pub not_null_columns: HashSet<String>,
+  // Whether the select in scope has GROUP BY, its aggregates are then computed over groups that have at least one row
+  pub has_group_by: bool,
 }
 
 impl TsQuery {
@@ -341,16 +380,20 @@ impl TsQuery {
       param_order: 0,
       params: BTreeMap::new(),
       annotated_params: BTreeMap::new(),
-      result: HashMap::new(),
+      result: BTreeMap::new(),
+      result_order: vec![],
       insert_params: BTreeMap::new(),
-      annotated_results: HashMap::new(),
+      annotated_results: BTreeMap::new(),
       annotated_insert_params: BTreeMap::new(),
       table_valued_function_columns: HashMap::new(),
+      nullable_tables: HashSet::new(),
+      not_null_columns: HashSet::new(),
+      has_group_by: false,
     }
   }
 
   /// set annotatd results to ts query so when generating ts types, it can use annotated results wherever possible
-  pub fn set_annotated_results(&mut self, annotated_results: HashMap<String, Vec<TsFieldType>>) {
+  pub fn set_annotated_results(&mut self, annotated_results: BTreeMap<String, Vec<TsFieldType>>) {
     self.annotated_results = annotated_results;
   }
 
@@ -381,6 +424,13 @@ impl TsQuery {
     }
   }
 
+  /// Whether a column of a table (referred to by its alias if it has one) can be null within the scope being translated
+  /// Columns of tables on the nullable side of outer joins are nullable unless the WHERE clause filters out nulls
+  pub fn is_column_nullable(&self, table: &str, column: &str, is_nullable: bool) -> bool {
+    (is_nullable || self.nullable_tables.contains(table))
+      && !self.not_null_columns.contains(&format!("{table}.{column}"))
+  }
+
   /// inserts a value into the result hashmap
   /// it should only insert a value if you are working with a non-subquery queries
   pub fn insert_result(
@@ -405,6 +455,9 @@ impl TsQuery {
           value.push(TsFieldType::Null);
         }
 
+        if !self.result_order.contains(alias) {
+          self.result_order.push(alias.to_owned());
+        }
         let _ = &self.result.insert(alias.to_owned(), value.to_owned());
       } else {
         return Err(TsGeneratorError::MissingAliasForFunctions(expr_for_logging.to_string()));
@@ -413,6 +466,162 @@ impl TsQuery {
     Ok(())
   }
 
+  /// Creates a TsQuery to translate a branch of a set operation (UNION, INTERSECT, EXCEPT) or a row of VALUES into
+  /// The branch carries over the params, so placeholders keep their order across the branches
+  pub fn new_branch(&self) -> TsQuery {
+    let mut branch = self.clone();
+    branch.result = BTreeMap::new();
+    branch.result_order = vec![];
+    branch
+  }
+
+  /// Merges a branch created by `new_branch` back into the query
+  ///
+  /// Result fields that exist in both are typed as the union of their types, and they are nullable if any of them is.
+  /// With `align_columns`, the branch is another operand of a set operation: its fields are matched against the
+  /// fields of this query by position (as the database does) unless both use the same field names, and fields that
+  /// cannot be matched are dropped since the result is always named after the first operand
+  pub fn merge_branch(&mut self, branch: TsQuery, align_columns: bool) {
+    self.params = branch.params;
+    self.param_order = branch.param_order;
+
+    let mut branch_result = branch.result;
+    let mut branch_order = branch.result_order;
+    let has_same_fields =
+      branch_result.len() == self.result.len() && branch_result.keys().all(|key| self.result.contains_key(key));
+
+    if align_columns
+      && !has_same_fields
+      && branch_order.len() == branch_result.len()
+      && branch_order.len() == self.result_order.len()
+    {
+      let renamed_result = branch_order
+        .iter()
+        .zip(&self.result_order)
+        .filter_map(|(branch_key, key)| branch_result.remove(branch_key).map(|types| (key.to_owned(), types)))
+        .collect();
+      branch_result = renamed_result;
+      branch_order = self.result_order.clone();
+    }
+
+    // Fields selected by a wildcard are not ordered, they are merged after the ordered ones
+    let mut remaining_keys = branch_result
+      .keys()
+      .filter(|key| !branch_order.contains(key))
+      .cloned()
+      .collect::<Vec<String>>();
+    remaining_keys.sort();
+    branch_order.extend(remaining_keys);
+
+    let drop_unmatched_fields = align_columns && !self.result.is_empty();
+    for key in branch_order {
+      let Some(branch_types) = branch_result.remove(&key) else {
+        continue;
+      };
+
+      match self.result.get_mut(&key) {
+        Some(types) => {
+          for ts_type in branch_types {
+            if !types.contains(&ts_type) {
+              types.push(ts_type);
+            }
+          }
+          // null always goes last, e.g. `number | string | null`
+          if let Some(null_idx) = types.iter().position(|t| *t == TsFieldType::Null) {
+            let null = types.remove(null_idx);
+            types.push(null);
+          }
+        }
+        None if drop_unmatched_fields => {}
+        None => {
+          self.result_order.push(key.to_owned());
+          self.result.insert(key, branch_types);
+        }
+      }
+    }
+  }
+
+  /// Merges only the params of a branch created by `new_branch`, for branches whose result fields are not returned
+  pub fn merge_branch_params(&mut self, branch: TsQuery) {
+    self.params = branch.params;
+    self.param_order = branch.param_order;
+  }
+
+  /// Replaces the types inferred by walking the SQL with the types the database described for the prepared statement
+  ///
+  /// The described types win unless they are `any` or the param / result was annotated. So does the nullability of the
+  /// result columns where the database knows it, otherwise the nullability inferred from the SQL is kept. Fields the
+  /// SQL walk missed are added
+  pub fn apply_described_statement(&mut self, described: DescribedStatement) {
+    let prefer = |types: &mut Vec<TsFieldType>, described: &TsFieldType, is_nullable: Option<bool>| {
+      let is_nullable = is_nullable.unwrap_or_else(|| types.contains(&TsFieldType::Null));
+      match described {
+        TsFieldType::Any => types.retain(|ts_type| *ts_type != TsFieldType::Null),
+        _ => *types = vec![described.to_owned()],
+      }
+      if is_nullable {
+        types.push(TsFieldType::Null);
+      }
+    };
+
+    if !self.insert_params.is_empty() {
+      // Values of INSERT statements are described in the same order as the rows and columns are listed
+      let count = self.insert_params.values().map(|row| row.len()).sum::<usize>();
+      if count == described.params.len() {
+        let cells = self.insert_params.iter_mut().flat_map(|(row, cols)| {
+          let is_annotated = self.annotated_insert_params.contains_key(row);
+          cols.values_mut().map(move |types| (is_annotated, types))
+        });
+        for ((is_annotated, types), described_type) in cells.zip(&described.params) {
+          if !is_annotated && *described_type != TsFieldType::Any {
+            prefer(types, described_type, None);
+          }
+        }
+      }
+    } else {
+      for (idx, described_type) in described.params.iter().enumerate() {
+        let order = idx + 1;
+        if self.annotated_params.contains_key(&order) || *described_type == TsFieldType::Any {
+          continue;
+        }
+        match self.params.get_mut(&order) {
+          Some(types) => prefer(types, described_type, None),
+          None => {
+            self.params.insert(order, vec![described_type.to_owned()]);
+          }
+        }
+      }
+    }
+
+    // Columns are matched by position when every field of the result was selected in order, otherwise by name
+    let match_by_position =
+      self.result_order.len() == self.result.len() && self.result_order.len() == described.columns.len();
+    for (idx, (name, described_type, is_nullable)) in described.columns.iter().enumerate() {
+      let key = match match_by_position {
+        true => self.result_order[idx].to_owned(),
+        false => self.format_column_name(name),
+      };
+      let is_annotated = self
+        .annotated_results
+        .keys()
+        .any(|alias| self.format_column_name(alias) == key);
+
+      match self.result.get_mut(&key) {
+        Some(_) if is_annotated || (*described_type == TsFieldType::Any && is_nullable.is_none()) => {}
+        Some(types) => prefer(types, described_type, *is_nullable),
+        None if is_annotated => {}
+        None => {
+          let mut types = vec![described_type.to_owned()];
+          if *is_nullable == Some(true) {
+            types.push(TsFieldType::Null);
+          }
+          self.result_order.push(key.to_owned());
+          self.result.insert(key, types);
+        }
+      }
+    }
+  }
+
   /// This is used to insert value params required for INSERT statements
   /// For example if you are given
   ///
@@ -542,13 +751,10 @@ impl TsQuery {
   }
 
   fn fmt_result(&self, _f: &mut fmt::Formatter<'_>) -> String {
-    let mut keys = Vec::from_iter(self.result.keys());
-    keys.sort();
-
-    let result: Vec<String> = keys
+    let result: Vec<String> = self
+      .result
       .iter()
-      .map(|key| {
-        let data_type = self.result.get(key.to_owned()).unwrap();
+      .map(|(key, data_type)| {
         let data_types = data_type
           .iter()
           .map(|ts_field_type| ts_field_type.to_string())
@@ -579,3 +785,89 @@ impl fmt::Display for TsQuery {
     writeln!(f, "{final_code}")
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn query_with_nullable_tables(nullable_tables: &[&str], not_null_columns: &[&str]) -> TsQuery {
+    let mut ts_query = TsQuery::new("query".to_string());
+    ts_query.nullable_tables = nullable_tables.iter().map(|table| table.to_string()).collect();
+    ts_query.not_null_columns = not_null_columns.iter().map(|column| column.to_string()).collect();
+    ts_query
+  }
+
+  #[test]
+  fn should_keep_nullability_of_columns_outside_outer_joins() {
+    let ts_query = query_with_nullable_tables(&[], &[]);
+
+    assert!(ts_query.is_column_nullable("items", "rarity", true));
+    assert!(!ts_query.is_column_nullable("items", "id", false));
+  }
+
+  #[test]
+  fn should_make_columns_of_nullable_tables_nullable() {
+    let ts_query = query_with_nullable_tables(&["g"], &[]);
+
+    assert!(ts_query.is_column_nullable("g", "id", false));
+    assert!(!ts_query.is_column_nullable("guilds", "id", false));
+  }
+
+  #[test]
+  fn should_narrow_columns_filtered_by_is_not_null() {
+    let ts_query = query_with_nullable_tables(&["g"], &["g.id", "items.rarity"]);
+
+    assert!(!ts_query.is_column_nullable("g", "id", false));
+    assert!(ts_query.is_column_nullable("g", "name", false));
+    assert!(!ts_query.is_column_nullable("items", "rarity", true));
+  }
+
+  fn query_with_results(results: &[(&str, Vec<TsFieldType>)]) -> TsQuery {
+    let mut ts_query = TsQuery::new("query".to_string());
+    for (name, types) in results {
+      ts_query.result_order.push(name.to_string());
+      ts_query.result.insert(name.to_string(), types.to_owned());
+    }
+    ts_query
+  }
+
+  fn described_columns(columns: &[(&str, TsFieldType, Option<bool>)]) -> DescribedStatement {
+    DescribedStatement {
+      params: vec![],
+      columns: columns
+        .iter()
+        .map(|(name, ts_type, is_nullable)| (name.to_string(), ts_type.to_owned(), *is_nullable))
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn should_prefer_described_nullability_of_columns() {
+    let mut ts_query = query_with_results(&[
+      ("id", vec![TsFieldType::Number, TsFieldType::Null]),
+      ("name", vec![TsFieldType::Any]),
+    ]);
+    ts_query.apply_described_statement(described_columns(&[
+      ("id", TsFieldType::Number, Some(false)),
+      ("name", TsFieldType::Any, Some(true)),
+    ]));
+
+    assert_eq!(ts_query.result["id"], vec![TsFieldType::Number]);
+    assert_eq!(ts_query.result["name"], vec![TsFieldType::Any, TsFieldType::Null]);
+  }
+
+  #[test]
+  fn should_keep_inferred_nullability_of_columns_the_database_does_not_know() {
+    let mut ts_query = query_with_results(&[
+      ("id", vec![TsFieldType::Any, TsFieldType::Null]),
+      ("name", vec![TsFieldType::String]),
+    ]);
+    ts_query.apply_described_statement(described_columns(&[
+      ("id", TsFieldType::Number, None),
+      ("name", TsFieldType::String, None),
+    ]));
+
+    assert_eq!(ts_query.result["id"], vec![TsFieldType::Number, TsFieldType::Null]);
+    assert_eq!(ts_query.result["name"], vec![TsFieldType::String]);
+  }
+}
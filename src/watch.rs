@@ -0,0 +1,218 @@
+use color_eyre::eyre::Result;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::time::sleep;
+
+use crate::common::diagnostics::print_diagnostics;
+use crate::common::lazy::{CONFIG, DIAGNOSTICS};
+use crate::common::logger::*;
+use crate::common::types::FileExtension;
+use crate::core::execute::{execute, Summary};
+use crate::parser::parse_source;
+use crate::scan_folder::{is_scanned_file, scan_folder};
+use crate::ts_generator::generator::write_generated_files;
+use crate::ts_generator::stale::{get_colocated_candidates, get_orphaned_files, remove_orphaned_files};
+
+/// Changes that arrive within this window are checked together, as editors often write a file in several steps
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// State of --watch kept between checks, while the connection pools and the schema cache stay warm in the globals
+#[derive(Default)]
+struct Watcher {
+  /// Every file that is checked, ordered by path
+  files: BTreeSet<PathBuf>,
+  /// Types generated for each file as of when it was last checked, ordered by the path of the file
+  types: BTreeMap<PathBuf, String>,
+}
+
+impl Watcher {
+  /// Files that were added or modified along with files that were removed, out of the paths of the filesystem events
+  fn get_changes(
+    &mut self,
+    source_folder: &Path,
+    paths: &BTreeSet<PathBuf>,
+    exts: &[FileExtension],
+  ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut changed = BTreeSet::new();
+    let mut removed = BTreeSet::new();
+
+    for path in paths {
+      if path.is_dir() {
+        // the files of a folder that is created or moved in are not reported one by one
+        changed.extend(scan_files(source_folder, path, exts));
+      } else if path.is_file() {
+        if is_checked_file(source_folder, path, exts) {
+          changed.insert(path.to_owned());
+        }
+      } else {
+        // a file or a folder that was removed or moved out
+        removed.extend(
+          self
+            .files
+            .iter()
+            .filter(|file_path| file_path.starts_with(path))
+            .cloned(),
+        );
+      }
+    }
+
+    self.files.extend(changed.iter().cloned());
+    for file_path in &removed {
+      self.files.remove(file_path);
+    }
+
+    (changed.into_iter().collect(), removed.into_iter().collect())
+  }
+
+  /// Re-checks the changed files, the generated files that did not change are left untouched
+  /// Generated files of the files that were removed or have no SQLs left are removed
+  async fn check_files(&mut self, changed: &[PathBuf], removed: &[PathBuf]) -> Result<()> {
+    let mut summary = Summary::default();
+
+    for file_path in removed {
+      self.types.remove(file_path);
+    }
+
+    for file_path in changed {
+      let (sqls, handler) = match parse_source(file_path) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+          error!("Failed to parse {:?} - {}", file_path, err);
+          continue;
+        }
+      };
+      execute(file_path, &sqls, &handler, &mut summary).await?;
+      summary.files_scanned += 1;
+
+      match summary.generated_types.remove(file_path) {
+        Some(types) => self.types.insert(file_path.to_owned(), types),
+        None => self.types.remove(file_path),
+      };
+    }
+
+    write_generated_files(&self.types)?;
+    // Only the files that were removed or have no SQLs left can leave their generated files over
+    let orphan_candidates = get_colocated_candidates(removed.iter().chain(&summary.files_without_sqls));
+    remove_orphaned_files(&get_orphaned_files(&orphan_candidates, &summary.files_without_sqls))?;
+
+    summary.print();
+    print_diagnostics();
+    DIAGNOSTICS.lock().unwrap().clear();
+
+    if summary.has_failures() {
+      error!("SQLs failed to compile!\n");
+    } else {
+      info!("No SQL errors detected!\n");
+    }
+    Ok(())
+  }
+}
+
+/// Whether the file is the single file of --generate-path, which is written by the watcher itself
+fn is_generated_single_file(file_path: &Path) -> bool {
+  let generate_path = CONFIG
+    .generate_types_config
+    .as_ref()
+    .and_then(|config| config.generate_path.as_ref());
+
+  match generate_path {
+    Some(generate_path) => {
+      generate_path.file_name() == file_path.file_name()
+        && fs::canonicalize(generate_path).ok() == fs::canonicalize(file_path).ok()
+    }
+    None => false,
+  }
+}
+
+/// Whether the file is written by the watcher itself, a `.queries.ts` file next to its source file or the single file
+fn is_generated_file(file_path: &Path) -> bool {
+  let is_colocated_file = file_path
+    .file_name()
+    .is_some_and(|file_name| file_name.to_string_lossy().ends_with(".queries.ts"));
+
+  is_colocated_file || is_generated_single_file(file_path)
+}
+
+/// Whether the file is picked up by scanning the source folder and is not written by the watcher itself
+fn is_checked_file(source_folder: &Path, file_path: &Path, exts: &[FileExtension]) -> bool {
+  !is_generated_file(file_path) && exts.iter().any(|ext| is_scanned_file(source_folder, file_path, ext))
+}
+
+/// Files under the folder that are checked
+fn scan_files(source_folder: &Path, folder: &PathBuf, exts: &[FileExtension]) -> Vec<PathBuf> {
+  exts
+    .iter()
+    .flat_map(|ext| scan_folder(folder, ext))
+    .filter(|file_path| is_checked_file(source_folder, file_path, exts))
+    .collect()
+}
+
+/// Paths of a filesystem event relative to the source folder as it was given, the same way the scanned files are
+/// Folders are only kept when they are created or moved in, as their own changes do not change the files within
+fn get_event_paths(source_folder: &Path, root: &Path, event: notify::Result<Event>) -> Vec<PathBuf> {
+  let event = match event {
+    Ok(event) => event,
+    Err(err) => {
+      warning!("Failed to watch {:?} for changes - {}", source_folder, err);
+      return vec![];
+    }
+  };
+  if event.kind.is_access() {
+    return vec![];
+  }
+  let keeps_folders = matches!(
+    event.kind,
+    EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+  );
+
+  event
+    .paths
+    .iter()
+    .filter_map(|path| path.strip_prefix(root).ok())
+    .filter(|path| !path.as_os_str().is_empty())
+    .map(|path| source_folder.join(path))
+    .filter(|path| keeps_folders || !path.is_dir())
+    .collect()
+}
+
+/// Checks every file, then keeps checking the files that change until the process is stopped
+pub async fn watch(source_folder: &PathBuf, exts: &[FileExtension]) -> Result<()> {
+  // events are reported with the canonical path of the folder that is watched
+  let root = fs::canonicalize(source_folder)?;
+
+  // the folder is watched before it is scanned, so files that change during the first check are checked again
+  let (sender, mut receiver) = unbounded_channel();
+  let mut fs_watcher = notify::recommended_watcher(move |event| {
+    let _ = sender.send(event);
+  })?;
+  fs_watcher.watch(&root, RecursiveMode::Recursive)?;
+
+  let mut watcher = Watcher::default();
+  let files = scan_files(source_folder, source_folder, exts);
+  watcher.files.extend(files.iter().cloned());
+  watcher.check_files(&files, &[]).await?;
+  info!("Watching {:?} for changes", source_folder);
+
+  while let Some(event) = receiver.recv().await {
+    let mut paths = get_event_paths(source_folder, &root, event)
+      .into_iter()
+      .collect::<BTreeSet<_>>();
+    sleep(DEBOUNCE).await;
+    while let Ok(event) = receiver.try_recv() {
+      paths.extend(get_event_paths(source_folder, &root, event));
+    }
+
+    let (changed, removed) = watcher.get_changes(source_folder, &paths, exts);
+    if !changed.is_empty() || !removed.is_empty() {
+      watcher.check_files(&changed, &removed).await?;
+      info!("Watching {:?} for changes", source_folder);
+    }
+  }
+
+  Ok(())
+}
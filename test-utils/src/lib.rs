@@ -5,6 +5,7 @@ pub mod test_utils {
 
     pub trait TSString {
         fn flatten(&self) -> Self;
+        fn strip_generated_header(&self) -> Self;
     }
 
     impl TSString for String {
@@ -12,6 +13,16 @@ pub mod test_utils {
             let re = Regex::new(r"[\n\s]+").unwrap();
             re.replace_all(&self, " ").trim().to_string()
         }
+
+        /// Removes the `// @generated by sqlx-ts` header, which records a hash that changes along with the types
+        fn strip_generated_header(&self) -> Self {
+            if !self.starts_with("// @generated by sqlx-ts") {
+                return self.to_string();
+            }
+
+            let types = self.split_once("\n\n").map(|(_, types)| types).unwrap_or("");
+            types.to_string()
+        }
     }
 
     #[test]
@@ -179,14 +179,14 @@ $(
 
         assert_eq!(
             generated_types.trim().to_string().flatten(),
-            type_file.trim().to_string().flatten()
+            type_file.strip_generated_header().flatten()
         );
         return Ok(());
       }
 
       let type_file = fs::read_to_string(parent_path.join("index.queries.ts"));
       if type_file.is_ok() {
-        let type_file = type_file.unwrap().clone();
+        let type_file = type_file.unwrap().strip_generated_header();
         let type_file = type_file.trim();
         assert_eq!(
             generated_types.trim().to_string().flatten(),
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod check_generated_files_tests {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use std::fs;
+  use std::path::Path;
+  use tempfile::tempdir;
+
+  fn run_postgres(parent_path: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .args(args);
+
+    cmd.assert()
+  }
+
+  #[test]
+  fn should_pass_when_the_generated_files_are_up_to_date() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    run_postgres(parent_path, &["-g"]).success();
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, &["--check"])
+      .success()
+      .stdout(predicates::str::contains("Generated files are up to date"));
+    Ok(())
+  }
+
+  #[test]
+  fn should_print_a_diff_of_stale_files_without_writing_them() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join("index.ts");
+    fs::write(
+      &file_path,
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    run_postgres(parent_path, &["-g"]).success();
+    let generated = fs::read_to_string(parent_path.join("index.queries.ts"))?;
+
+    // The query changed without regenerating its types
+    fs::write(
+      &file_path,
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id, name FROM items`;\n",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, &["--check"])
+      .failure()
+      .stdout(predicates::str::contains("index.queries.ts\n@@ -1,11 +1,12 @@"))
+      .stdout(predicates::str::contains(" \tid: number;\n+\tname: string;\n }"))
+      .stderr(predicates::str::contains("1 generated files are out of date"));
+
+    assert_eq!(fs::read_to_string(parent_path.join("index.queries.ts"))?, generated);
+    Ok(())
+  }
+
+  #[test]
+  fn should_check_the_single_file_of_generate_path() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let generate_path = parent_path.join("types").join("queries.ts");
+    let generate_path_arg = format!("--generate-path={}", generate_path.to_str().unwrap());
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE & ASSERT - nothing was generated yet, and --check does not write it either
+    run_postgres(parent_path, &["--check", &generate_path_arg])
+      .failure()
+      .stdout(predicates::str::contains("@@ -0,0 +1,14 @@"));
+    assert!(!generate_path.exists());
+
+    run_postgres(parent_path, &["-g", &generate_path_arg]).success();
+    run_postgres(parent_path, &["--check", &generate_path_arg]).success();
+    Ok(())
+  }
+
+  #[test]
+  fn should_report_generated_files_that_would_be_removed() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join("index.ts");
+    fs::write(
+      &file_path,
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("other.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT name FROM items`;\n",
+    )?;
+    run_postgres(parent_path, &["-g"]).success();
+    fs::remove_file(&file_path)?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, &["--check"])
+      .failure()
+      .stdout(predicates::str::contains(
+        "index.queries.ts\" would be removed as its source file has no SQLs left",
+      ))
+      .stdout(predicates::str::contains("index.queries.ts\n@@ -1,14 +0,0 @@"))
+      .stderr(predicates::str::contains("1 generated files are out of date"));
+
+    assert!(parent_path.join("index.queries.ts").exists());
+    Ok(())
+  }
+}
@@ -507,4 +507,59 @@ mod cli_test {
     // Cleanup happens automatically when demo_dir and config_dir go out of scope
     Ok(())
   }
+
+  #[test]
+  fn should_report_invalid_tags_in_the_config() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let config_file_path = parent_path.join(".sqlxrc.json");
+    fs::write(
+      parent_path.join("index.ts"),
+      "const someQuery = sql`SELECT id FROM items`;",
+    )?;
+    fs::write(
+      &config_file_path,
+      r#"{ "connections": {}, "tags": [{ "module": "slonik" }] }"#,
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg(format!("--config={}", config_file_path.to_str().unwrap()))
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres");
+
+    // ASSERT
+    cmd
+      .assert()
+      .code(1)
+      .stderr(predicates::str::contains(
+        "Empty or invalid JSON provided for file based configuration - config file:",
+      ))
+      .stderr(predicates::str::contains("missing field `name`"));
+    Ok(())
+  }
+
+  #[test]
+  fn should_require_the_project_path() -> Result<(), Box<dyn std::error::Error>> {
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd.arg("--offline");
+
+    // ASSERT
+    cmd
+      .assert()
+      .code(2)
+      .stderr(predicates::str::contains(
+        "the following required arguments were not provided",
+      ))
+      .stderr(predicates::str::contains("<PATH>"));
+    Ok(())
+  }
 }
@@ -0,0 +1,90 @@
+/// Test suites for running `INIT_SQL` statements on every new connection of the pool
+#[cfg(test)]
+mod connection_init_sql {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_run_init_sql_after_search_path, TestConfig::new("postgres", true, None, Some(".sqlxrc.init_sql.json".to_string())),
+//// TS query ////
+r#"
+const someQuery = sql`SELECT id, name FROM items WHERE id = $1`;
+const otherQuery = sql`SELECT name FROM characters WHERE id = $1`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number];
+
+export interface ISomeQueryResult {
+    id: number;
+    name: string;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+
+export type OtherQueryParams = [number];
+
+export interface IOtherQueryResult {
+    name: string;
+}
+
+export interface IOtherQueryQuery {
+    params: OtherQueryParams;
+    result: IOtherQueryResult;
+}
+"#
+);
+
+  #[test]
+  fn should_fail_when_init_sql_fails() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let config_path = parent_path.join(".sqlxrc.json");
+    fs::write(
+      &config_path,
+      r#"{
+  "connections": {
+    "default": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 54321,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres",
+      "INIT_SQL": ["SET ROLE sqlx_ts_missing_role"]
+    }
+  }
+}"#,
+    )?;
+    fs::write(
+      parent_path.join("index.ts"),
+      "const someQuery = sql`SELECT id FROM items`;",
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg(format!("--config={}", config_path.to_str().unwrap()));
+
+    // ASSERT
+    cmd.assert().failure().stderr(predicates::str::contains(
+      "Failed to run the init statement \"SET ROLE sqlx_ts_missing_role\"",
+    ));
+
+    Ok(())
+  }
+}
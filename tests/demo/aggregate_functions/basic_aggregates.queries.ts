@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: basic_aggregates.ts
+// hash: sha256:9d5c5fcd497690eb62be3f305f838a89306d05af21dd7f23b0b5cc5a1083289a
+
 export type CountVariantsParams = [];
 
 export interface ICountVariantsResult {
@@ -30,9 +34,9 @@ export type MinAndMaxParams = [];
 
 export interface IMinAndMaxResult {
 	maxId: number;
-	maxName: number;
+	maxName: string;
 	minId: number;
-	minName: number;
+	minName: string;
 	rarity: string | null;
 }
 
@@ -30,9 +30,9 @@ export type MinAndMaxParams = [];
 
 export interface IMinAndMaxResult {
 	maxId: number;
-	maxName: number;
+	maxName: string;
 	minId: number;
-	minName: number;
+	minName: string;
 	rarity: string | null;
 }
 
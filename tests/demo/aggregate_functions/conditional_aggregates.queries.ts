@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: conditional_aggregates.ts
+// hash: sha256:bd47fcbfc10966bb7a31b056080e60799b2c2db5c5b3b68240d9501a0f93936d
+
 export type CountWithFilterParams = [];
 
 export interface ICountWithFilterResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: alias.ts
+// hash: sha256:6ea17fd6f3d434073efc567accf6f712dd3cac40155c10ed0dee0a965a521518
+
 export type Sql1Params = [];
 
 export interface ISql1Result {
@@ -45,7 +49,7 @@ export interface ISql4Query {
 export type Sql5Params = [];
 
 export interface ISql5Result {
-	theAvg: number;
+	theAvg: number | null;
 }
 
 export interface ISql5Query {
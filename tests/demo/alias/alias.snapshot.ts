@@ -45,7 +45,7 @@ export interface ISql4Query {
 export type Sql5Params = [];
 
 export interface ISql5Result {
-	theAvg: number;
+	theAvg: number | null;
 }
 
 export interface ISql5Query {
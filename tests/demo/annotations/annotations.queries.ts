@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: annotations.ts
+// hash: sha256:c60cec0d650112263ed3889acad21edd6f6b4c3a40f2bb65d3abc54adb6323a2
+
 export type TestMysqlQueryParams = [];
 
 export interface ITestMysqlQueryResult {
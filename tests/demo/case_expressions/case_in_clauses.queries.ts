@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: case_in_clauses.ts
+// hash: sha256:b20118a9fb83ba35f784b3fc4226b2725c743e66125870d6146e83608a1b68f7
+
 export type CaseInWhereParams = [];
 
 export interface ICaseInWhereResult {
@@ -28,7 +32,7 @@ export type CaseInGroupByHavingParams = [];
 
 export interface ICaseInGroupByHavingResult {
 	count: number;
-	rarityGroup: any;
+	rarityGroup: string;
 }
 
 export interface ICaseInGroupByHavingQuery {
@@ -28,7 +28,7 @@ export type CaseInGroupByHavingParams = [];
 
 export interface ICaseInGroupByHavingResult {
 	count: number;
-	rarityGroup: any;
+	rarityGroup: string;
 }
 
 export interface ICaseInGroupByHavingQuery {
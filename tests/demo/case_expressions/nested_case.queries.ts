@@ -1,7 +1,11 @@
+// @generated by sqlx-ts - do not edit
+// source: nested_case.ts
+// hash: sha256:c466fa7be0bb1f55116d2bc5c54bb6950f4d3960e1f4a8ffaa77aa3df3b06fba
+
 export type NestedCaseBasicParams = [];
 
 export interface INestedCaseBasicResult {
-	detailedRarity: any;
+	detailedRarity: string;
 	id: number;
 	name: string;
 	rarity: string | null;
@@ -17,7 +21,7 @@ export type NestedCaseMultipleLevelsParams = [];
 export interface INestedCaseMultipleLevelsResult {
 	id: number;
 	name: string;
-	tier: any;
+	tier: string;
 }
 
 export interface INestedCaseMultipleLevelsQuery {
@@ -1,8 +1,12 @@
+// @generated by sqlx-ts - do not edit
+// source: searched_case.ts
+// hash: sha256:bb5fc7873ff842ef4b06cbc7121aaed25ee48b5be1e07db54e69c501709a6898
+
 export type SearchedCaseBasicParams = [];
 
 export interface ISearchedCaseBasicResult {
 	id: number;
-	idCategory: any;
+	idCategory: string;
 	name: string;
 }
 
@@ -15,7 +19,7 @@ export type SearchedCaseMultipleConditionsParams = [];
 
 export interface ISearchedCaseMultipleConditionsResult {
 	id: number;
-	itemClass: any;
+	itemClass: string;
 	name: string;
 	rarity: string | null;
 }
@@ -25,12 +29,12 @@ export interface ISearchedCaseMultipleConditionsQuery {
 	result: ISearchedCaseMultipleConditionsResult;
 }
 
-export type SearchedCaseWithParamsParams = [];
+export type SearchedCaseWithParamsParams = [number];
 
 export interface ISearchedCaseWithParamsResult {
 	id: number;
 	name: string;
-	thresholdStatus: any;
+	thresholdStatus: string;
 }
 
 export interface ISearchedCaseWithParamsQuery {
@@ -2,7 +2,7 @@ export type SearchedCaseBasicParams = [];
 
 export interface ISearchedCaseBasicResult {
 	id: number;
-	idCategory: any;
+	idCategory: string;
 	name: string;
 }
 
@@ -15,7 +15,7 @@ export type SearchedCaseMultipleConditionsParams = [];
 
 export interface ISearchedCaseMultipleConditionsResult {
 	id: number;
-	itemClass: any;
+	itemClass: string;
 	name: string;
 	rarity: string | null;
 }
@@ -25,12 +25,12 @@ export interface ISearchedCaseMultipleConditionsQuery {
 	result: ISearchedCaseMultipleConditionsResult;
 }
 
-export type SearchedCaseWithParamsParams = [];
+export type SearchedCaseWithParamsParams = [number];
 
 export interface ISearchedCaseWithParamsResult {
 	id: number;
 	name: string;
-	thresholdStatus: any;
+	thresholdStatus: string;
 }
 
 export interface ISearchedCaseWithParamsQuery {
@@ -1,9 +1,13 @@
+// @generated by sqlx-ts - do not edit
+// source: simple_case.ts
+// hash: sha256:9d9ef392c1c95562b1a00cf886808e87d62640f9801f32adfa29de25840dcb8e
+
 export type SimpleCaseBasicParams = [];
 
 export interface ISimpleCaseBasicResult {
 	id: number;
 	name: string;
-	rarityCode: any;
+	rarityCode: string;
 }
 
 export interface ISimpleCaseBasicQuery {
@@ -16,7 +20,7 @@ export type SimpleCaseWithNullParams = [];
 export interface ISimpleCaseWithNullResult {
 	id: number;
 	name: string;
-	rarityLevel: any;
+	rarityLevel: number;
 }
 
 export interface ISimpleCaseWithNullQuery {
@@ -28,9 +32,9 @@ export type MultipleCaseExpressionsParams = [];
 
 export interface IMultipleCaseExpressionsResult {
 	id: number;
-	idRange: any;
+	idRange: string;
 	name: string;
-	rarityTier: any;
+	rarityTier: string;
 }
 
 export interface IMultipleCaseExpressionsQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: distinct.ts
+// hash: sha256:baf5155dd0a84888ba10162ef62148ffdebc7550e20b3ac872dfb78d8235a3ab
+
 export type DistinctBasicParams = [];
 
 export interface IDistinctBasicResult {
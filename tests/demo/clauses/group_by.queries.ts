@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: group_by.ts
+// hash: sha256:0f1fd557e99a9af58b381f975491572b6afb5ff52c36834a2f9e9faec4f54f70
+
 export type GroupBySingleParams = [];
 
 export interface IGroupBySingleResult {
@@ -66,7 +70,7 @@ export type GroupByWithJoinParams = [];
 
 export interface IGroupByWithJoinResult {
 	itemsRarity: string | null;
-	totalQuantity: number;
+	totalQuantity: number | null;
 }
 
 export interface IGroupByWithJoinQuery {
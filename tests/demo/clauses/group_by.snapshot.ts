@@ -66,7 +66,7 @@ export type GroupByWithJoinParams = [];
 
 export interface IGroupByWithJoinResult {
 	itemsRarity: string | null;
-	totalQuantity: number;
+	totalQuantity: number | null;
 }
 
 export interface IGroupByWithJoinQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: having.ts
+// hash: sha256:d4f543859b0f996025d7c73425ee1354c500b58fec983725d1a5c80087edcbd4
+
 export type HavingBasicParams = [];
 
 export interface IHavingBasicResult {
@@ -10,7 +14,7 @@ export interface IHavingBasicQuery {
 	result: IHavingBasicResult;
 }
 
-export type HavingWithAggregateParams = [];
+export type HavingWithAggregateParams = [number, number];
 
 export interface IHavingWithAggregateResult {
 	count: number;
@@ -27,7 +31,7 @@ export type HavingWithSumParams = [];
 
 export interface IHavingWithSumResult {
 	itemsRarity: string | null;
-	totalQuantity: number;
+	totalQuantity: number | null;
 }
 
 export interface IHavingWithSumQuery {
@@ -10,7 +10,7 @@ export interface IHavingBasicQuery {
 	result: IHavingBasicResult;
 }
 
-export type HavingWithAggregateParams = [];
+export type HavingWithAggregateParams = [number, number];
 
 export interface IHavingWithAggregateResult {
 	count: number;
@@ -27,7 +27,7 @@ export type HavingWithSumParams = [];
 
 export interface IHavingWithSumResult {
 	itemsRarity: string | null;
-	totalQuantity: number;
+	totalQuantity: number | null;
 }
 
 export interface IHavingWithSumQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: limit_offset.ts
+// hash: sha256:2fba50a740eb1e8887a0a5678a3f57abdf4dc87324c4eaf6141dc9c46a04f305
+
 export type LimitBasicParams = [];
 
 export interface ILimitBasicResult {
@@ -46,7 +50,7 @@ export interface ILimitWithOrderByQuery {
 	result: ILimitWithOrderByResult;
 }
 
-export type PaginationWithParamsParams = [string | null];
+export type PaginationWithParamsParams = [string | null, number, number];
 
 export interface IPaginationWithParamsResult {
 	id: number;
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: order_by.ts
+// hash: sha256:4454ec826a96c80859191f4fe06c6167961668579d4f36d2e0fe49f728e0d7db
+
 export type OrderByAscParams = [];
 
 export interface IOrderByAscResult {
@@ -0,0 +1,62 @@
+// @generated by sqlx-ts - do not edit
+// source: set_operations.ts
+// hash: sha256:ac5726adfa15ddd22ce51885fbefe7c0d1a9352182a3ef658267e96438751f1f
+
+export type UnionAllParams = [number, string];
+
+export interface IUnionAllResult {
+	id: number;
+	name: string;
+	rarity: string | null;
+}
+
+export interface IUnionAllQuery {
+	params: UnionAllParams;
+	result: IUnionAllResult;
+}
+
+export type UnionByPositionParams = [string | null, number | null];
+
+export interface IUnionByPositionResult {
+	id: number;
+	name: string | null;
+}
+
+export interface IUnionByPositionQuery {
+	params: UnionByPositionParams;
+	result: IUnionByPositionResult;
+}
+
+export type IntersectParams = [string | null];
+
+export interface IIntersectResult {
+	id: number;
+}
+
+export interface IIntersectQuery {
+	params: IntersectParams;
+	result: IIntersectResult;
+}
+
+export type ExceptParams = [string | null, number];
+
+export interface IExceptResult {
+	id: number;
+}
+
+export interface IExceptQuery {
+	params: ExceptParams;
+	result: IExceptResult;
+}
+
+export type ValuesParams = [];
+
+export interface IValuesResult {
+	column1: number;
+	column2: string | null;
+}
+
+export interface IValuesQuery {
+	params: ValuesParams;
+	result: IValuesResult;
+}
@@ -0,0 +1,58 @@
+export type UnionAllParams = [number, string];
+
+export interface IUnionAllResult {
+	id: number;
+	name: string;
+	rarity: string | null;
+}
+
+export interface IUnionAllQuery {
+	params: UnionAllParams;
+	result: IUnionAllResult;
+}
+
+export type UnionByPositionParams = [string | null, number | null];
+
+export interface IUnionByPositionResult {
+	id: number;
+	name: string | null;
+}
+
+export interface IUnionByPositionQuery {
+	params: UnionByPositionParams;
+	result: IUnionByPositionResult;
+}
+
+export type IntersectParams = [string | null];
+
+export interface IIntersectResult {
+	id: number;
+}
+
+export interface IIntersectQuery {
+	params: IntersectParams;
+	result: IIntersectResult;
+}
+
+export type ExceptParams = [string | null, number];
+
+export interface IExceptResult {
+	id: number;
+}
+
+export interface IExceptQuery {
+	params: ExceptParams;
+	result: IExceptResult;
+}
+
+export type ValuesParams = [];
+
+export interface IValuesResult {
+	column1: number;
+	column2: string | null;
+}
+
+export interface IValuesQuery {
+	params: ValuesParams;
+	result: IValuesResult;
+}
@@ -0,0 +1,41 @@
+import { sql } from 'sqlx-ts'
+
+// UNION ALL
+const unionAll = sql`
+-- @name: union all
+SELECT id, name, rarity FROM items WHERE id = $1
+UNION ALL
+SELECT id, name, NULL AS rarity FROM characters WHERE name = $2
+`
+
+// UNION with columns lined up by position
+const unionByPosition = sql`
+-- @name: union by position
+SELECT id, name FROM items WHERE rarity = $1
+UNION
+SELECT id, description FROM quests WHERE required_level > $2
+`
+
+// INTERSECT
+const intersect = sql`
+-- @name: intersect
+SELECT id FROM items
+INTERSECT
+SELECT inventory_id AS id FROM items WHERE rarity = $1
+`
+
+// EXCEPT with parenthesised branches
+const except = sql`
+-- @name: except
+(SELECT id FROM items)
+EXCEPT
+(SELECT id FROM items WHERE rarity = $1)
+ORDER BY id
+LIMIT $2
+`
+
+// VALUES
+const values = sql`
+-- @name: values
+VALUES (1, 'common'), (2, NULL)
+`
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: boolean_logic.ts
+// hash: sha256:f998c59989b219fbc47bbe293121d610ab922938dbf40bb24e6d2b66fd2371d6
+
 export type AndOperatorParams = [];
 
 export interface IAndOperatorResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: mathematical_operations.ts
+// hash: sha256:3c2727d5ec3c887b738ec639b4bd7f27a2cac3b55baf9330768ca81d32b51215
+
 export type ArithmeticOperatorsParams = [];
 
 export interface IArithmeticOperatorsResult {
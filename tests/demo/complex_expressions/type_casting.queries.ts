@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: type_casting.ts
+// hash: sha256:8749a3c100a11c23733fcda5b670f2cdaeb39435ff34c0f7f3810763c969ef68
+
 export type CastFunctionParams = [];
 
 export interface ICastFunctionResult {
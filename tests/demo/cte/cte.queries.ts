@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: cte.ts
+// hash: sha256:fc4b946f406eb5b9bbf943495fa9ca952ae39ac14d84abe8231dfd40bb5f2647
+
 export type SimpleCteParams = [];
 
 export interface ISimpleCteResult {
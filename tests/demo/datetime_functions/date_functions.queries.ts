@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: date_functions.ts
+// hash: sha256:5aa4a39b203d9110edb2b0eb260b0fbe1523ac6e166f51d9239bcaf6d36c4099
+
 export type CurrentDateTimestampParams = [];
 
 export interface ICurrentDateTimestampResult {
@@ -59,7 +63,7 @@ export interface IAgeFunctionQuery {
 export type DateArithmeticParams = [];
 
 export interface IDateArithmeticResult {
-	createdAt: any | null;
+	createdAt: any;
 	id: number;
 	name: string;
 	oneMonthAgo: number;
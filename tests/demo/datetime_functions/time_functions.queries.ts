@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: time_functions.ts
+// hash: sha256:d19c97388560844703dcf737116b861bc3ad6baee2684668250ee6ece971a94b
+
 export type NowFunctionParams = [];
 
 export interface INowFunctionResult {
@@ -16,8 +20,8 @@ export type TimeComparisonParams = [];
 
 export interface ITimeComparisonResult {
 	id: number;
-	loginTime: any | null;
-	logoutTime: any | null;
+	loginTime: any;
+	logoutTime: any;
 	name: string;
 }
 
@@ -29,7 +33,7 @@ export interface ITimeComparisonQuery {
 export type IntervalOperationsParams = [];
 
 export interface IIntervalOperationsResult {
-	createdAt: any | null;
+	createdAt: any;
 	id: number;
 	name: string;
 	oneHourLater: number;
@@ -46,7 +50,7 @@ export type DateDifferenceParams = [];
 export interface IDateDifferenceResult {
 	id: number;
 	name: string;
-	sessionDuration: any | null;
+	sessionDuration: any;
 }
 
 export interface IDateDifferenceQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: delete.ts
+// hash: sha256:2fc671b85112c4e956fb2db9f1349745104e496451865be43cb7c31046918558
+
 export type DeleteSql1Params = [number];
 
 export interface IDeleteSql1Result {
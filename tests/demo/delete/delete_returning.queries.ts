@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: delete_returning.ts
+// hash: sha256:3660777253aef671ef31c8b44d8d5602f132fc322f2cc708021f136deebe7947
+
 export type DeleteReturningAllParams = [number];
 
 export interface IDeleteReturningAllResult {
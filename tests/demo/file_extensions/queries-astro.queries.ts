@@ -0,0 +1,27 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-astro.astro
+// hash: sha256:68dd9032b513db1c6a61c48c749f63335f1306e101a8eecf1dbf07e90429be8b
+
+export type AstroQueryParams = [string | null];
+
+export interface IAstroQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IAstroQueryQuery {
+	params: AstroQueryParams;
+	result: IAstroQueryResult;
+}
+
+export type AstroByIdQueryParams = [number];
+
+export interface IAstroByIdQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IAstroByIdQueryQuery {
+	params: AstroByIdQueryParams;
+	result: IAstroByIdQueryResult;
+}
@@ -0,0 +1,23 @@
+export type AstroQueryParams = [string | null];
+
+export interface IAstroQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IAstroQueryQuery {
+	params: AstroQueryParams;
+	result: IAstroQueryResult;
+}
+
+export type AstroByIdQueryParams = [number];
+
+export interface IAstroByIdQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IAstroByIdQueryQuery {
+	params: AstroByIdQueryParams;
+	result: IAstroByIdQueryResult;
+}
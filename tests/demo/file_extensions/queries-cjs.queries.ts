@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-cjs.cjs
+// hash: sha256:73bf5c2c60cee193f1722180cb81a57d805e89f8d0abb79309c482c08701a42d
+
 export type CjsQueryParams = [string | null];
 
 export interface ICjsQueryResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-cts.cts
+// hash: sha256:d76d8784e8f16b3b0546ee12045491f10cfe5d9987922200ea12b903486c02b7
+
 export type CtsQueryParams = [string | null];
 
 export interface ICtsQueryResult {
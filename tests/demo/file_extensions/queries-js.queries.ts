@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-js.js
+// hash: sha256:79adf4d2bc4870c6815dad8b27589905c881e02cf517114046cddb1e5d5a641b
+
 export type JsQueryParams = [string | null];
 
 export interface IJsQueryResult {
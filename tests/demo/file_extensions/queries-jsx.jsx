@@ -0,0 +1,16 @@
+const { sql } = require('sqlx-ts')
+
+// .jsx file extension test (React component in JavaScript)
+const jsxQuery = sql`
+-- @name: jsx query
+SELECT id, name FROM items WHERE rarity = $1
+`
+
+export const ItemList = async ({ id }) => (
+  <>
+    {await db.query(sql`
+-- @name: jsx child query
+SELECT id, name FROM items WHERE id = $1
+`)}
+  </>
+)
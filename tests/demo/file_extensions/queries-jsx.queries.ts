@@ -0,0 +1,27 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-jsx.jsx
+// hash: sha256:8be83bdb31654145e161da5455be1c513c56c911f3dc8eda6f0e182f93d9dc15
+
+export type JsxQueryParams = [string | null];
+
+export interface IJsxQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IJsxQueryQuery {
+	params: JsxQueryParams;
+	result: IJsxQueryResult;
+}
+
+export type JsxChildQueryParams = [number];
+
+export interface IJsxChildQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IJsxChildQueryQuery {
+	params: JsxChildQueryParams;
+	result: IJsxChildQueryResult;
+}
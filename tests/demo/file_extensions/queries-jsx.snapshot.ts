@@ -0,0 +1,23 @@
+export type JsxQueryParams = [string | null];
+
+export interface IJsxQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IJsxQueryQuery {
+	params: JsxQueryParams;
+	result: IJsxQueryResult;
+}
+
+export type JsxChildQueryParams = [number];
+
+export interface IJsxChildQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IJsxChildQueryQuery {
+	params: JsxChildQueryParams;
+	result: IJsxChildQueryResult;
+}
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-mjs.mjs
+// hash: sha256:ceab4a91d8cdd9e9ae30970963b27eba88ee82bfda1321f6bdb4699b4c6c19ea
+
 export type MjsQueryParams = [string | null];
 
 export interface IMjsQueryResult {
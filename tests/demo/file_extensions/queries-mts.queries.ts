@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-mts.mts
+// hash: sha256:1dec622b99cbb7b3a6d97ae6441b84b2bb757423473cba348ee11cba769c74a4
+
 export type MtsQueryParams = [string | null];
 
 export interface IMtsQueryResult {
@@ -0,0 +1,27 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-svelte.svelte
+// hash: sha256:2bd8d9e29de0cfd6d62a594bd23942a3235711030d4da4fb06cf3d67cee3d85a
+
+export type SvelteQueryParams = [string | null];
+
+export interface ISvelteQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ISvelteQueryQuery {
+	params: SvelteQueryParams;
+	result: ISvelteQueryResult;
+}
+
+export type SvelteInstanceQueryParams = [number];
+
+export interface ISvelteInstanceQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ISvelteInstanceQueryQuery {
+	params: SvelteInstanceQueryParams;
+	result: ISvelteInstanceQueryResult;
+}
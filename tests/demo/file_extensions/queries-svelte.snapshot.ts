@@ -0,0 +1,23 @@
+export type SvelteQueryParams = [string | null];
+
+export interface ISvelteQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ISvelteQueryQuery {
+	params: SvelteQueryParams;
+	result: ISvelteQueryResult;
+}
+
+export type SvelteInstanceQueryParams = [number];
+
+export interface ISvelteInstanceQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ISvelteInstanceQueryQuery {
+	params: SvelteInstanceQueryParams;
+	result: ISvelteInstanceQueryResult;
+}
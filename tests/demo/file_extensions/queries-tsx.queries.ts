@@ -0,0 +1,26 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-tsx.tsx
+// hash: sha256:f5c2d5bff8d9446a3db69daf343727192c833751ca036e9410ccfe36391014e1
+
+export type TsxQueryParams = [string | null];
+
+export interface ITsxQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ITsxQueryQuery {
+	params: TsxQueryParams;
+	result: ITsxQueryResult;
+}
+
+export type TsxJsxAttributeQueryParams = [number];
+
+export interface ITsxJsxAttributeQueryResult {
+	total: number;
+}
+
+export interface ITsxJsxAttributeQueryQuery {
+	params: TsxJsxAttributeQueryParams;
+	result: ITsxJsxAttributeQueryResult;
+}
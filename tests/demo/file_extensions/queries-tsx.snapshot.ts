@@ -0,0 +1,22 @@
+export type TsxQueryParams = [string | null];
+
+export interface ITsxQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface ITsxQueryQuery {
+	params: TsxQueryParams;
+	result: ITsxQueryResult;
+}
+
+export type TsxJsxAttributeQueryParams = [number];
+
+export interface ITsxJsxAttributeQueryResult {
+	total: number;
+}
+
+export interface ITsxJsxAttributeQueryQuery {
+	params: TsxJsxAttributeQueryParams;
+	result: ITsxJsxAttributeQueryResult;
+}
@@ -0,0 +1,25 @@
+import { sql } from 'sqlx-ts'
+
+// .tsx file extension test (React server component)
+const tsxQuery = sql`
+-- @name: tsx query
+SELECT id, name FROM items WHERE rarity = $1
+`
+
+export default async function ItemsPage({ rarity }: { rarity: string }) {
+  const items = await db.query<{ id: number; name: string }>(tsxQuery, [rarity])
+
+  return (
+    <ul>
+      {items.map((item) => (
+        <li key={item.id}>{item.name}</li>
+      ))}
+      <ItemCount
+        count={await db.query(sql`
+-- @name: tsx jsx attribute query
+SELECT COUNT(*) AS total FROM items WHERE id = $1
+`)}
+      />
+    </ul>
+  )
+}
@@ -0,0 +1,27 @@
+// @generated by sqlx-ts - do not edit
+// source: queries-vue.vue
+// hash: sha256:3fb4b03c7980807ea0809b0918e77cefbc519294e835e9b1ac345f46e8f669fc
+
+export type VueQueryParams = [string | null];
+
+export interface IVueQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IVueQueryQuery {
+	params: VueQueryParams;
+	result: IVueQueryResult;
+}
+
+export type VueSetupQueryParams = [number];
+
+export interface IVueSetupQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IVueSetupQueryQuery {
+	params: VueSetupQueryParams;
+	result: IVueSetupQueryResult;
+}
@@ -0,0 +1,23 @@
+export type VueQueryParams = [string | null];
+
+export interface IVueQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IVueQueryQuery {
+	params: VueQueryParams;
+	result: IVueQueryResult;
+}
+
+export type VueSetupQueryParams = [number];
+
+export interface IVueSetupQueryResult {
+	id: number;
+	name: string;
+}
+
+export interface IVueSetupQueryQuery {
+	params: VueSetupQueryParams;
+	result: IVueSetupQueryResult;
+}
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: insert-query.ts
+// hash: sha256:ebb8de1ead8ffbab38745703b5e070504fe4422cae83a574aabc1efbe3311899
+
 export type InsertWithWildcardParams = [number];
 
 export interface IInsertWithWildcardResult {
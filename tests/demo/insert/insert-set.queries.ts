@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: insert-set.ts
+// hash: sha256:344e22f51a3a0a8c72e09eebbde8f8e05480781ba8e6001febfb76e6b6f0047f
+
 export type InsertWithUnionSetParams = [number, number];
 
 export interface IInsertWithUnionSetResult {
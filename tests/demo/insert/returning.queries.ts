@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: returning.ts
+// hash: sha256:c63c62275646c7d015bafc01f18397b2d6aaaa4b51936956716ea64952c341a9
+
 export type InsertWildcardParams = [];
 
 export interface IInsertWildcardResult {
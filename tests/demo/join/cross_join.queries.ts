@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: cross_join.ts
+// hash: sha256:1741d98883ebc35014b127fb209dacb60c3355284cc4e1e522c7b1c5184b61f6
+
 export type BasicCrossJoinParams = [];
 
 export interface IBasicCrossJoinResult {
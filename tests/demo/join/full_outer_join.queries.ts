@@ -1,9 +1,13 @@
+// @generated by sqlx-ts - do not edit
+// source: full_outer_join.ts
+// hash: sha256:8e69cd2ef8f4a998209340a909936c6d856d7058a88e718867bb38dcc21dd76a
+
 export type BasicFullOuterJoinParams = [];
 
 export interface IBasicFullOuterJoinResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IBasicFullOuterJoinQuery {
@@ -14,9 +18,9 @@ export interface IBasicFullOuterJoinQuery {
 export type FullOuterJoinWithCoalesceParams = [];
 
 export interface IFullOuterJoinWithCoalesceResult {
-	id: number;
+	id: number | null;
 	inventoryQuantity: number | null;
-	itemsName: string;
+	itemsName: string | null;
 }
 
 export interface IFullOuterJoinWithCoalesceQuery {
@@ -2,8 +2,8 @@ export type BasicFullOuterJoinParams = [];
 
 export interface IBasicFullOuterJoinResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IBasicFullOuterJoinQuery {
@@ -14,9 +14,9 @@ export interface IBasicFullOuterJoinQuery {
 export type FullOuterJoinWithCoalesceParams = [];
 
 export interface IFullOuterJoinWithCoalesceResult {
-	id: number;
+	id: number | null;
 	inventoryQuantity: number | null;
-	itemsName: string;
+	itemsName: string | null;
 }
 
 export interface IFullOuterJoinWithCoalesceQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: left_join.ts
+// hash: sha256:3bbf5c1a8921db8028b24941db7e7ba1914a7154bc5c268b3ec4488d12cbeac3
+
 export type BasicLeftJoinParams = [];
 
 export interface IBasicLeftJoinResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: multiple_joins.ts
+// hash: sha256:7888270a32ecec40b0782dd6bcd912e0ce2149a626b8e7e16236c7dea4fe289c
+
 export type MixedJoinTypesParams = [];
 
 export interface IMixedJoinTypesResult {
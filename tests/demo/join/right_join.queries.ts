@@ -1,9 +1,13 @@
+// @generated by sqlx-ts - do not edit
+// source: right_join.ts
+// hash: sha256:15bab881396d940bc1342ea3873075fd7258af55d0461eb72260804eacede225
+
 export type BasicRightJoinParams = [];
 
 export interface IBasicRightJoinResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IBasicRightJoinQuery {
@@ -15,8 +19,8 @@ export type RightJoinWithParamsParams = [number | null];
 
 export interface IRightJoinWithParamsResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IRightJoinWithParamsQuery {
@@ -2,8 +2,8 @@ export type BasicRightJoinParams = [];
 
 export interface IBasicRightJoinResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IBasicRightJoinQuery {
@@ -15,8 +15,8 @@ export type RightJoinWithParamsParams = [number | null];
 
 export interface IRightJoinWithParamsResult {
 	inventoryQuantity: number | null;
-	itemsId: number;
-	itemsName: string;
+	itemsId: number | null;
+	itemsName: string | null;
 }
 
 export interface IRightJoinWithParamsQuery {
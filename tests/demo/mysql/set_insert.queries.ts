@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: set_insert.ts
+// hash: sha256:83a395682508bbffbfc6269dd883b3ffdbfad21fa708feaff96c6f6cf7aa9535
+
 export type SetInsert1Params = [[string | null]];
 
 export interface ISetInsert1Result {
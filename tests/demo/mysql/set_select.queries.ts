@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: set_select.ts
+// hash: sha256:92d64c95ddb1c47d51af3ec901a082780eea97dde95ed8308010fd336da7a316
+
 export type SetSelect1Params = [];
 
 export interface ISetSelect1Result {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: coalesce.ts
+// hash: sha256:9d563f22ed2963703ba1c3a004f209ba16f80a152fb24165401df7f57ef38992
+
 export type CoalesceBasicParams = [];
 
 export interface ICoalesceBasicResult {
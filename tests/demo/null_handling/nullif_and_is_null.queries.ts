@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: nullif_and_is_null.ts
+// hash: sha256:6a310fa6039e8810a1645dbe6516e8e3a682f58177712023ed3582f4be43e8c4
+
 export type IsNullBasicParams = [];
 
 export interface IIsNullBasicResult {
@@ -16,7 +20,7 @@ export type IsNotNullParams = [];
 export interface IIsNotNullResult {
 	id: number;
 	name: string;
-	rarity: string | null;
+	rarity: string;
 }
 
 export interface IIsNotNullQuery {
@@ -29,7 +33,7 @@ export type NullifBasicParams = [];
 export interface INullifBasicResult {
 	id: number;
 	name: string;
-	rarityExcludingCommon: string;
+	rarityExcludingCommon: string | null;
 }
 
 export interface INullifBasicQuery {
@@ -42,7 +46,7 @@ export type NullifEmptyStringParams = [];
 export interface INullifEmptyStringResult {
 	id: number;
 	name: string;
-	nameSafe: string;
+	nameSafe: string | null;
 }
 
 export interface INullifEmptyStringQuery {
@@ -16,7 +16,7 @@ export type IsNotNullParams = [];
 export interface IIsNotNullResult {
 	id: number;
 	name: string;
-	rarity: string | null;
+	rarity: string;
 }
 
 export interface IIsNotNullQuery {
@@ -29,7 +29,7 @@ export type NullifBasicParams = [];
 export interface INullifBasicResult {
 	id: number;
 	name: string;
-	rarityExcludingCommon: string;
+	rarityExcludingCommon: string | null;
 }
 
 export interface INullifBasicQuery {
@@ -42,7 +42,7 @@ export type NullifEmptyStringParams = [];
 export interface INullifEmptyStringResult {
 	id: number;
 	name: string;
-	nameSafe: string;
+	nameSafe: string | null;
 }
 
 export interface INullifEmptyStringQuery {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: boolean-functions.ts
+// hash: sha256:b1357283b5e8a9ca9e3c4ba0aa5189941dea225483fdb2439b757c1b665b1584
+
 export type ExistsParams = [number];
 
 export interface IExistsResult {
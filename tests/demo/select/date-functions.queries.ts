@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: date-functions.ts
+// hash: sha256:b0de1955ef4a553e06f06b505ef11bee26500cf7fa11535c5663c45640eb6243
+
 export type AtTimeZoneParams = [];
 
 export interface IAtTimeZoneResult {
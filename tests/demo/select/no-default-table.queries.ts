@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: no-default-table.ts
+// hash: sha256:e1b586a670db2a0714e772b5c69be957f44873f5ea817078cd28d3849060de07
+
 export type AllNumbersParams = [];
 
 export interface IAllNumbersResult {
@@ -5,7 +9,7 @@ export interface IAllNumbersResult {
 	acos1: number;
 	asin1: number;
 	atan1: number;
-	avg1: number;
+	avg1: number | null;
 	ceil1: number;
 	ceiling1: number;
 	cos1: number;
@@ -19,8 +23,8 @@ export interface IAllNumbersResult {
 	ln1: number;
 	log1: number;
 	log101: number;
-	max1: number;
-	min1: number;
+	max1: number | null;
+	min1: number | null;
 	mod1: number;
 	pi1: number;
 	pow1: number;
@@ -30,7 +34,7 @@ export interface IAllNumbersResult {
 	sign1: number;
 	sin1: number;
 	sqrt1: number;
-	sum1: number;
+	sum1: number | null;
 	tan1: number;
 	trunc0: number;
 }
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: number-functions.ts
+// hash: sha256:ec2cf575bfaea25e384d2e2c3cc54758e5b497177ebf8c355f9acf878223d2ce
+
 export type CeilParams = [];
 
 export interface ICeilResult {
@@ -17,7 +21,7 @@ export interface IAllResult {
 	acos1: number;
 	asin1: number;
 	atan1: number;
-	avg1: number;
+	avg1: number | null;
 	ceil1: number;
 	ceiling1: number;
 	cos1: number;
@@ -31,8 +35,8 @@ export interface IAllResult {
 	ln1: number;
 	log1: number;
 	log101: number;
-	max1: number;
-	min1: number;
+	max1: number | null;
+	min1: number | null;
 	mod1: number;
 	pi1: number;
 	pow1: number;
@@ -42,7 +46,7 @@ export interface IAllResult {
 	sign1: number;
 	sin1: number;
 	sqrt1: number;
-	sum1: number;
+	sum1: number | null;
 	tan1: number;
 	trunc1: number;
 }
@@ -17,7 +17,7 @@ export interface IAllResult {
 	acos1: number;
 	asin1: number;
 	atan1: number;
-	avg1: number;
+	avg1: number | null;
 	ceil1: number;
 	ceiling1: number;
 	cos1: number;
@@ -31,8 +31,8 @@ export interface IAllResult {
 	ln1: number;
 	log1: number;
 	log101: number;
-	max1: number;
-	min1: number;
+	max1: number | null;
+	min1: number | null;
 	mod1: number;
 	pi1: number;
 	pow1: number;
@@ -42,7 +42,7 @@ export interface IAllResult {
 	sign1: number;
 	sin1: number;
 	sqrt1: number;
-	sum1: number;
+	sum1: number | null;
 	tan1: number;
 	trunc1: number;
 }
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: qualified_table_names.ts
+// hash: sha256:7b1c0add8699a7658575d0a0c6c83ccdc32e7a6fd136cd2c4c03cbec3f14a983
+
 export type QualifiedTableNameParams = [];
 
 export interface IQualifiedTableNameResult {
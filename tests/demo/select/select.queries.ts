@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: select.ts
+// hash: sha256:d21b9afff9913efdd85b64fe6573ed3d1fda3197271e2f764bfa25b9e535840a
+
 export type SelectSql1Params = [];
 
 export interface ISelectSql1Result {
@@ -284,7 +288,7 @@ export interface ISelectSql24Query {
 export type SelectSql25Params = [];
 
 export interface ISelectSql25Result {
-	guildName: string;
+	guildName: string | null;
 }
 
 export interface ISelectSql25Query {
@@ -295,7 +299,7 @@ export interface ISelectSql25Query {
 export type SelectSql26Params = [];
 
 export interface ISelectSql26Result {
-	invQuantity: number;
+	invQuantity: number | null;
 }
 
 export interface ISelectSql26Query {
@@ -342,7 +346,7 @@ export type SelectSql30Params = [];
 export interface ISelectSql30Result {
 	charName: string;
 	lvl: number;
-	nonZeroGold: number;
+	nonZeroGold: number | null;
 }
 
 export interface ISelectSql30Query {
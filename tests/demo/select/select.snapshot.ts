@@ -284,7 +284,7 @@ export interface ISelectSql24Query {
 export type SelectSql25Params = [];
 
 export interface ISelectSql25Result {
-	guildName: string;
+	guildName: string | null;
 }
 
 export interface ISelectSql25Query {
@@ -295,7 +295,7 @@ export interface ISelectSql25Query {
 export type SelectSql26Params = [];
 
 export interface ISelectSql26Result {
-	invQuantity: number;
+	invQuantity: number | null;
 }
 
 export interface ISelectSql26Query {
@@ -342,7 +342,7 @@ export type SelectSql30Params = [];
 export interface ISelectSql30Result {
 	charName: string;
 	lvl: number;
-	nonZeroGold: number;
+	nonZeroGold: number | null;
 }
 
 export interface ISelectSql30Query {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: basic_select.sql
+// hash: sha256:cb279c6fe0d92382f5d833ad24c7f2b938d7c1a0bb8e4d7859052a2ad76258d1
+
 export type BasicSelectParams = [];
 
 export interface IBasicSelectResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: insert_query.sql
+// hash: sha256:587343d9b61255a958cba0f89fcdf22e6c36e8f3cbab3c37f916aa4fa2695205
+
 export type InsertQueryParams = [string, string | null];
 
 export interface IInsertQueryResult {
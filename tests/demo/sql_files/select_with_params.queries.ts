@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: select_with_params.sql
+// hash: sha256:264e1a37dca5fe0a1b32c9271f7fbc4e4c02fe879fdc9dec7cc06600df1156e4
+
 export type SelectWithParamsParams = [string | null];
 
 export interface ISelectWithParamsResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: update_delete.sql
+// hash: sha256:8878c85280919b7f0c02cb87c8be0ff5dcd54174628696e7640b32d1ae3aa58d
+
 export type UpdateQueryParams = [string, number];
 
 export interface IUpdateQueryResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: basic_string_functions.ts
+// hash: sha256:d1191eafcb883ef02df9980f522c53cb5dfc9044f57f4da76edeb7827a08d0f3
+
 export type ConcatFunctionParams = [];
 
 export interface IConcatFunctionResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: pattern_matching.ts
+// hash: sha256:82f4e43159e3fbec174ecf1326bd37d32424b19bfd28cf44aa098758cbba8a4a
+
 export type LikeBasicParams = [];
 
 export interface ILikeBasicResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: decorators.ts
+// hash: sha256:b8038a50feaf20eacab6a003a76199905ad11e31f98d460a96d7a76bc5c1031d
+
 export type PrivDecoConstructorParams = [];
 
 export interface IPrivDecoConstructorResult {
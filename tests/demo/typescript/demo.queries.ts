@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: demo.ts
+// hash: sha256:6d9ea233207a847b88aac994352c0c4463aa033cf7debf572c0db432b528abc4
+
 export type TestSequelizeQueryParams = [number];
 
 export interface ITestSequelizeQueryResult {
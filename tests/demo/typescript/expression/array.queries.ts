@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: array.ts
+// hash: sha256:64582a13359c086efd4887fb4342d229522db8124d04650fa128f802124e1a21
+
 export type ArrayQueryParams = [];
 
 export interface IArrayQueryResult {
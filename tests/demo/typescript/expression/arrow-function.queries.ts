@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: arrow-function.ts
+// hash: sha256:7a99dd24d39d783ab8d07e23915311400a6a3ad39201cc60dfa1fb212cc19c9b
+
 export type PlainArrowFunctionParams = [];
 
 export interface IPlainArrowFunctionResult {
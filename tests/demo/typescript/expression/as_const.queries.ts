@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: as_const.ts
+// hash: sha256:e18c762dc0105d10ed7fcdb242de8c04693d9d638446f77daa10af97b4ee7e6c
+
 export type BasicAsConstParams = [];
 
 export interface IBasicAsConstResult {
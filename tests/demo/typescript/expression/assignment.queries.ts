@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: assignment.ts
+// hash: sha256:5238678089485bb1825d2d57818f427d60a1f9826dc516f1039c029ba0a71855
+
 export type AssignmentParams = [];
 
 export interface IAssignmentResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: await.ts
+// hash: sha256:d1c98feb5bf60ec7bf248dbb48fb6368c1470bf11245d366f7a8ef841417c8a5
+
 export type AsyncPlainFunctionParams = [];
 
 export interface IAsyncPlainFunctionResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: binary-operations.ts
+// hash: sha256:4898b6a86a265df171c1dd04a94c532e42db461a5af2fd96d20e67cee81e61e5
+
 export type NullishCoalescingParams = [];
 
 export interface INullishCoalescingResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: call.ts
+// hash: sha256:7bfc1d2a58872ff62621751fa63cbe4967f9696e0e0a5be9745fc85d0729b815
+
 export type CallerTestParams = [];
 
 export interface ICallerTestResult {
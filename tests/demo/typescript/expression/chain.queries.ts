@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: chain.ts
+// hash: sha256:78a0022468a71bfc890f62535ba4261b7d88f0ec656d21599abbe9e0c4b6778d
+
 export type ParentChildParams = [];
 
 export interface IParentChildResult {
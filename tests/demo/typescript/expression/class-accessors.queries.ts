@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: class-accessors.ts
+// hash: sha256:083e0a5cd143dcbcf614b15c6333794901f2ad068736e03bdd1b94839c021f5c
+
 export type GetterQueryParams = [];
 
 export interface IGetterQueryResult {
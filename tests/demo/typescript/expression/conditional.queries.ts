@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: conditional.ts
+// hash: sha256:809c8da5533f787a77464c4845607c1cb3f4464b32f153e0f3b7f6235efb8bdf
+
 export type TruthyParams = [];
 
 export interface ITruthyResult {
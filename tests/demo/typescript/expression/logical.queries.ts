@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: logical.ts
+// hash: sha256:ddc5b480f008f3b58863453ef9634784ad1fbb5029cf5b9d63b5cc4ceb035ac6
+
 export type NullishCoalescingParams = [];
 
 export interface INullishCoalescingResult {
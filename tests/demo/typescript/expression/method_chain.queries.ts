@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: method_chain.ts
+// hash: sha256:d0f942ae079efb45c616abe686a7ee8fe68e00415efd5762f20f9580f49ca95d
+
 export type MethodChainSingleParams = [];
 
 export interface IMethodChainSingleResult {
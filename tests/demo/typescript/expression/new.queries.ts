@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: new.ts
+// hash: sha256:c8934c2a0776868734b9176e44e7a28a151638699990d3badf0f4c7a633eb164
+
 export type NewClassParams = [];
 
 export interface INewClassResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: nullish-coalescing.ts
+// hash: sha256:b723f7e387ab79035ea079534c5d265f06fddebaa90e0f201cb39e2ad7787c84
+
 export type TestNullishCoalescingQueryParams = [];
 
 export interface ITestNullishCoalescingQueryResult {
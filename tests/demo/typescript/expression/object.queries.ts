@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: object.ts
+// hash: sha256:88e3598f6c3aec8779ff68567204afd3e9c34500757d32fb43618bfdc97b0e07
+
 export type AnotherTestObjectQueryParams = [];
 
 export interface IAnotherTestObjectQueryResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: scope.ts
+// hash: sha256:f51e26f6338dc0fe258683816f9b7bda5ac7247b814e49540a9c8d3bfe870e1b
+
 export type TestParenthesisQueryParams = [];
 
 export interface ITestParenthesisQueryResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: super.ts
+// hash: sha256:29b88b67dab1869b29553c00a421e5c1f181a48866ee520b2d89e23b0272c011
+
 export type SuperQueryParams = [];
 
 export interface ISuperQueryResult {
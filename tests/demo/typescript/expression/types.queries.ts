@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: types.ts
+// hash: sha256:fde7d0c2329e75ddbcb6ad9edf50a1a902706175c7dae951d64951df3ae99959
+
 export type ModuleSqlParams = [];
 
 export interface IModuleSqlResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: yield.ts
+// hash: sha256:3499f4437f8ff2851c84829d62dab5db60577da5dec0d111c5ac9ac689fdd083
+
 export type YieldQueryParams = [];
 
 export interface IYieldQueryResult {
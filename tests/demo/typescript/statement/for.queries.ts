@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: for.ts
+// hash: sha256:7c94fa955a9ceb5f3b8604aa77e3e54f639a85f9d4e663bd23e15fc8429880ee
+
 export type For1Params = [];
 
 export interface IFor1Result {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: function.ts
+// hash: sha256:2d77c2c7f434cb4d77c6bf4d293003bcb91c28d45267b8dd8c65bde5de6f197c
+
 export type FunctionAssignParams = [];
 
 export interface IFunctionAssignResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: if.ts
+// hash: sha256:2020aee50d8fb6ca4f369ecde22751e3061dba60ebba4ffd729433246ef9dc4b
+
 export type IfstmtParams = [];
 
 export interface IIfstmtResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: switch.ts
+// hash: sha256:3c943cba1a2de2673ac1bebf03bab3c4e93306e2d4cfcfedfd700868f29b6173
+
 export type Case1Params = [];
 
 export interface ICase1Result {
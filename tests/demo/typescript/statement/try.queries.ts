@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: try.ts
+// hash: sha256:b5af0460d144e63972e437ce28f9560d5578a0272aa651ebfe4cdb31d3e42b58
+
 export type Try2Params = [];
 
 export interface ITry2Result {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: using.ts
+// hash: sha256:c4585fb5aac9329589bbca0b05fe27a431decf615f27d0de9bd96d3c8ed4cfdf
+
 export type TestAsyncUsingParams = [];
 
 export interface ITestAsyncUsingResult {
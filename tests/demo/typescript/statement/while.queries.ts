@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: while.ts
+// hash: sha256:6c719bfe21208554b8e9b9b77540b1ad08588c972aed7e29f164402ec970609d
+
 export type WhileSqlParams = [boolean];
 
 export interface IWhileSqlResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: with.ts
+// hash: sha256:c1271497eca759a9b3751e2ee7dab2b3587c326e4ea367db532c1fab768ba725
+
 export type WithSqlParams = [boolean];
 
 export interface IWithSqlResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: weird.name.ts
+// hash: sha256:06cd0c843cd6ac753f94df9b4f20317968a01f6880299656246752fee672f5d1
+
 export type WeirdNameParams = [];
 
 export interface IWeirdNameResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: update.ts
+// hash: sha256:647276105b1719953a6e912c61c2945cbf34ff7f08390b2629582782d8ff9436
+
 export type UpdateQueryParams = [string, string | null, number];
 
 export interface IUpdateQueryResult {
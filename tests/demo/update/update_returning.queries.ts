@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: update_returning.ts
+// hash: sha256:76b958ac2c9f92c8e895c6c15b6d5c7fd900e84429cbba8d25c6169d93923180
+
 export type UpdateReturningAllParams = [string, number];
 
 export interface IUpdateReturningAllResult {
@@ -1,9 +1,13 @@
+// @generated by sqlx-ts - do not edit
+// source: lag_lead.ts
+// hash: sha256:0407be1bd576cbe80b12fd6373bc744a4cbea89913913200fcba9f863e39ac54
+
 export type BasicLagParams = [];
 
 export interface IBasicLagResult {
 	id: number;
 	name: string;
-	previousName: string;
+	previousName: string | null;
 }
 
 export interface IBasicLagQuery {
@@ -16,7 +20,7 @@ export type BasicLeadParams = [];
 export interface IBasicLeadResult {
 	id: number;
 	name: string;
-	nextName: string;
+	nextName: string | null;
 }
 
 export interface IBasicLeadQuery {
@@ -42,8 +46,8 @@ export type LagAndLeadParams = [];
 export interface ILagAndLeadResult {
 	id: number;
 	name: string;
-	nextName: string;
-	previousName: string;
+	nextName: string | null;
+	previousName: string | null;
 }
 
 export interface ILagAndLeadQuery {
@@ -56,7 +60,7 @@ export type LagWithPartitionParams = [];
 export interface ILagWithPartitionResult {
 	id: number;
 	name: string;
-	previousInRarity: string;
+	previousInRarity: string | null;
 	rarity: string | null;
 }
 
@@ -3,7 +3,7 @@ export type BasicLagParams = [];
 export interface IBasicLagResult {
 	id: number;
 	name: string;
-	previousName: string;
+	previousName: string | null;
 }
 
 export interface IBasicLagQuery {
@@ -16,7 +16,7 @@ export type BasicLeadParams = [];
 export interface IBasicLeadResult {
 	id: number;
 	name: string;
-	nextName: string;
+	nextName: string | null;
 }
 
 export interface IBasicLeadQuery {
@@ -42,8 +42,8 @@ export type LagAndLeadParams = [];
 export interface ILagAndLeadResult {
 	id: number;
 	name: string;
-	nextName: string;
-	previousName: string;
+	nextName: string | null;
+	previousName: string | null;
 }
 
 export interface ILagAndLeadQuery {
@@ -56,7 +56,7 @@ export type LagWithPartitionParams = [];
 export interface ILagWithPartitionResult {
 	id: number;
 	name: string;
-	previousInRarity: string;
+	previousInRarity: string | null;
 	rarity: string | null;
 }
 
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: partition_by.ts
+// hash: sha256:d102bcfefcc49f693890f617d672a7cf6208bfaa522c0c8ba1e60faf9afc0961
+
 export type SinglePartitionParams = [];
 
 export interface ISinglePartitionResult {
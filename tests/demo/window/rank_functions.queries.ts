@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: rank_functions.ts
+// hash: sha256:eb2704be8b8eadce87f12ba4ad7e4f72f8216b72e8ecc76f0ec0b7ce7b3c72a1
+
 export type BasicRankParams = [];
 
 export interface IBasicRankResult {
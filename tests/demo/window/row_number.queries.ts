@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: row_number.ts
+// hash: sha256:0dc9732ec622a33e3375f2a393adb5551cce35f2a614964cde82679f78b2ec22
+
 export type BasicRowNumberParams = [];
 
 export interface IBasicRowNumberResult {
@@ -31,7 +35,7 @@ export type RowNumberWithWhereParams = [];
 export interface IRowNumberWithWhereResult {
 	id: number;
 	name: string;
-	rarity: string | null;
+	rarity: string;
 	rowNum: number;
 }
 
@@ -31,7 +31,7 @@ export type RowNumberWithWhereParams = [];
 export interface IRowNumberWithWhereResult {
 	id: number;
 	name: string;
-	rarity: string | null;
+	rarity: string;
 	rowNum: number;
 }
 
@@ -7,8 +7,10 @@ mod demo_happy_path_tests {
   use std::fs;
   use std::io::Write;
   use std::path::Path;
+  use test_utils::test_utils::TSString;
   use walkdir::WalkDir;
 
+  #[allow(clippy::unnecessary_unwrap)]
   fn run_demo_test(demo_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     // EXECUTE - Generate types for .ts files
     let mut cmd = cargo_bin_cmd!("sqlx-ts");
@@ -27,7 +29,7 @@ mod demo_happy_path_tests {
     // Also generate types for other extensions in file_extensions directory
     let file_extensions_path = demo_path.join("file_extensions");
     if file_extensions_path.exists() {
-      for ext in &["js", "mts", "cts", "mjs", "cjs"] {
+      for ext in &["js", "mts", "cts", "mjs", "cjs", "tsx", "jsx", "vue", "svelte", "astro"] {
         let mut cmd = cargo_bin_cmd!("sqlx-ts");
         cmd
           .arg(file_extensions_path.to_str().unwrap())
@@ -69,7 +71,7 @@ mod demo_happy_path_tests {
           let base_file_name = base_file_name.first().unwrap();
           let snapshot_path = parent.join(format!("{base_file_name}.snapshot.ts"));
 
-          let generated_types = fs::read_to_string(path)?;
+          let generated_types = fs::read_to_string(path)?.strip_generated_header();
 
           if !snapshot_path.exists() {
             let mut snapshot_file = fs::File::create(&snapshot_path)?;
@@ -234,6 +236,126 @@ mod demo_happy_path_tests {
     Ok(())
   }
 
+  #[test]
+  fn test_tsx_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let root_path = current_dir().unwrap();
+    let demo_path = root_path.join("tests/demo/file_extensions");
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(demo_path.to_str().unwrap())
+      .arg("--ext=tsx")
+      .arg("--config=.sqlxrc.sample.json")
+      .arg("-g");
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_jsx_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let root_path = current_dir().unwrap();
+    let demo_path = root_path.join("tests/demo/file_extensions");
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(demo_path.to_str().unwrap())
+      .arg("--ext=jsx")
+      .arg("--config=.sqlxrc.sample.json")
+      .arg("-g");
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_vue_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let root_path = current_dir().unwrap();
+    let demo_path = root_path.join("tests/demo/file_extensions");
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(demo_path.to_str().unwrap())
+      .arg("--ext=vue")
+      .arg("--config=.sqlxrc.sample.json")
+      .arg("-g");
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_svelte_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let root_path = current_dir().unwrap();
+    let demo_path = root_path.join("tests/demo/file_extensions");
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(demo_path.to_str().unwrap())
+      .arg("--ext=svelte")
+      .arg("--config=.sqlxrc.sample.json")
+      .arg("-g");
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_astro_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let root_path = current_dir().unwrap();
+    let demo_path = root_path.join("tests/demo/file_extensions");
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(demo_path.to_str().unwrap())
+      .arg("--ext=astro")
+      .arg("--config=.sqlxrc.sample.json")
+      .arg("-g");
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
   #[test]
   fn test_sql_files() -> Result<(), Box<dyn std::error::Error>> {
     // SETUP
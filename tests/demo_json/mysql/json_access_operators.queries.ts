@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_access_operators.ts
+// hash: sha256:aecaf3f2b0f882054f89d064ff99ee22705ebb390ab0e4f6b27cc52cefa4be23
+
 export type JsonFieldAccessParams = [];
 
 export interface IJsonFieldAccessResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_array_functions.ts
+// hash: sha256:5a8afdd037deccd82c6f88758f97ce22b5184f2440b74f7b4d534be2f26bcd50
+
 export type JsonArrayLengthParams = [];
 
 export interface IJsonArrayLengthResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_comprehensive.ts
+// hash: sha256:3d00457b648eddd0353a1f9684cc44621cd2c6f40100b2786d1577f7c1af4a23
+
 export type JsonExtractParams = [];
 
 export interface IJsonExtractResult {
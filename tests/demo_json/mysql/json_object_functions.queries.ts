@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_object_functions.ts
+// hash: sha256:a915d31f711aaef51ae9a07b2641c974e2d569b6503950e33b56a524f6a2c5e7
+
 export type JsonObjectKeysParams = [];
 
 export interface IJsonObjectKeysResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_operations.ts
+// hash: sha256:0bdd172e00b4562fee4930e7c92964c989376e224a658cbac1f2a3bc4d227112
+
 export type JsonOperatorsSelectParams = [];
 
 export interface IJsonOperatorsSelectResult {
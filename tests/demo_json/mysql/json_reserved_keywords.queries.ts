@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_reserved_keywords.ts
+// hash: sha256:4a3c80b93a28c7745bd437813fb27ae62864bb7f18882ee77eeed3e56e637b9e
+
 export type JsonReservedKeywordsParams = [];
 
 export interface IJsonReservedKeywordsResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: array_operations.ts
+// hash: sha256:ea77163fd901050010f5a4e3b9cc8f029ae9dd4df4c20422e529a4b3eb4446b7
+
 export type ArrayAggBasicParams = [];
 
 export interface IArrayAggBasicResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_access_operators.ts
+// hash: sha256:bd8667dfce81e564df1fb0e5aaad2ebd304a49c707ee7f48b396b305a16bfb4a
+
 export type JsonFieldAccessParams = [];
 
 export interface IJsonFieldAccessResult {
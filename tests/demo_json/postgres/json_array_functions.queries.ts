@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_array_functions.ts
+// hash: sha256:af428887955fc6a9bfacffa9b3448e87376415deca5efb0064ec297335b94960
+
 export type JsonbArrayLengthParams = [];
 
 export interface IJsonbArrayLengthResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_comprehensive.ts
+// hash: sha256:194e5b7d8888af8e170bc6c5e52e7fc608706a67cb74f67d58d0f38d9fecddf1
+
 export type JsonAccessOperatorsParams = [];
 
 export interface IJsonAccessOperatorsResult {
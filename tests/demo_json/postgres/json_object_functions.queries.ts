@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_object_functions.ts
+// hash: sha256:bfd4be0c5e73e6f39c00558e6519c9138e486eacb7b8bff7a8cb1a35a5440cd1
+
 export type JsonbObjectKeysParams = [];
 
 export interface IJsonbObjectKeysResult {
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: json_reserved_keywords.ts
+// hash: sha256:805cdf859df835ff45f9753452143f1c34cc77facb5272ca7d8f0d7886569173
+
 export type JsonbReservedKeywordsParams = [];
 
 export interface IJsonbReservedKeywordsResult {
@@ -27,7 +31,7 @@ export interface IJsonbInvalidIdentifiersQuery {
 export type JsonbAggReservedKeywordsParams = [];
 
 export interface IJsonbAggReservedKeywordsResult {
-	aggregatedReservedKeywords: Array<{ "class": string; "interface": number; "default": boolean }>;
+	aggregatedReservedKeywords: Array<{ "class": string; "interface": number; "default": boolean }> | null;
 }
 
 export interface IJsonbAggReservedKeywordsQuery {
@@ -27,7 +27,7 @@ export interface IJsonbInvalidIdentifiersQuery {
 export type JsonbAggReservedKeywordsParams = [];
 
 export interface IJsonbAggReservedKeywordsResult {
-	aggregatedReservedKeywords: Array<{ "class": string; "interface": number; "default": boolean }>;
+	aggregatedReservedKeywords: Array<{ "class": string; "interface": number; "default": boolean }> | null;
 }
 
 export interface IJsonbAggReservedKeywordsQuery {
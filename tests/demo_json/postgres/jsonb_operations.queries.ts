@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: jsonb_operations.ts
+// hash: sha256:a1ca095a6a8db3534ec3b006d257bb3ca810ecbe8f34c3629f8a42adc6a37aaf
+
 export type JsonbBuildObjectBasicParams = [];
 
 export interface IJsonbBuildObjectBasicResult {
@@ -13,7 +17,7 @@ export interface IJsonbBuildObjectBasicQuery {
 export type JsonbAggregationParams = [];
 
 export interface IJsonbAggregationResult {
-	items: Array<{ id: number; name: string }>;
+	items: Array<{ id: number; name: string }> | null;
 	rarity: string | null;
 }
 
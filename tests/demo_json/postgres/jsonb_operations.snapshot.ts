@@ -13,7 +13,7 @@ export interface IJsonbBuildObjectBasicQuery {
 export type JsonbAggregationParams = [];
 
 export interface IJsonbAggregationResult {
-	items: Array<{ id: number; name: string }>;
+	items: Array<{ id: number; name: string }> | null;
 	rarity: string | null;
 }
 
@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: upsert.ts
+// hash: sha256:cd50d7a172d41049bc2e3a25b961295b0201f7949204bc3ee6d85b885a1aa176
+
 export type UpsertDoNothingParams = [number, string, string | null];
 
 export interface IUpsertDoNothingResult {
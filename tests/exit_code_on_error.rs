@@ -269,4 +269,140 @@ const query6 = sql`SELECT rarity FROM items WHERE id = $1;`;
 
     Ok(())
   }
+
+  fn run_postgres(parent_path: &std::path::Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .arg("--db-name=postgres")
+      .args(args);
+
+    cmd.assert()
+  }
+
+  /// Every file is checked before exiting, so a single run reports all the broken files
+  #[test]
+  fn should_check_every_file_and_print_a_summary() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("first.ts"),
+      "const firstQuery = sql`SELECT * FROM first_unknown_table`;",
+    )?;
+    fs::write(
+      parent_path.join("second.ts"),
+      "const secondQuery = sql`SELECT * FROM second_unknown_table`;\nconst validQuery = sql`SELECT id FROM items`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, &[])
+      .failure()
+      .stdout(predicates::str::contains("Scanned 2 files"))
+      .stdout(predicates::str::contains("Found 3 SQL queries"))
+      .stderr(predicates::str::contains(
+        "relation \"first_unknown_table\" does not exist",
+      ))
+      .stderr(predicates::str::contains(
+        "relation \"second_unknown_table\" does not exist",
+      ))
+      .stderr(predicates::str::contains("1 SQLs failed to compile in"))
+      .stderr(predicates::str::contains("first.ts"))
+      .stderr(predicates::str::contains("second.ts"))
+      .stderr(predicates::str::contains("SQLs failed to compile!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_stop_at_the_first_failing_file_with_fail_fast() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("first.ts"),
+      "const firstQuery = sql`SELECT * FROM first_unknown_table`;",
+    )?;
+    fs::write(
+      parent_path.join("second.ts"),
+      "const secondQuery = sql`SELECT * FROM second_unknown_table`;",
+    )?;
+
+    // EXECUTE
+    let output = run_postgres(parent_path, &["--fail-fast"])
+      .failure()
+      .stdout(predicates::str::contains("Scanned 1 files"))
+      .get_output()
+      .clone();
+
+    // ASSERT - only the file checked first is reported
+    let stderr = String::from_utf8(output.stderr)?;
+    assert_eq!(stderr.matches("does not exist").count(), 1);
+
+    Ok(())
+  }
+
+  /// A SQL that types cannot be generated for does not stop the types of other files from being generated
+  #[test]
+  fn should_generate_types_of_other_files_after_a_generation_error() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("first.ts"),
+      "function getItems() {\n  return sql`SELECT id FROM items`;\n}",
+    )?;
+    fs::write(
+      parent_path.join("second.ts"),
+      "const validQuery = sql`SELECT id FROM items`;",
+    )?;
+
+    // EXECUTE
+    let output = run_postgres(parent_path, &["-g"])
+      .failure()
+      .stderr(predicates::str::contains("Failed to generate types in"))
+      .stderr(predicates::str::contains("[E001] Unable to infer an appropriate name"))
+      .stderr(predicates::str::contains("SQLs failed to compile!"))
+      .get_output()
+      .clone();
+
+    // ASSERT - the error is reported once
+    let stderr = String::from_utf8(output.stderr)?;
+    assert_eq!(stderr.matches("Unable to infer an appropriate name").count(), 1);
+    assert!(parent_path.join("second.queries.ts").exists());
+
+    Ok(())
+  }
+
+  /// A `@db` annotation of a connection that is not configured fails its SQL instead of the whole run
+  #[test]
+  fn should_report_an_unknown_connection_on_its_sql() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "const unknownQuery = sql`\n-- @db: unknown\nSELECT id FROM items`;\nconst validQuery = sql`SELECT id FROM items`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, &["-g"])
+      .failure()
+      .stderr(predicates::str::contains(
+        "check the database name annotated in your query",
+      ))
+      .stderr(predicates::str::contains("connection: unknown"))
+      .stderr(predicates::str::contains("index.ts:1:1"))
+      .stderr(predicates::str::contains("1 SQLs failed to compile in"));
+
+    assert!(fs::read_to_string(parent_path.join("index.queries.ts"))?.contains("IValidQueryResult"));
+    Ok(())
+  }
 }
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod generated_files_tests {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use pretty_assertions::assert_eq;
+  use std::fs;
+  use std::path::Path;
+  use std::time::{Duration, SystemTime};
+  use tempfile::tempdir;
+
+  fn run_postgres(parent_path: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .arg("-g")
+      .args(args);
+
+    cmd.assert()
+  }
+
+  #[test]
+  fn should_record_the_source_file_and_a_hash_in_the_header() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    run_postgres(parent_path, &[]).success();
+
+    // ASSERT
+    let generated = fs::read_to_string(parent_path.join("index.queries.ts"))?;
+    let lines = generated.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "// @generated by sqlx-ts - do not edit");
+    assert_eq!(lines[1], "// source: index.ts");
+    assert!(lines[2].starts_with("// hash: sha256:"));
+    assert_eq!(lines[3], "");
+    assert_eq!(lines[4], "export type SomeQueryParams = [];");
+    Ok(())
+  }
+
+  #[test]
+  fn should_leave_files_untouched_when_their_content_has_not_changed() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let generated_file_path = parent_path.join("index.queries.ts");
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    run_postgres(parent_path, &[]).success();
+
+    let modified_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    fs::File::options()
+      .write(true)
+      .open(&generated_file_path)?
+      .set_modified(modified_time)?;
+
+    // EXECUTE
+    run_postgres(parent_path, &[]).success();
+
+    // ASSERT
+    assert_eq!(fs::metadata(&generated_file_path)?.modified()?, modified_time);
+    Ok(())
+  }
+
+  #[test]
+  fn should_order_the_single_file_by_the_path_of_the_source_file() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let generate_path = parent_path.join("types.ts");
+    let generate_path_arg = format!("--generate-path={}", generate_path.to_str().unwrap());
+    fs::write(
+      parent_path.join("b.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst firstQuery = sql`SELECT id FROM items`;\nconst secondQuery = sql`SELECT name FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("a.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst thirdQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    run_postgres(parent_path, &[&generate_path_arg]).success();
+    let generated = fs::read_to_string(&generate_path)?;
+    run_postgres(parent_path, &[&generate_path_arg]).success();
+
+    // ASSERT
+    let position = |name: &str| generated.find(name).unwrap();
+    assert!(generated.contains("// source: a.ts\n// source: b.ts\n"));
+    assert!(position("IThirdQueryResult") < position("IFirstQueryResult"));
+    assert!(position("IFirstQueryResult") < position("ISecondQueryResult"));
+    assert_eq!(fs::read_to_string(&generate_path)?, generated);
+    Ok(())
+  }
+
+  #[test]
+  fn should_remove_generated_files_whose_source_has_no_sqls_left() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("removed.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    // Files that were not generated by sqlx-ts are never removed
+    fs::write(parent_path.join("manual.queries.ts"), "export type Manual = [];\n")?;
+    run_postgres(parent_path, &[]).success();
+
+    fs::write(parent_path.join("index.ts"), "export const items = [];\n")?;
+    fs::remove_file(parent_path.join("removed.ts"))?;
+
+    // EXECUTE
+    run_postgres(parent_path, &[])
+      .success()
+      .stdout(predicates::str::contains(
+        "index.queries.ts\" as its source file has no SQLs left",
+      ))
+      .stdout(predicates::str::contains(
+        "removed.queries.ts\" as its source file has no SQLs left",
+      ));
+
+    // ASSERT
+    assert!(!parent_path.join("index.queries.ts").exists());
+    assert!(!parent_path.join("removed.queries.ts").exists());
+    assert!(parent_path.join("manual.queries.ts").exists());
+    Ok(())
+  }
+
+  #[test]
+  fn should_report_the_files_it_would_write_or_remove_in_a_dry_run() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("removed.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    run_postgres(parent_path, &[]).success();
+    let index_types = fs::read_to_string(parent_path.join("index.queries.ts"))?;
+
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id, name FROM items`;\n",
+    )?;
+    fs::remove_file(parent_path.join("removed.ts"))?;
+
+    // EXECUTE
+    run_postgres(parent_path, &["--dry-run"])
+      .success()
+      .stdout(predicates::str::contains("index.queries.ts\" would be written"))
+      .stdout(predicates::str::contains(
+        "removed.queries.ts\" would be removed as its source file has no SQLs left",
+      ));
+
+    // ASSERT
+    assert_eq!(fs::read_to_string(parent_path.join("index.queries.ts"))?, index_types);
+    assert!(parent_path.join("removed.queries.ts").exists());
+    Ok(())
+  }
+}
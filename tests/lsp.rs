@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod lsp_tests {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use serde_json::{json, Value};
+  use std::fs;
+  use tempfile::tempdir;
+
+  /// Frames the messages the way a client writes them to the stdin of the server
+  fn get_stdin(messages: &[Value]) -> Vec<u8> {
+    messages
+      .iter()
+      .flat_map(|message| {
+        let content = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{content}", content.len()).into_bytes()
+      })
+      .collect()
+  }
+
+  /// Messages the server wrote to its stdout
+  fn get_messages(stdout: &[u8]) -> Vec<Value> {
+    String::from_utf8(stdout.to_vec())
+      .unwrap()
+      .split("Content-Length: ")
+      .filter(|message| !message.is_empty())
+      .map(|message| serde_json::from_str(message.split_once("\r\n\r\n").unwrap().1).unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn should_publish_diagnostics_and_show_types_on_hover() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let file_path = dir.path().join("index.ts");
+    fs::write(
+      &file_path,
+      "import { sql } from \"sqlx-ts\";\n\nconst someQuery = sql`SELECT id FROM itmes`;\nconst items = sql`SELECT id, name FROM items WHERE id = $1`;\n",
+    )?;
+    let uri = format!("file://{}", file_path.to_str().unwrap());
+
+    let stdin = get_stdin(&[
+      json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+      json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+      json!({ "jsonrpc": "2.0", "method": "textDocument/didSave", "params": { "textDocument": { "uri": uri } } }),
+      json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "textDocument/hover",
+        "params": { "textDocument": { "uri": uri }, "position": { "line": 3, "character": 25 } },
+      }),
+      json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+      json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]);
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg("lsp")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .write_stdin(stdin);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let messages = get_messages(&output);
+
+    // ASSERT
+    assert_eq!(messages[0]["result"]["capabilities"]["hoverProvider"], true);
+
+    let diagnostics = &messages[1]["params"]["diagnostics"];
+    assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["code"], "42P01");
+    assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 2, "character": 37 }));
+    assert_eq!(
+      diagnostics[0]["message"],
+      "relation \"itmes\" does not exist\nhelp: did you mean `items`?"
+    );
+
+    let hover = messages[2]["result"]["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("export type ItemsParams = [number];"));
+    assert!(hover.contains("export interface IItemsResult {\n\tid: number;\n\tname: string;\n}"));
+
+    assert_eq!(messages[3], json!({ "jsonrpc": "2.0", "id": 3, "result": null }));
+    Ok(())
+  }
+
+  #[test]
+  fn should_exit_with_code_1_without_a_shutdown_request() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg("lsp")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .write_stdin(get_stdin(&[json!({ "jsonrpc": "2.0", "method": "exit" })]));
+
+    cmd.assert().failure().code(1);
+    Ok(())
+  }
+}
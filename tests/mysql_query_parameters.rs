@@ -142,6 +142,62 @@ export interface ISomeQueryResult {
     rarity: string | null;
 }
 
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+  #[rustfmt::skip]
+run_test!(should_pick_query_params_from_join_constraints_and_limit, TestConfig::new("mysql", true, None, None),
+
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT items.id, items.name
+FROM items
+JOIN inventory ON inventory.id = items.inventory_id AND quantity > ?
+WHERE items.rarity = ?
+ORDER BY items.id
+LIMIT ?, ?
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number | null, string | null, number, number];
+
+export interface ISomeQueryResult {
+    items_id: number;
+    items_name: string;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+  #[rustfmt::skip]
+run_test!(should_pick_query_params_from_union_branches_in_order, TestConfig::new("mysql", true, None, None),
+
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT id, name FROM items WHERE rarity = ?
+UNION ALL
+SELECT id, name FROM characters WHERE level > ?
+LIMIT ?
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [string | null, number | null, number];
+
+export interface ISomeQueryResult {
+    id: number;
+    name: string;
+}
+
 export interface ISomeQueryQuery {
     params: SomeQueryParams;
     result: ISomeQueryResult;
@@ -0,0 +1,218 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[cfg(test)]
+mod offline_snapshot_tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+
+  /// Runs `sqlx-ts snapshot` against the playpen postgres database
+  fn write_snapshot(dir_path: &Path, snapshot_path: &Path) {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg("snapshot")
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg(format!("--snapshot-path={}", snapshot_path.to_str().unwrap()))
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .arg("--db-name=postgres");
+
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Schema snapshot written to"));
+  }
+
+  /// Runs sqlx-ts in offline mode, the database connection details point to a port nothing listens on
+  fn run_offline(dir_path: &Path, snapshot_path: &Path) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--offline")
+      .arg("-g")
+      .arg(format!("--snapshot-path={}", snapshot_path.to_str().unwrap()))
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=1")
+      .arg("--db-user=postgres");
+
+    cmd.assert()
+  }
+
+  #[test]
+  fn should_snapshot_only_tables_touched_by_queries() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let snapshot_path = dir_path.join("snapshot.json");
+
+    fs::write(
+      dir_path.join("index.ts"),
+      r#"
+const someQuery = sql`
+SELECT characters.id AS id, races.name AS race
+FROM characters
+JOIN races ON races.id = characters.race_id
+WHERE characters.id = $1
+`;
+"#,
+    )?;
+
+    // EXECUTE
+    write_snapshot(dir_path, &snapshot_path);
+
+    // ASSERT
+    let snapshot: serde_json::Value = serde_json::from_str(&fs::read_to_string(&snapshot_path)?)?;
+    let default_connection = &snapshot["connections"]["default"];
+    assert_eq!(default_connection["db_type"], "postgres");
+
+    let tables = default_connection["tables"].as_object().unwrap();
+    assert_eq!(tables.keys().collect::<Vec<_>>(), vec!["characters", "races"]);
+    assert_eq!(
+      tables["characters"]["id"],
+      serde_json::json!({ "data_type": "integer", "is_nullable": false })
+    );
+    assert_eq!(tables["races"]["name"]["enum_values"][0], "human");
+    // Generating types is not part of writing a snapshot
+    assert!(!dir_path.join("index.queries.ts").exists());
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_generate_types_offline_from_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let snapshot_path = dir_path.join("snapshot.json");
+
+    fs::write(
+      dir_path.join("index.ts"),
+      r#"
+const someQuery = sql`
+SELECT characters.name AS name, characters.level AS level, races.name AS race
+FROM characters
+JOIN races ON races.id = characters.race_id
+WHERE characters.id = $1
+`;
+"#,
+    )?;
+    write_snapshot(dir_path, &snapshot_path);
+
+    // EXECUTE
+    run_offline(dir_path, &snapshot_path)
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    // ASSERT
+    let generated = fs::read_to_string(dir_path.join("index.queries.ts"))?;
+    assert_eq!(
+      generated.strip_generated_header().replace('\t', "    "),
+      r#"export type SomeQueryParams = [number];
+
+export interface ISomeQueryResult {
+    level: number | null;
+    name: string;
+    race: 'human' | 'night elf' | 'dwarf' | 'gnome' | 'orc' | 'troll' | 'tauren' | 'undead';
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_offline_for_tables_and_columns_missing_from_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let snapshot_path = dir_path.join("snapshot.json");
+    let file_path = dir_path.join("index.ts");
+
+    fs::write(&file_path, "const someQuery = sql`SELECT id FROM items`;")?;
+    write_snapshot(dir_path, &snapshot_path);
+
+    fs::write(
+      &file_path,
+      r#"
+const unknownTable = sql`SELECT id FROM characters`;
+const unknownColumn = sql`SELECT unknown_column FROM items`;
+const misspelledTable = sql`SELECT id FROM itmes`;
+"#,
+    )?;
+
+    // EXECUTE & ASSERT
+    run_offline(dir_path, &snapshot_path)
+      .failure()
+      .stderr(predicates::str::contains(
+        "relation \"characters\" does not exist in the schema snapshot",
+      ))
+      .stderr(predicates::str::contains(
+        "Column 'unknown_column' not found in table 'items'",
+      ))
+      .stderr(predicates::str::contains(
+        "relation \"itmes\" does not exist in the schema snapshot",
+      ))
+      .stderr(predicates::str::contains("help: did you mean `items`?"))
+      .stderr(predicates::str::contains("SQLs failed to compile!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_offline_without_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    fs::write(
+      dir_path.join("index.ts"),
+      "const someQuery = sql`SELECT id FROM items`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_offline(dir_path, &dir_path.join("missing.json"))
+      .failure()
+      .stderr(predicates::str::contains("Failed to read the schema snapshot"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_offline_for_connection_missing_from_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let snapshot_path = dir_path.join("snapshot.json");
+    fs::write(
+      dir_path.join("index.ts"),
+      "const someQuery = sql`SELECT id FROM items`;",
+    )?;
+    fs::write(
+      &snapshot_path,
+      r#"{ "connections": { "other": { "db_type": "postgres", "tables": {} } } }"#,
+    )?;
+
+    // EXECUTE & ASSERT
+    run_offline(dir_path, &snapshot_path)
+      .failure()
+      .stderr(predicates::str::contains(
+        "The connection 'default' is not in the schema snapshot",
+      ))
+      .stderr(predicates::str::contains("run `sqlx-ts snapshot` again"));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod output_format_tests {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use std::fs;
+  use std::path::Path;
+  use tempfile::tempdir;
+
+  const INDEX_CONTENT: &str = r#"import { sql } from "sqlx-ts";
+
+const someQuery = sql`SELECT id FROM itmes`;
+
+foo(sql`SELECT id FROM items`);
+"#;
+
+  fn run_postgres(parent_path: &Path, format: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .arg("-g")
+      .arg(format!("--format={format}"));
+
+    cmd.assert()
+  }
+
+  #[test]
+  fn should_print_every_error_as_json() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(parent_path.join("index.ts"), INDEX_CONTENT)?;
+
+    // EXECUTE
+    let assert = run_postgres(parent_path, "json").failure();
+
+    // ASSERT - stdout only holds the report
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let diagnostics: serde_json::Value = serde_json::from_str(&stdout)?;
+    let diagnostics = diagnostics.as_array().unwrap();
+    assert_eq!(diagnostics.len(), 2);
+
+    let db_error = &diagnostics[0];
+    assert!(db_error["file"].as_str().unwrap().ends_with("index.ts"));
+    assert_eq!(db_error["line"], 3);
+    assert_eq!(db_error["column"], 38);
+    assert_eq!(db_error["queryName"], "SomeQuery");
+    assert_eq!(db_error["code"], "42P01");
+    assert_eq!(db_error["message"], "relation \"itmes\" does not exist");
+    assert_eq!(db_error["notes"][0], "help: did you mean `items`?");
+
+    let generation_error = &diagnostics[1];
+    assert_eq!(generation_error["line"], 5);
+    assert_eq!(generation_error["code"], "E001");
+    assert_eq!(generation_error["queryName"], serde_json::Value::Null);
+    Ok(())
+  }
+
+  #[test]
+  fn should_print_every_error_as_sarif() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(parent_path.join("index.ts"), INDEX_CONTENT)?;
+
+    // EXECUTE
+    let assert = run_postgres(parent_path, "sarif").failure();
+
+    // ASSERT
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let log: serde_json::Value = serde_json::from_str(&stdout)?;
+    let results = log["runs"][0]["results"].as_array().unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "sqlx-ts");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "42P01");
+    assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+    assert_eq!(results[0]["properties"]["queryName"], "SomeQuery");
+    assert_eq!(results[1]["ruleId"], "E001");
+    Ok(())
+  }
+
+  #[test]
+  fn should_print_every_error_as_github_annotations() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(parent_path.join("index.ts"), INDEX_CONTENT)?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, "github")
+      .failure()
+      .stdout(predicates::str::contains(
+        "line=3,col=38,endLine=3,endColumn=43,title=sqlx-ts 42P01 in SomeQuery::relation \"itmes\" does not exist%0Ahelp: did you mean `items`?",
+      ))
+      .stdout(predicates::str::contains(
+        "line=5,col=1,endLine=5,endColumn=32,title=sqlx-ts E001::Unable to infer an appropriate name for the query",
+      ));
+    Ok(())
+  }
+
+  #[test]
+  fn should_print_an_empty_report_without_errors() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("index.ts"),
+      "import { sql } from \"sqlx-ts\";\n\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_postgres(parent_path, "json")
+      .success()
+      .stdout(predicates::str::diff("[]\n"));
+    Ok(())
+  }
+}
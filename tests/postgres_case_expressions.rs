@@ -0,0 +1,71 @@
+/// Test suites for result types of CASE expressions and placeholders within them
+#[cfg(test)]
+mod postgres_case_expressions {
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_type_case_as_union_of_its_branches, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const caseTypes = sql`
+SELECT
+  CASE WHEN id > 1 THEN 'high' ELSE 'low' END AS tier,
+  CASE WHEN id > 1 THEN name END AS high_name,
+  CASE WHEN id > 1 THEN id ELSE NULL END AS high_id
+FROM items
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type CaseTypesParams = [];
+
+export interface ICaseTypesResult {
+	high_id: number | null;
+	high_name: string | null;
+	tier: string;
+}
+
+export interface ICaseTypesQuery {
+	params: CaseTypesParams;
+	result: ICaseTypesResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_pick_up_placeholders_of_case_in_order, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const caseParams = sql`
+SELECT
+  CASE WHEN id > $1 THEN 'high' ELSE 'low' END AS tier,
+  CASE rarity WHEN $2 THEN 'match' ELSE 'other' END AS rarity_match
+FROM items
+WHERE id > $3
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type CaseParamsParams = [number, string | null, number];
+
+export interface ICaseParamsResult {
+	rarity_match: string;
+	tier: string;
+}
+
+export interface ICaseParamsQuery {
+	params: CaseParamsParams;
+	result: ICaseParamsResult;
+}
+"#
+);
+}
@@ -0,0 +1,251 @@
+/// Test suites for nullability of result fields through outer joins, COALESCE / NULLIF, aggregates, CASE and
+/// `IS NOT NULL` filters
+#[cfg(test)]
+mod postgres_nullability {
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_make_columns_of_left_joined_tables_nullable, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const leftJoin = sql`
+SELECT items.id AS item_id, inv.quantity AS quantity, inv.id AS inventory_id
+FROM items
+LEFT JOIN inventory inv ON items.inventory_id = inv.id
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type LeftJoinParams = [];
+
+export interface ILeftJoinResult {
+	inventory_id: number | null;
+	item_id: number;
+	quantity: number | null;
+}
+
+export interface ILeftJoinQuery {
+	params: LeftJoinParams;
+	result: ILeftJoinResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_narrow_columns_filtered_by_is_not_null, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const narrowed = sql`
+SELECT items.id AS item_id, inv.id AS inventory_id, quantity
+FROM inventory inv
+RIGHT JOIN items ON items.inventory_id = inv.id
+WHERE inv.id IS NOT NULL AND inv.quantity IS NOT NULL
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type NarrowedParams = [];
+
+export interface INarrowedResult {
+	inventory_id: number;
+	item_id: number;
+	quantity: number;
+}
+
+export interface INarrowedQuery {
+	params: NarrowedParams;
+	result: INarrowedResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_infer_nullability_of_coalesce_and_nullif, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const coalesce = sql`
+SELECT COALESCE(rarity, 'common') AS rarity, COALESCE(rarity, flavor_text) AS description, NULLIF(name, '') AS name
+FROM items
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type CoalesceParams = [];
+
+export interface ICoalesceResult {
+	description: string | null;
+	name: string | null;
+	rarity: string;
+}
+
+export interface ICoalesceQuery {
+	params: CoalesceParams;
+	result: ICoalesceResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_make_aggregates_over_empty_sets_nullable, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const aggregates = sql`
+SELECT COUNT(*) AS total, SUM(quantity) AS total_quantity, MAX(id) AS max_id FROM inventory
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type AggregatesParams = [];
+
+export interface IAggregatesResult {
+	max_id: number | null;
+	total: number;
+	total_quantity: number | null;
+}
+
+export interface IAggregatesQuery {
+	params: AggregatesParams;
+	result: IAggregatesResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_make_case_without_else_nullable, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const caseWithoutElse = sql`
+SELECT CASE WHEN id > $1 THEN 'high' END AS tier, CASE WHEN id > 1 THEN 'high' ELSE 'low' END AS tier_with_else
+FROM items
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type CaseWithoutElseParams = [number];
+
+export interface ICaseWithoutElseResult {
+	tier: string | null;
+	tier_with_else: string;
+}
+
+export interface ICaseWithoutElseQuery {
+	params: CaseWithoutElseParams;
+	result: ICaseWithoutElseResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_type_min_and_max_as_their_argument, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const minMax = sql`
+SELECT MAX(name) AS last_name, MIN(rarity) AS first_rarity FROM items
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type MinMaxParams = [];
+
+export interface IMinMaxResult {
+	first_rarity: string | null;
+	last_name: string | null;
+}
+
+export interface IMinMaxQuery {
+	params: MinMaxParams;
+	result: IMinMaxResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_keep_aggregates_of_not_null_arguments_over_groups_not_null, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const groupedAggregates = sql`
+SELECT rarity, MAX(name) AS last_name, SUM(id) AS total_id, MAX(flavor_text) AS last_flavor_text
+FROM items
+GROUP BY rarity
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type GroupedAggregatesParams = [];
+
+export interface IGroupedAggregatesResult {
+	last_flavor_text: string | null;
+	last_name: string;
+	rarity: string | null;
+	total_id: number;
+}
+
+export interface IGroupedAggregatesQuery {
+	params: GroupedAggregatesParams;
+	result: IGroupedAggregatesResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_resolve_table_of_columns_filtered_by_is_not_null, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const resolvedNarrowing = sql`
+SELECT items.id AS item_id, inv.quantity AS quantity, inv.character_id AS character_id
+FROM items
+LEFT JOIN public.inventory inv ON items.inventory_id = inv.id
+WHERE quantity IS NOT NULL
+`;
+
+const qualifiedNarrowing = sql`
+SELECT items.id AS item_id, inventory.quantity AS quantity
+FROM items
+LEFT JOIN public.inventory ON items.inventory_id = inventory.id
+WHERE public.inventory.quantity IS NOT NULL
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type ResolvedNarrowingParams = [];
+
+export interface IResolvedNarrowingResult {
+	character_id: number | null;
+	item_id: number;
+	quantity: number;
+}
+
+export interface IResolvedNarrowingQuery {
+	params: ResolvedNarrowingParams;
+	result: IResolvedNarrowingResult;
+}
+
+export type QualifiedNarrowingParams = [];
+
+export interface IQualifiedNarrowingResult {
+	item_id: number;
+	quantity: number;
+}
+
+export interface IQualifiedNarrowingQuery {
+	params: QualifiedNarrowingParams;
+	result: IQualifiedNarrowingResult;
+}
+"#
+);
+}
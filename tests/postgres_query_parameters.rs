@@ -33,6 +33,93 @@ export interface ISomeQueryResult {
     quantity: number | null;
 }
 
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+  #[rustfmt::skip]
+run_test!(should_turn_template_interpolations_into_query_params, TestConfig::new("postgres", true, None, None),
+
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT id, quantity
+FROM inventory
+WHERE quantity > ${minQuantity}
+AND character_id = ${characterId}
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number | null, number | null];
+
+export interface ISomeQueryResult {
+    id: number;
+    quantity: number | null;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+  #[rustfmt::skip]
+run_test!(should_pick_query_params_from_join_constraints, TestConfig::new("postgres", true, None, None),
+
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT items.id, items.name
+FROM items
+JOIN inventory ON inventory.id = items.inventory_id AND quantity > $1
+JOIN characters c ON c.id = inventory.character_id AND c.name = $2
+WHERE items.rarity = $3
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number | null, string, string | null];
+
+export interface ISomeQueryResult {
+    items_id: number;
+    items_name: string;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+
+  #[rustfmt::skip]
+run_test!(should_pick_query_params_from_having_order_by_limit_and_offset, TestConfig::new("postgres", true, None, None),
+
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT character_id, SUM(quantity) AS total_quantity
+FROM inventory
+WHERE quantity > $1
+GROUP BY character_id
+HAVING COUNT(*) > $2
+ORDER BY total_quantity DESC
+LIMIT $3
+OFFSET $4
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number | null, number, number, number];
+
+export interface ISomeQueryResult {
+    character_id: number | null;
+    total_quantity: number | null;
+}
+
 export interface ISomeQueryQuery {
     params: SomeQueryParams;
     result: ISomeQueryResult;
@@ -0,0 +1,116 @@
+/// Test suites for UNION, INTERSECT and EXCEPT query bodies
+#[cfg(test)]
+mod postgres_set_operations {
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_make_union_fields_nullable_if_any_branch_is, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const unionNullability = sql`
+SELECT id, name, rarity FROM items
+UNION ALL
+SELECT id, name, NULL AS rarity FROM characters
+UNION ALL
+SELECT id, description, name FROM quests
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type UnionNullabilityParams = [];
+
+export interface IUnionNullabilityResult {
+	id: number;
+	name: string | null;
+	rarity: string | null;
+}
+
+export interface IUnionNullabilityQuery {
+	params: UnionNullabilityParams;
+	result: IUnionNullabilityResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_keep_param_order_across_union_branches, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const unionParams = sql`
+SELECT id, name FROM items WHERE rarity = $1
+UNION
+SELECT id, name FROM characters WHERE level > $2
+UNION
+SELECT id, name FROM quests WHERE completed = $3
+LIMIT $4
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type UnionParamsParams = [string | null, number | null, boolean | null, number];
+
+export interface IUnionParamsResult {
+	id: number;
+	name: string;
+}
+
+export interface IUnionParamsQuery {
+	params: UnionParamsParams;
+	result: IUnionParamsResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_take_fields_of_first_branch_of_except_and_intersect, TestConfig::new("postgres", true, None, None),
+//// TS query ////
+r#"
+const except = sql`
+SELECT id, name FROM items
+EXCEPT
+SELECT inventory_id, rarity FROM items WHERE rarity = $1
+`;
+
+const intersect = sql`
+SELECT id AS item_id FROM items
+INTERSECT
+SELECT inventory_id AS inventory_id FROM items WHERE flavor_text = $1
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type ExceptParams = [string | null];
+
+export interface IExceptResult {
+	id: number;
+	name: string;
+}
+
+export interface IExceptQuery {
+	params: ExceptParams;
+	result: IExceptResult;
+}
+
+export type IntersectParams = [string | null];
+
+export interface IIntersectResult {
+	item_id: number;
+}
+
+export interface IIntersectQuery {
+	params: IntersectParams;
+	result: IIntersectResult;
+}
+"#
+);
+}
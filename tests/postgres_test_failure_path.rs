@@ -230,4 +230,140 @@ VALUES ('steak', 'normal', 'asd', 1);
       js_fails_to_find_an_unknown_table_using_aliased_import: "js",
       ts_fails_to_find_an_unknown_table_using_aliased_import: "ts",
   }
+
+  macro_rules! fails_at_the_line_of_the_single_file_component {
+($($name:ident: ($ext:expr, $content:expr, $location:expr),)*) => {
+$(
+// MACRO STARTS
+
+    #[test]
+    fn $name() -> Result<(), Box<dyn std::error::Error>> {
+        // SETUP
+        let dir = tempdir()?;
+        let parent_path = dir.path();
+        let file_path = parent_path.join(format!("Component.{}", $ext));
+        fs::write(&file_path, $content)?;
+
+        // EXECUTE
+        let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+        cmd.arg(parent_path.to_str().unwrap())
+            .arg(format!("--ext={}", $ext))
+            .arg("--db-type=postgres")
+            .arg("--db-host=127.0.0.1")
+            .arg("--db-port=54321")
+            .arg("--db-user=postgres")
+            .arg("--db-pass=postgres");
+
+        // ASSERT
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains("relation \"sfc_unknown\" does not exist"))
+            .stderr(predicates::str::contains(format!("Component.{}:{}", $ext, $location)));
+        Ok(())
+    }
+
+// MACRO ENDS
+)*};}
+
+  fails_at_the_line_of_the_single_file_component! {
+      vue_fails_at_the_line_of_the_component: ("vue", r#"<template>
+  <p>{{ rows }}</p>
+</template>
+
+<script setup lang="ts">
+import { sql } from "sqlx-ts";
+
+const someQuery = sql`SELECT * FROM sfc_unknown`;
+</script>
+"#, "8:37"),
+      svelte_fails_at_the_line_of_the_component: ("svelte", r#"<h1>Items</h1>
+
+<script lang="ts">
+  import { sql } from "sqlx-ts";
+  const someQuery = sql`SELECT * FROM sfc_unknown`;
+</script>
+"#, "5:39"),
+      astro_fails_at_the_line_of_the_component: ("astro", r#"---
+import { sql } from "sqlx-ts";
+
+const someQuery = sql`SELECT * FROM sfc_unknown`;
+---
+<h1>Items</h1>
+"#, "4:37"),
+  }
+
+  #[test]
+  fn points_at_the_offending_token_within_the_template_literal() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join("index.ts");
+
+    let index_content = r#"import { sql } from "sqlx-ts";
+
+const someQuery = sql`
+  SELECT id, name
+  FROM items
+  WHERE unknown_column = ${id}
+`;
+"#;
+    fs::write(&file_path, index_content)?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres");
+
+    // ASSERT
+    cmd
+      .assert()
+      .failure()
+      .stderr(predicates::str::contains("column \"unknown_column\" does not exist"))
+      .stderr(predicates::str::contains("index.ts:6:9"))
+      .stderr(predicates::str::contains("  WHERE unknown_column = ${id}"))
+      .stderr(predicates::str::contains("^^^^^^^^^^^^^^"));
+    Ok(())
+  }
+
+  #[test]
+  fn reports_the_sqlstate_and_suggests_tables_close_to_an_unknown_table() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join("index.ts");
+    fs::write(
+      &file_path,
+      "import { sql } from \"sqlx-ts\";\n\nconst someQuery = sql`SELECT id FROM itmes`;\n",
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres");
+
+    // ASSERT
+    cmd
+      .assert()
+      .failure()
+      .stderr(predicates::str::contains(
+        "error[42P01]: relation \"itmes\" does not exist",
+      ))
+      .stderr(predicates::str::contains("help: did you mean `items`?"));
+    Ok(())
+  }
 }
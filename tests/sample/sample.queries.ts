@@ -1,3 +1,7 @@
+// @generated by sqlx-ts - do not edit
+// source: sample.ts
+// hash: sha256:1cfbbd70c680050e98e8fef0fb2342fe4a8bb4c3ae7dd992e659ac302161076f
+
 export type SampleSelectQueryParams = [number];
 
 export interface ISampleSelectQueryResult {
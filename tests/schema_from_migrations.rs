@@ -0,0 +1,363 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[cfg(test)]
+mod schema_from_migrations_tests {
+  use super::*;
+  use predicates::prelude::PredicateBooleanExt;
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+
+  /// Runs sqlx-ts with the schema built from migrations, the database connection details point to a port nothing listens on
+  fn run_with_schema_from(dir_path: &Path, db_type: &str, schema_from: &Path) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("-g")
+      .arg(format!("--schema-from={}", schema_from.to_str().unwrap()))
+      .arg(format!("--db-type={db_type}"))
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=1")
+      .arg("--db-user=root");
+
+    cmd.assert()
+  }
+
+  fn read_generated_types(file_path: &Path) -> String {
+    fs::read_to_string(file_path)
+      .unwrap()
+      .strip_generated_header()
+      .replace('\t', "    ")
+  }
+
+  #[test]
+  fn should_generate_types_from_playpen_postgres_migration() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let migration = Path::new(env!("CARGO_MANIFEST_DIR")).join("playpen/db/postgres_migration.sql");
+
+    fs::write(
+      dir_path.join("index.ts"),
+      r#"
+const someQuery = sql`
+SELECT characters.name AS name, characters.level AS level, races.name AS race
+FROM characters
+JOIN races ON races.id = characters.race_id
+WHERE characters.id = $1
+`;
+"#,
+    )?;
+
+    // EXECUTE
+    run_with_schema_from(&dir_path, "postgres", &migration)
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type SomeQueryParams = [number];
+
+export interface ISomeQueryResult {
+    level: number | null;
+    name: string;
+    race: 'human' | 'night elf' | 'dwarf' | 'gnome' | 'orc' | 'troll' | 'tauren' | 'undead';
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_replay_postgres_migrations_in_order() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    let migrations_path = dir.path().join("migrations");
+    fs::create_dir(&dir_path)?;
+    fs::create_dir_all(migrations_path.join("0002_rename"))?;
+
+    fs::write(
+      migrations_path.join("0001_init.sql"),
+      r#"
+CREATE TYPE mood AS ENUM ('happy', 'sad');
+
+CREATE TABLE pets (
+  id SERIAL,
+  nickname VARCHAR(20),
+  legs INTEGER,
+  PRIMARY KEY (id)
+);
+"#,
+    )?;
+    fs::write(
+      migrations_path.join("0002_rename/up.sql"),
+      r#"
+ALTER TABLE pets RENAME COLUMN nickname TO name;
+ALTER TABLE pets ALTER COLUMN name SET NOT NULL;
+ALTER TABLE pets ADD COLUMN current_mood mood;
+ALTER TYPE mood ADD VALUE 'sleepy';
+"#,
+    )?;
+    // Down migrations revert the schema, so they must never be applied
+    fs::write(migrations_path.join("0002_rename/down.sql"), "DROP TABLE pets;")?;
+    fs::write(
+      migrations_path.join("0003_drop_legs.sql"),
+      "ALTER TABLE pets DROP COLUMN legs;",
+    )?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const petQuery = sql`SELECT id, name, current_mood FROM pets WHERE name = $1`;",
+    )?;
+
+    // EXECUTE
+    run_with_schema_from(&dir_path, "postgres", &migrations_path).success();
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type PetQueryParams = [string];
+
+export interface IPetQueryResult {
+    current_mood: 'happy' | 'sad' | 'sleepy' | null;
+    id: number;
+    name: string;
+}
+
+export interface IPetQueryQuery {
+    params: PetQueryParams;
+    result: IPetQueryResult;
+}
+"#
+    );
+
+    // A column dropped by a migration should be reported
+    fs::write(
+      dir_path.join("index.ts"),
+      "const petQuery = sql`SELECT legs FROM pets`;",
+    )?;
+    run_with_schema_from(&dir_path, "postgres", &migrations_path)
+      .failure()
+      .stderr(predicates::str::contains("Column 'legs' not found in table 'pets'"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_replay_migrations_without_zero_padded_versions_in_numeric_order() -> Result<(), Box<dyn std::error::Error>>
+  {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    let migrations_path = dir.path().join("migrations");
+    fs::create_dir(&dir_path)?;
+    fs::create_dir(&migrations_path)?;
+
+    fs::write(
+      migrations_path.join("2_create_pets.sql"),
+      "CREATE TABLE pets (id SERIAL PRIMARY KEY, nickname VARCHAR(20));",
+    )?;
+    // Sorted by their names, this migration would run before the table exists
+    fs::write(
+      migrations_path.join("10_rename_nickname.sql"),
+      "ALTER TABLE pets RENAME COLUMN nickname TO name;",
+    )?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const petQuery = sql`SELECT id, name FROM pets`;",
+    )?;
+
+    // EXECUTE
+    run_with_schema_from(&dir_path, "postgres", &migrations_path).success();
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type PetQueryParams = [];
+
+export interface IPetQueryResult {
+    id: number;
+    name: string | null;
+}
+
+export interface IPetQueryQuery {
+    params: PetQueryParams;
+    result: IPetQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_with_the_migration_that_cannot_be_read() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    let migrations_path = dir.path().join("migrations");
+    fs::create_dir(&dir_path)?;
+    fs::create_dir(&migrations_path)?;
+
+    fs::write(
+      migrations_path.join("1_broken.sql"),
+      "CREATE TABLE pets (name VARCHAR(20) DEFAULT 'unterminated);",
+    )?;
+    fs::write(
+      dir_path.join("index.ts"),
+      "const petQuery = sql`SELECT name FROM pets`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_with_schema_from(&dir_path, "postgres", &migrations_path)
+      .failure()
+      .stderr(predicates::str::contains("Failed to parse the migration"))
+      .stderr(predicates::str::contains("1_broken.sql"))
+      .stderr(predicates::str::contains("panicked").not());
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_resolve_schema_qualified_tables_and_search_path() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let migration = dir.path().join("schemas.sql");
+
+    fs::write(
+      &migration,
+      r#"
+CREATE TYPE billing.invoice_status AS ENUM ('draft', 'paid');
+
+CREATE TABLE billing.invoices (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  status billing.invoice_status NOT NULL
+);
+
+CREATE TABLE auth.users (
+  id SERIAL PRIMARY KEY,
+  email TEXT NOT NULL
+);
+
+CREATE TABLE users (
+  id SERIAL PRIMARY KEY,
+  nickname TEXT
+);
+"#,
+    )?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      r#"
+const invoiceQuery = sql`
+SELECT invoices.id AS id, status, users.email AS email
+FROM invoices
+JOIN auth.users ON users.id = invoices.user_id
+WHERE invoices.id = $1
+`;
+"#,
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("-g")
+      .arg(format!("--schema-from={}", migration.to_str().unwrap()))
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=1")
+      .arg("--db-user=root")
+      .arg("--pg-search-path=billing,public");
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type InvoiceQueryParams = [number];
+
+export interface IInvoiceQueryResult {
+    email: string;
+    id: number;
+    status: 'draft' | 'paid';
+}
+
+export interface IInvoiceQueryQuery {
+    params: InvoiceQueryParams;
+    result: IInvoiceQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_generate_types_from_mysql_migrations() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    let migration = dir.path().join("migration.sql");
+    fs::create_dir(&dir_path)?;
+
+    fs::write(
+      &migration,
+      r#"
+CREATE TABLE orders (
+  id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  status ENUM('pending', 'shipped') NOT NULL,
+  note TEXT,
+  created_at DATETIME NOT NULL
+);
+ALTER TABLE orders MODIFY COLUMN note VARCHAR(100) NOT NULL;
+"#,
+    )?;
+    fs::write(
+      dir_path.join("index.ts"),
+      "const orderQuery = sql`SELECT id, status, note FROM orders WHERE id = ?`;",
+    )?;
+
+    // EXECUTE
+    run_with_schema_from(&dir_path, "mysql", &migration).success();
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type OrderQueryParams = [number];
+
+export interface IOrderQueryResult {
+    id: number;
+    note: string;
+    status: 'pending' | 'shipped';
+}
+
+export interface IOrderQueryQuery {
+    params: OrderQueryParams;
+    result: IOrderQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+}
@@ -53,7 +53,7 @@ SELECT id, name, level, experience FROM characters WHERE level > 1;
 SELECT id, name, level, race_id, class_id FROM characters WHERE id = $1;
 
 -- @name: getCharactersByRace
--- @db: postgres
+-- @db: default
 SELECT id, name, level FROM characters WHERE race_id = $1 ORDER BY level DESC;
 
 -- @name: createCharacter
@@ -370,13 +370,51 @@ SELECT id, name, FROM characters WHERE; -- Missing table and incomplete WHERE cl
       .assert()
       .failure()
       .stderr(predicates::str::contains(
-        "error: internal compiler error: syntax error at or near \"FROM\"",
+        "error[42601]: syntax error at or near \"FROM\"",
       ))
+      .stderr(predicates::str::contains("invalid.sql:3:18"))
       .stderr(predicates::str::contains("SQLs failed to compile!"));
 
     Ok(())
   }
 
+  #[test]
+  fn test_sql_file_error_points_at_the_line_of_the_file() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let sql_file = dir_path.join("queries.sql");
+
+    let sql_content = r#"-- @name: getItems
+SELECT id FROM items;
+
+-- @name: getUnknownColumn
+-- @db: default
+SELECT id,
+  unknown_column
+FROM items;
+"#;
+
+    fs::write(&sql_file, sql_content)?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=sql")
+      .arg("--config=.sqlxrc.sample.json");
+
+    // ASSERT - annotation lines are left out of the query, yet the error is at the line of the file
+    cmd
+      .assert()
+      .failure()
+      .stderr(predicates::str::contains("column \"unknown_column\" does not exist"))
+      .stderr(predicates::str::contains("queries.sql:7:3"))
+      .stderr(predicates::str::contains("^^^^^^^^^^^^^^"));
+
+    Ok(())
+  }
+
   #[test]
   fn test_sql_file_with_database_connection_annotation() -> Result<(), Box<dyn std::error::Error>> {
     // SETUP
@@ -411,7 +449,71 @@ SELECT * FROM characters WHERE level > $1;
       "DB_PORT": 54321,
       "DB_USER": "postgres",
       "DB_PASS": "postgres",
-      "DB_NAME": "postgres_secondary"
+      "DB_NAME": "postgres"
+    }
+  }
+}
+"#;
+
+    fs::write(&sql_file, sql_content)?;
+    fs::write(&config_file, config_content)?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=sql")
+      .arg(format!("--config={}", config_file.to_str().unwrap()));
+
+    // ASSERT
+    cmd
+      .assert()
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_sql_file_with_file_header_database_connection_annotation() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let sql_file = dir_path.join("secondary_db.sql");
+    let config_file = dir_path.join(".sqlxrc.json");
+
+    // The header annotation routes every query of the file to `secondary`
+    let sql_content = r#"
+-- @db: secondary
+
+-- @name: getCharacters
+SELECT * FROM characters;
+
+-- @name: getCharactersByLevel
+SELECT * FROM characters WHERE level > $1;
+"#;
+
+    // `default` points at a port nothing listens on, so the queries only pass if they run against `secondary`
+    let config_content = r#"
+{
+  "connections": {
+    "default": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 1,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres",
+      "CONNECTION_TIMEOUT": 1
+    },
+    "secondary": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 54321,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres"
     }
   }
 }
@@ -437,6 +539,73 @@ SELECT * FROM characters WHERE level > $1;
     Ok(())
   }
 
+  #[test]
+  fn test_sql_file_query_annotation_overrides_file_header_annotation() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path();
+    let sql_file = dir_path.join("mixed_db.sql");
+    let config_file = dir_path.join(".sqlxrc.json");
+
+    let sql_content = r#"
+-- @db: secondary
+
+-- @name: getCharacters
+SELECT * FROM characters;
+
+-- @name: getCharactersFromUnreachableDB
+-- @db: unreachable
+SELECT * FROM characters WHERE level > $1;
+"#;
+
+    let config_content = r#"
+{
+  "connections": {
+    "default": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 54321,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres"
+    },
+    "secondary": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 54321,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres"
+    },
+    "unreachable": {
+      "DB_TYPE": "postgres",
+      "DB_HOST": "127.0.0.1",
+      "DB_PORT": 1,
+      "DB_USER": "postgres",
+      "DB_PASS": "postgres",
+      "DB_NAME": "postgres",
+      "CONNECTION_TIMEOUT": 1
+    }
+  }
+}
+"#;
+
+    fs::write(&sql_file, sql_content)?;
+    fs::write(&config_file, config_content)?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=sql")
+      .arg(format!("--config={}", config_file.to_str().unwrap()));
+
+    // ASSERT
+    cmd.assert().failure();
+
+    Ok(())
+  }
+
   #[test]
   fn test_sql_file_type_generation_with_custom_path() -> Result<(), Box<dyn std::error::Error>> {
     // SETUP
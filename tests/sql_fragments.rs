@@ -0,0 +1,120 @@
+/// Test suites for SQL fragments declared with the sql tag and composed into other queries of the same module
+#[cfg(test)]
+mod sql_fragments {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_inline_const_fragments_declared_in_the_same_module, TestConfig::new("postgres", true, None, None),
+
+//// TS query ////
+r#"
+const itemColumns = sql`id, name`
+const rareOnly = sql`rarity = 'rare'`
+export const rareItems = sql`${rareOnly} AND inventory_id IS NOT NULL`
+
+const someQuery = sql`
+SELECT ${itemColumns}
+FROM items
+WHERE ${rareItems}
+AND id = ${id}
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [number];
+
+export interface ISomeQueryResult {
+    id: number;
+    name: string;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+
+  #[rustfmt::skip]
+run_test!(should_not_inline_fragments_that_are_not_statically_known, TestConfig::new("postgres", true, None, None),
+
+//// TS query ////
+r#"
+const rareItemIds = sql`SELECT id FROM items WHERE rarity = ${rarity}`
+
+const someQuery = sql`
+SELECT id
+FROM items
+WHERE id = ${rareItemIds}
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type RareItemIdsParams = [string | null];
+
+export interface IRareItemIdsResult {
+    id: number;
+}
+
+export interface IRareItemIdsQuery {
+    params: RareItemIdsParams;
+    result: IRareItemIdsResult;
+}
+
+export type SomeQueryParams = [number];
+
+export interface ISomeQueryResult {
+    id: number;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#);
+
+  #[test]
+  fn should_report_errors_of_fragments_at_the_outer_query() -> Result<(), Box<dyn std::error::Error>> {
+    let ts_content = r#"
+const itemColumns = sql`id, no_such_column`
+
+const someQuery = sql`
+SELECT ${itemColumns}
+FROM items
+`;
+"#;
+
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join("index.ts");
+    let mut temp_file = fs::File::create(file_path)?;
+    writeln!(temp_file, "{ts_content}")?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--config=.sqlxrc.sample.json");
+
+    // ASSERT
+    cmd
+      .assert()
+      .failure()
+      .stderr(predicates::str::contains("column \"no_such_column\" does not exist"))
+      .stderr(predicates::str::contains("index.ts:5:10"))
+      .stderr(predicates::str::contains("SELECT ${itemColumns}"));
+
+    Ok(())
+  }
+}
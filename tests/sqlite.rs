@@ -0,0 +1,260 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[cfg(test)]
+mod sqlite_tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+
+  const SCHEMA: &str = r#"
+CREATE TABLE users (
+  id INTEGER PRIMARY KEY,
+  email TEXT NOT NULL,
+  nickname VARCHAR(20),
+  score REAL,
+  created_at DATETIME NOT NULL
+);
+
+CREATE TABLE posts (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users(id),
+  body TEXT
+);
+"#;
+
+  fn run_sqlite(dir_path: &Path, db_url: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("-g")
+      .arg("--db-type=sqlite")
+      .arg(format!("--db-url={db_url}"));
+
+    cmd.assert()
+  }
+
+  fn read_generated_types(file_path: &Path) -> String {
+    fs::read_to_string(file_path)
+      .unwrap()
+      .strip_generated_header()
+      .replace('\t', "    ")
+  }
+
+  #[test]
+  fn should_generate_types_from_sqlite_database_file() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let db_path = dir.path().join("app.db");
+    rusqlite::Connection::open(&db_path)?.execute_batch(SCHEMA)?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      r#"
+const userQuery = sql`
+SELECT users.id AS id, email, nickname, score, created_at, posts.body AS body
+FROM users
+LEFT JOIN posts ON posts.user_id = users.id
+WHERE users.id = ?
+`;
+"#,
+    )?;
+
+    // EXECUTE
+    run_sqlite(&dir_path, &format!("sqlite://{}", db_path.to_str().unwrap()))
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type UserQueryParams = [number];
+
+export interface IUserQueryResult {
+    body: string | null;
+    created_at: string;
+    email: string;
+    id: number;
+    nickname: string | null;
+    score: number | null;
+}
+
+export interface IUserQueryQuery {
+    params: UserQueryParams;
+    result: IUserQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_turn_template_interpolations_into_sqlite_placeholders() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let db_path = dir.path().join("app.db");
+    rusqlite::Connection::open(&db_path)?.execute_batch(SCHEMA)?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const postQuery = sql`SELECT id FROM posts WHERE body = ${body} AND user_id = ${userId}`;",
+    )?;
+
+    // EXECUTE
+    run_sqlite(&dir_path, db_path.to_str().unwrap())
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type PostQueryParams = [string | null, number];
+
+export interface IPostQueryResult {
+    id: number;
+}
+
+export interface IPostQueryQuery {
+    params: PostQueryParams;
+    result: IPostQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_on_invalid_sqlite_query() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let db_path = dir.path().join("app.db");
+    rusqlite::Connection::open(&db_path)?.execute_batch(SCHEMA)?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const someQuery = sql`SELECT missing FROM users`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_sqlite(&dir_path, db_path.to_str().unwrap())
+      .failure()
+      .stderr(predicates::str::contains("no such column: missing"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_suggest_columns_close_to_an_unknown_column() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let db_path = dir.path().join("app.db");
+    rusqlite::Connection::open(&db_path)?.execute_batch(SCHEMA)?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const someQuery = sql`SELECT nickame FROM users`;",
+    )?;
+
+    // EXECUTE & ASSERT
+    run_sqlite(&dir_path, db_path.to_str().unwrap())
+      .failure()
+      .stderr(predicates::str::contains("error: no such column: nickame"))
+      .stderr(predicates::str::contains("help: did you mean `nickname`?"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_create_in_memory_database_with_init_sql() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    let config_path = dir.path().join(".sqlxrc.json");
+    fs::write(
+      &config_path,
+      serde_json::json!({
+        "generateTypes": { "enabled": true },
+        "connections": {
+          "default": {
+            "DB_TYPE": "sqlite",
+            "DB_URL": "sqlite::memory:",
+            "INIT_SQL": [SCHEMA]
+          }
+        }
+      })
+      .to_string(),
+    )?;
+
+    fs::write(
+      dir_path.join("index.ts"),
+      "const postQuery = sql`SELECT id, body FROM posts WHERE user_id = $1`;",
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg(format!("--config={}", config_path.to_str().unwrap()));
+    cmd.assert().success();
+
+    // ASSERT
+    assert_eq!(
+      read_generated_types(&dir_path.join("index.queries.ts")),
+      r#"export type PostQueryParams = [number];
+
+export interface IPostQueryResult {
+    body: string | null;
+    id: number;
+}
+
+export interface IPostQueryQuery {
+    params: PostQueryParams;
+    result: IPostQueryResult;
+}
+"#
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_fail_on_sqlite_connection_without_db_url() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let dir_path = dir.path().join("src");
+    fs::create_dir(&dir_path)?;
+    fs::write(
+      dir_path.join("index.ts"),
+      "const postQuery = sql`SELECT id FROM posts`;",
+    )?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(dir_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=sqlite");
+
+    // ASSERT
+    cmd.assert().failure().stderr(predicates::str::contains(
+      "Failed to fetch DB URL of the SQLite connection 'default'",
+    ));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,212 @@
+/// Test suites for resolving which tagged templates are the sql tag of sqlx-ts
+#[cfg(test)]
+mod tag_bindings {
+  use assert_cmd::cargo::cargo_bin_cmd;
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  fn run_sqlx_ts(
+    file_name: &str,
+    ext: &str,
+    content: &str,
+  ) -> Result<assert_cmd::assert::Assert, Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let file_path = parent_path.join(file_name);
+    let mut temp_file = fs::File::create(file_path)?;
+    writeln!(temp_file, "{content}")?;
+
+    // EXECUTE
+    let mut cmd = cargo_bin_cmd!("sqlx-ts");
+    cmd
+      .arg(parent_path.to_str().unwrap())
+      .arg(format!("--ext={ext}"))
+      .arg("--config=.sqlxrc.sample.json");
+
+    Ok(cmd.assert())
+  }
+
+  #[test]
+  fn should_only_pick_tags_that_exactly_match_the_import() -> Result<(), Box<dyn std::error::Error>> {
+    let ts_content = r#"
+import { sql as q } from 'sqlx-ts'
+
+const someQuery = q`SELECT id FROM items`
+const notSqlx1 = mysql`NOT A QUERY`
+const notSqlx2 = nosql`NOT A QUERY`
+const notSqlx3 = sqlHelper`NOT A QUERY`
+const notSqlx4 = sql`NOT A QUERY`
+"#;
+
+    // ASSERT
+    run_sqlx_ts("index.ts", "ts", ts_content)?
+      .success()
+      .stdout(predicates::str::contains("Found 1 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_pick_member_tags_of_namespace_and_default_imports() -> Result<(), Box<dyn std::error::Error>> {
+    let ts_content = r#"
+import * as sqlx from 'sqlx-ts'
+import SQLX from 'sqlx-ts'
+
+const someQuery1 = sqlx.sql`SELECT id FROM items`
+const someQuery2 = SQLX.sql`SELECT name FROM items`
+const notSqlx = sqlx.raw`NOT A QUERY`
+"#;
+
+    // ASSERT
+    run_sqlx_ts("index.ts", "ts", ts_content)?
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_pick_tags_bound_by_common_js_require() -> Result<(), Box<dyn std::error::Error>> {
+    let js_content = r#"
+const sqlx = require('sqlx-ts')
+const { sql: query } = require('sqlx-ts')
+
+const someQuery1 = sqlx.sql`SELECT id FROM items`
+const someQuery2 = query`SELECT name FROM items`
+const notSqlx = sql`NOT A QUERY`
+"#;
+
+    // ASSERT
+    run_sqlx_ts("index.js", "js", js_content)?
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_ignore_tags_shadowed_by_local_bindings() -> Result<(), Box<dyn std::error::Error>> {
+    let ts_content = r#"
+import { sql } from 'sqlx-ts'
+
+const someQuery = sql`SELECT id FROM items`
+
+function withParam(sql) {
+  return sql`NOT A QUERY`
+}
+
+const withArrowParam = (sql) => sql`NOT A QUERY`
+
+function withLocal() {
+  const sql = (strings) => strings.join('')
+  return sql`NOT A QUERY`
+}
+
+try {
+  doSomething()
+} catch (sql) {
+  sql`NOT A QUERY`
+}
+
+for (const sql of []) {
+  sql`NOT A QUERY`
+}
+
+class Repository {
+  constructor(private sql: any) {
+    this.query = sql`NOT A QUERY`
+  }
+
+  find() {
+    const findQuery = sql`SELECT name FROM items`
+    return findQuery
+  }
+}
+"#;
+
+    // ASSERT
+    run_sqlx_ts("index.ts", "ts", ts_content)?
+      .success()
+      .stdout(predicates::str::contains("Found 2 SQL queries"))
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn should_ignore_sql_tags_imported_from_other_modules() -> Result<(), Box<dyn std::error::Error>> {
+    let ts_content = r#"
+import { sql } from 'some-other-library'
+
+const notSqlx = sql`NOT A QUERY`
+"#;
+
+    // ASSERT
+    run_sqlx_ts("index.ts", "ts", ts_content)?
+      .success()
+      .stdout(predicates::str::contains("No SQL errors detected!"));
+
+    Ok(())
+  }
+
+  #[rustfmt::skip]
+run_test!(should_pick_tags_configured_for_third_party_libraries, TestConfig::new("postgres", true, None, Some(".sqlxrc.tags.json".to_string())),
+
+//// TS query ////
+r#"
+import { sql } from 'slonik'
+import { Prisma } from '@prisma/client'
+import { db } from './reporting'
+
+const slonikQuery = sql`SELECT id FROM items WHERE name = ${name}`
+const prismaQuery = Prisma.sql`SELECT name FROM items WHERE id = ${id}`
+const reportQuery = db.sql`SELECT title FROM reports WHERE id = ${id}`
+const notATag = db.raw`NOT A QUERY`
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SlonikQueryParams = [string];
+
+export interface ISlonikQueryResult {
+    id: number;
+}
+
+export interface ISlonikQueryQuery {
+    params: SlonikQueryParams;
+    result: ISlonikQueryResult;
+}
+
+export type PrismaQueryParams = [number];
+
+export interface IPrismaQueryResult {
+    name: string;
+}
+
+export interface IPrismaQueryQuery {
+    params: PrismaQueryParams;
+    result: IPrismaQueryResult;
+}
+
+export type ReportQueryParams = [number];
+
+export interface IReportQueryResult {
+    title: string;
+}
+
+export interface IReportQueryQuery {
+    params: ReportQueryParams;
+    result: IReportQueryResult;
+}
+"#);
+}
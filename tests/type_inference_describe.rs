@@ -0,0 +1,64 @@
+/// Test suites for taking param and column types from the database's description of the prepared statement
+/// when the connection is configured with `"TYPE_INFERENCE": "describe"`
+#[cfg(test)]
+mod type_inference_describe {
+  use std::env;
+  use std::fs;
+  use std::io::Write;
+  use tempfile::tempdir;
+
+  use pretty_assertions::assert_eq;
+  use test_utils::test_utils::TSString;
+  use test_utils::{run_test, sandbox::TestConfig};
+
+  #[rustfmt::skip]
+run_test!(should_prefer_described_types_and_keep_nullability, TestConfig::new("postgres", true, None, Some(".sqlxrc.describe.json".to_string())),
+//// TS query ////
+r#"
+const someQuery = sql`
+SELECT id * 2.5 AS scaled, upper(name) AS upper_name, name || $1 AS greeting, experience
+FROM characters
+WHERE level = $2 AND name = lower($3)
+`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [string, number | null, string];
+
+export interface ISomeQueryResult {
+    experience: any | null;
+    greeting: string;
+    scaled: number;
+    upper_name: string;
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#
+);
+
+  #[rustfmt::skip]
+run_test!(should_prefer_described_types_of_insert_values, TestConfig::new("postgres", true, None, Some(".sqlxrc.describe.json".to_string())),
+//// TS query ////
+r#"
+const someQuery = sql`INSERT INTO characters (name, level) VALUES (upper($1), $2)`;
+"#,
+
+//// Generated TS interfaces ////
+r#"
+export type SomeQueryParams = [string, number | null];
+
+export interface ISomeQueryResult {
+    
+}
+
+export interface ISomeQueryQuery {
+    params: SomeQueryParams;
+    result: ISomeQueryResult;
+}
+"#
+);
+}
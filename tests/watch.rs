@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod watch_tests {
+  use std::fs;
+  use std::io::{BufRead, BufReader};
+  use std::path::Path;
+  use std::process::{Child, Command, Stdio};
+  use std::sync::mpsc::{channel, Receiver};
+  use std::thread;
+  use std::time::Duration;
+  use tempfile::tempdir;
+
+  /// Starts `sqlx-ts --watch` along with a channel of the lines it prints
+  fn spawn_watch(parent_path: &Path, args: &[&str]) -> (Child, Receiver<String>) {
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin!("sqlx-ts"))
+      .arg(parent_path.to_str().unwrap())
+      .arg("--ext=ts")
+      .arg("--db-type=postgres")
+      .arg("--db-host=127.0.0.1")
+      .arg("--db-port=54321")
+      .arg("--db-user=postgres")
+      .arg("--db-pass=postgres")
+      .arg("-g")
+      .arg("--watch")
+      .args(args)
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .spawn()
+      .unwrap();
+
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+      for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        if sender.send(line).is_err() {
+          break;
+        }
+      }
+    });
+
+    (child, receiver)
+  }
+
+  /// Lines printed until the watcher goes back to waiting for changes
+  fn wait_for_check(receiver: &Receiver<String>) -> Vec<String> {
+    let mut lines = vec![];
+    loop {
+      let line = receiver
+        .recv_timeout(Duration::from_secs(30))
+        .expect("Timed out waiting for the watcher to check the files");
+      if line.contains("Watching") {
+        return lines;
+      }
+      lines.push(line);
+    }
+  }
+
+  #[test]
+  fn should_recheck_only_the_changed_file() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("second.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT name FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    let (mut child, receiver) = spawn_watch(parent_path, &[]);
+    let first_check = wait_for_check(&receiver);
+    let second_types = fs::read_to_string(parent_path.join("second.queries.ts"))?;
+
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id, name FROM items`;\n",
+    )?;
+    let second_check = wait_for_check(&receiver);
+    child.kill()?;
+
+    // ASSERT
+    assert!(first_check.contains(&"[INFO] Scanned 2 files".to_string()));
+    assert!(second_check.contains(&"[INFO] Scanned 1 files".to_string()));
+    assert!(fs::read_to_string(parent_path.join("first.queries.ts"))?.contains("name: string;"));
+    assert_eq!(fs::read_to_string(parent_path.join("second.queries.ts"))?, second_types);
+    Ok(())
+  }
+
+  #[test]
+  fn should_regenerate_the_single_file_from_every_file() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let generate_path = parent_path.join("types.ts");
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    fs::write(
+      parent_path.join("second.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT name FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    let generate_path_arg = format!("--generate-path={}", generate_path.to_str().unwrap());
+    let (mut child, receiver) = spawn_watch(parent_path, &[&generate_path_arg]);
+    wait_for_check(&receiver);
+
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst renamedQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    wait_for_check(&receiver);
+    child.kill()?;
+
+    // ASSERT
+    let types = fs::read_to_string(&generate_path)?;
+    assert!(types.contains("export interface IRenamedQueryResult"));
+    assert!(types.contains("export interface IOtherQueryResult"));
+    assert!(!types.contains("export interface ISomeQueryResult"));
+    Ok(())
+  }
+
+  #[test]
+  fn should_check_added_files_and_remove_the_types_of_removed_files() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    let (mut child, receiver) = spawn_watch(parent_path, &[]);
+    wait_for_check(&receiver);
+
+    fs::create_dir(parent_path.join("nested"))?;
+    fs::write(
+      parent_path.join("nested").join("second.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst otherQuery = sql`SELECT name FROM items`;\n",
+    )?;
+    let added_check = wait_for_check(&receiver);
+    let second_types_exist = parent_path.join("nested").join("second.queries.ts").exists();
+
+    fs::remove_file(parent_path.join("first.ts"))?;
+    wait_for_check(&receiver);
+    child.kill()?;
+
+    // ASSERT
+    assert!(added_check.contains(&"[INFO] Scanned 1 files".to_string()));
+    assert!(second_types_exist);
+    assert!(!parent_path.join("first.queries.ts").exists());
+    Ok(())
+  }
+
+  #[test]
+  fn should_not_recheck_the_files_it_generated() -> Result<(), Box<dyn std::error::Error>> {
+    // SETUP
+    let dir = tempdir()?;
+    let parent_path = dir.path();
+    let generate_path = parent_path.join("types.ts");
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst someQuery = sql`SELECT id FROM items`;\n",
+    )?;
+
+    // EXECUTE
+    let generate_path_arg = format!("--generate-path={}", generate_path.to_str().unwrap());
+    let (mut child, receiver) = spawn_watch(parent_path, &[&generate_path_arg]);
+    wait_for_check(&receiver);
+
+    fs::write(
+      parent_path.join("first.ts"),
+      "import { sql } from \"sqlx-ts\";\nconst renamedQuery = sql`SELECT id FROM items`;\n",
+    )?;
+    wait_for_check(&receiver);
+    let next_line = receiver.recv_timeout(Duration::from_secs(2));
+    child.kill()?;
+
+    // ASSERT
+    assert!(
+      next_line.is_err(),
+      "Checked again after writing the types: {next_line:?}"
+    );
+    Ok(())
+  }
+}